@@ -0,0 +1,75 @@
+//! Allocations of buffer reuse. Kept apart from the other sampler benches
+//! because it counts allocations through its own global allocator. Run
+//! with `cargo bench --bench allocations`.
+
+use booted::{Sampler, SamplingStrategy};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The system allocator, counting allocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Allocations made by `f`.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+/// Resamples of a large sample: a fresh vector per draw from
+/// `Sampler::sample` against one buffer refilled by `sample_into_buffer`,
+/// as `Bootstrap::run` does per worker. Asserts that the first allocates
+/// per draw and the second only its buffer before timing both.
+fn buffer_reuse(c: &mut Criterion) {
+    const DRAWS: usize = 1_000;
+    let indices: Vec<usize> = (0..100_000).collect();
+    let sampler = SamplingStrategy::Iid;
+    let allocating = || {
+        let mut rng = rand::rng();
+        for _ in 0..DRAWS {
+            black_box(sampler.sample(&indices, &mut rng).unwrap());
+        }
+    };
+    let reused = || {
+        let mut rng = rand::rng();
+        let mut buffer = Vec::with_capacity(indices.len());
+        for _ in 0..DRAWS {
+            sampler
+                .sample_into_buffer(&indices, &mut buffer, &mut rng)
+                .unwrap();
+            black_box(&buffer);
+        }
+    };
+    // Set up the thread-local RNG outside the counts.
+    black_box(rand::rng());
+    let per_draw = allocations(allocating);
+    assert!(per_draw >= DRAWS, "sample allocated {per_draw} times");
+    let once = allocations(reused);
+    assert_eq!(once, 1, "sample_into_buffer allocated {once} times");
+    let mut group = c.benchmark_group("iid_1e3_draws_of_1e5");
+    group.sample_size(20);
+    group.bench_function("sample", |b| b.iter(allocating));
+    group.bench_function("sample_into_buffer", |b| b.iter(reused));
+    group.finish();
+}
+
+criterion_group!(benches, buffer_reuse);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+//! End-to-end `Bootstrap::run` and summary throughput. Run with
+//! `cargo bench --bench bootstrap`.
+
+use booted::summary::calculate_stats;
+use booted::{Bootstrap, Estimator, SamplingStrategy, Summarisable};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::sync::Arc;
+
+const N: usize = 1_000;
+
+fn data() -> Arc<Vec<f64>> {
+    Arc::new((0..N).map(|i| (i as f64 * 0.731).sin() + 2.0).collect())
+}
+
+fn mean_estimator() -> Estimator<f64> {
+    let data = data();
+    Estimator::new((0..N).collect(), move |ind: &[usize]| {
+        Ok(ind.iter().map(|&i| data[i]).sum::<f64>() / ind.len() as f64)
+    })
+}
+
+/// Mean and second and third raw moments, as a small vector statistic.
+fn moments_estimator() -> Estimator<Vec<f64>> {
+    let data = data();
+    Estimator::new((0..N).collect(), move |ind: &[usize]| {
+        let n = ind.len() as f64;
+        let mut m = vec![0.0; 3];
+        for &i in ind {
+            let x = data[i];
+            m[0] += x;
+            m[1] += x * x;
+            m[2] += x * x * x;
+        }
+        Ok(m.into_iter().map(|s| s / n).collect())
+    })
+}
+
+/// Scalar mean under `Iid` against `Block` resampling.
+fn samplers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run_sampler");
+    for (name, sampler) in [
+        ("iid", SamplingStrategy::Iid),
+        ("block_10", SamplingStrategy::Block { block_size: 10 }),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                Bootstrap::new(mean_estimator())
+                    .n_boot(1_000)
+                    .sampler(sampler.clone())
+                    .seed(1)
+                    .run()
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Scalar against `Vec<f64>` statistics, run and summarised.
+fn statistic_types(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run_and_summarise");
+    group.bench_function("f64", |b| {
+        b.iter(|| {
+            Bootstrap::new(mean_estimator())
+                .n_boot(1_000)
+                .seed(1)
+                .run()
+                .unwrap()
+                .summarise()
+        })
+    });
+    group.bench_function("vec_f64_len_3", |b| {
+        b.iter(|| {
+            Bootstrap::new(moments_estimator())
+                .n_boot(1_000)
+                .seed(1)
+                .run()
+                .unwrap()
+                .summarise()
+        })
+    });
+    group.finish();
+}
+
+/// Scaling of a scalar run with the number of replicas.
+fn n_boot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run_n_boot");
+    group.sample_size(20);
+    for n_boot in [100, 1_000, 10_000] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(n_boot),
+            &n_boot,
+            |b, &n_boot| {
+                b.iter(|| {
+                    Bootstrap::new(mean_estimator())
+                        .n_boot(n_boot)
+                        .seed(1)
+                        .run()
+                        .unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Summary statistics of replica vectors of increasing length.
+fn summary_stats(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calculate_stats");
+    for len in [1_000, 100_000] {
+        let replicas: Vec<f64> = (0..len).map(|i| (i as f64 * 0.37).cos()).collect();
+        group.bench_with_input(BenchmarkId::from_parameter(len), &replicas, |b, r| {
+            b.iter(|| calculate_stats(&mut black_box(r.clone())))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, samplers, statistic_types, n_boot, summary_stats);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+//! Sampler throughput. Run with `cargo bench --bench samplers`.
+
+use booted::{Sampler, SamplingStrategy};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const N_BOOT: usize = 1_000_000;
+const N: usize = 16;
+
+/// A million `Iid` draws from a small sample, where per-draw overhead
+/// dominates: fetching the thread-local RNG for every draw, as the samplers
+/// once did internally, against one RNG held across draws, as each worker
+/// in `Bootstrap::run` now holds.
+fn rng_per_draw(c: &mut Criterion) {
+    let indices: Vec<usize> = (0..N).collect();
+    let sampler = SamplingStrategy::Iid;
+    let mut group = c.benchmark_group("iid_1e6_draws");
+    group.sample_size(10);
+    group.bench_function("rng_per_draw", |b| {
+        b.iter(|| {
+            let mut buffer = Vec::with_capacity(N);
+            for _ in 0..N_BOOT {
+                sampler
+                    .sample_into_buffer(&indices, &mut buffer, &mut rand::rng())
+                    .unwrap();
+                black_box(&buffer);
+            }
+        })
+    });
+    group.bench_function("rng_hoisted", |b| {
+        b.iter(|| {
+            let mut rng = rand::rng();
+            let mut buffer = Vec::with_capacity(N);
+            for _ in 0..N_BOOT {
+                sampler
+                    .sample_into_buffer(&indices, &mut buffer, &mut rng)
+                    .unwrap();
+                black_box(&buffer);
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, rng_per_draw);
+criterion_main!(benches);
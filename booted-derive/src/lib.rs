@@ -0,0 +1,176 @@
+//! `#[derive(BootstrapStatistic)]` for `booted`. Use it through the
+//! `derive` feature of `booted` rather than depending on this crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+/// Implement `booted::Arithmetic`, `booted::Elementwise`,
+/// `booted::SameBits` and `booted::SummaryStatistic` for a struct with
+/// named fields, each of which implements all four traits (`f64`,
+/// `Vec<f64>`, another derived struct, …).
+///
+/// Arithmetic acts field by field; `len` is the number of fields. The
+/// summary type is a generated `<Name>Statistics` struct with one public
+/// field of each field's own statistics, under the same name, so a
+/// `struct Fit { slope: f64, intercept: f64 }` is summarised as
+/// `FitStatistics { slope: Statistics, intercept: Statistics }`. The
+/// struct itself must also implement `Clone`, `Debug` and
+/// `serde::Serialize`, as every `SummaryStatistic` does.
+///
+/// `Arithmetic::zero_like` sizes each field from the template's, so `Vec`
+/// fields keep their length through bias correction and the jackknife.
+/// `Arithmetic::zero` only receives a length and builds each field with
+/// `zero(1)`.
+#[proc_macro_derive(BootstrapStatistic)]
+pub fn derive_bootstrap_statistic(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "BootstrapStatistic cannot be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if !fields.named.is_empty() => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "BootstrapStatistic needs a struct with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "BootstrapStatistic can only be derived for structs",
+            ));
+        }
+    };
+    let names: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let count = names.len();
+    let stats = format_ident!("{}Statistics", name);
+    let stats_doc =
+        format!("Per-field statistics of [`{name}`], generated by `BootstrapStatistic`.");
+
+    let binary = |method: proc_macro2::TokenStream| {
+        quote! {
+            Self { #( #names: #method(&self.#names, &other.#names), )* }
+        }
+    };
+    let add = binary(quote!(::booted::Arithmetic::add));
+    let sub = binary(quote!(::booted::Arithmetic::sub));
+    let mul = binary(quote!(::booted::Elementwise::mul));
+
+    Ok(quote! {
+        impl ::booted::Arithmetic for #name {
+            fn add(&self, other: &Self) -> Self {
+                #add
+            }
+            fn sub(&self, other: &Self) -> Self {
+                #sub
+            }
+            fn scale(&self, factor: f64) -> Self {
+                Self { #( #names: ::booted::Arithmetic::scale(&self.#names, factor), )* }
+            }
+            fn zero(_len: usize) -> Self {
+                Self { #( #names: <#types as ::booted::Arithmetic>::zero(1), )* }
+            }
+            fn zero_like(&self) -> Self {
+                Self { #( #names: ::booted::Arithmetic::zero_like(&self.#names), )* }
+            }
+            fn len(&self) -> usize {
+                #count
+            }
+            fn add_assign(&mut self, other: &Self) {
+                #( ::booted::Arithmetic::add_assign(&mut self.#names, &other.#names); )*
+            }
+        }
+
+        impl ::booted::SameBits for #name {
+            fn same_bits(&self, other: &Self) -> bool {
+                true #( && ::booted::SameBits::same_bits(&self.#names, &other.#names) )*
+            }
+        }
+
+        impl ::booted::Elementwise for #name {
+            fn mul(&self, other: &Self) -> Self {
+                #mul
+            }
+            fn sqrt(&self) -> Self {
+                Self { #( #names: ::booted::Elementwise::sqrt(&self.#names), )* }
+            }
+        }
+
+        #[doc = #stats_doc]
+        #[derive(Debug, Clone, ::booted::__serde::Serialize)]
+        #[serde(crate = "::booted::__serde")]
+        #vis struct #stats {
+            #( pub #names: <#types as ::booted::SummaryStatistic>::Stats, )*
+        }
+
+        impl ::booted::SummaryStatistic for #name {
+            type Stats = #stats;
+
+            fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+                Self::compute_stats_with(samples, &::booted::SummaryOptions::default())
+            }
+
+            fn compute_stats_with(
+                samples: &[Self],
+                options: &::booted::SummaryOptions,
+            ) -> Option<Self::Stats> {
+                Some(#stats {
+                    #(
+                        #names: <#types as ::booted::SummaryStatistic>::compute_stats_with(
+                            &samples.iter().map(|s| s.#names.clone()).collect::<Vec<_>>(),
+                            options,
+                        )?,
+                    )*
+                })
+            }
+
+            fn compute_stats_from(
+                result: &::booted::BootstrapResult<Self>,
+                options: &::booted::SummaryOptions,
+            ) -> Option<Self::Stats> {
+                Some(#stats {
+                    #(
+                        #names: <#types as ::booted::SummaryStatistic>::compute_stats_from(
+                            &result.map(|s| s.#names),
+                            options,
+                        )?,
+                    )*
+                })
+            }
+
+            fn standard_error(stats: &Self::Stats) -> Self {
+                Self {
+                    #( #names: <#types as ::booted::SummaryStatistic>::standard_error(&stats.#names), )*
+                }
+            }
+
+            fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+                Some(Self {
+                    #(
+                        #names: <#types as ::booted::SummaryStatistic>::bias(
+                            &stats.#names,
+                            &central.#names,
+                        )?,
+                    )*
+                })
+            }
+        }
+    })
+}
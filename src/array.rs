@@ -0,0 +1,162 @@
+//! `ndarray::Array1<f64>` statistics, behind the `ndarray` feature.
+
+use crate::bootstrap::{Arithmetic, BootstrapResult, Elementwise, SameBits};
+use crate::export::CsvRow;
+use crate::summary::{
+    SummaryOptions, SummaryStatistic, VectorStatistics, vector_stats_from_columns,
+};
+use ndarray::{Array1, Array2, Axis};
+
+impl SameBits for Array1<f64> {
+    fn same_bits(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.same_bits(b))
+    }
+}
+
+impl Arithmetic for Array1<f64> {
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+    fn scale(&self, factor: f64) -> Self {
+        self * factor
+    }
+    fn zero(len: usize) -> Self {
+        Array1::zeros(len)
+    }
+    fn len(&self) -> usize {
+        Array1::len(self)
+    }
+    fn add_assign(&mut self, other: &Self) {
+        *self += other;
+    }
+}
+
+impl Elementwise for Array1<f64> {
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+    fn sqrt(&self) -> Self {
+        self.mapv(f64::sqrt)
+    }
+}
+
+impl CsvRow for Array1<f64> {
+    fn fields(&self) -> Vec<f64> {
+        self.to_vec()
+    }
+}
+
+/// Replicas stacked into a replica × component matrix and split into its
+/// columns; `None` without replicas or when their lengths differ.
+fn stacked_columns(rows: &[Array1<f64>]) -> Option<Vec<Vec<f64>>> {
+    let views: Vec<_> = rows.iter().map(Array1::view).collect();
+    let stacked: Array2<f64> = ndarray::stack(Axis(0), &views).ok()?;
+    Some(stacked.columns().into_iter().map(|c| c.to_vec()).collect())
+}
+
+/// Same statistics as for `Vec<f64>`. The replicas are stacked into a
+/// replica × component matrix whose columns are summarised directly.
+impl SummaryStatistic for Array1<f64> {
+    type Stats = VectorStatistics;
+
+    fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+        Self::compute_stats_with(samples, &SummaryOptions::default())
+    }
+
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
+        vector_stats_from_columns(stacked_columns(samples)?, options, None, None)
+    }
+
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        let central = result.central.as_ref().ok().map(|c| c.to_vec());
+        let jackknife = result.jackknife.as_deref().and_then(stacked_columns);
+        vector_stats_from_columns(
+            stacked_columns(&result.samples)?,
+            options,
+            central.as_deref(),
+            jackknife.as_deref(),
+        )
+    }
+
+    fn standard_error(stats: &Self::Stats) -> Self {
+        stats.iter().map(|s| s.stddev).collect()
+    }
+
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        Vec::<f64>::bias(stats, &central.to_vec()).map(Array1::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::{Bootstrap, Estimator};
+    use crate::summary::{BootstrapSummary, Summarisable};
+    use ndarray::array;
+
+    #[test]
+    fn array_statistics_match_vec_statistics() {
+        let data = array![
+            [1.0, 10.0],
+            [2.0, 14.0],
+            [4.0, 11.0],
+            [3.0, 19.0],
+            [5.0, 12.0]
+        ];
+        let rows = data.nrows();
+        let column_means = move |ind: &[usize]| -> Array1<f64> {
+            ind.iter()
+                .map(|&i| data.row(i).to_owned())
+                .fold(Array1::zeros(2), |a, r| a + r)
+                / ind.len() as f64
+        };
+        let by_array = {
+            let f = column_means.clone();
+            Estimator::new((0..rows).collect(), move |ind: &[usize]| Ok(f(ind)))
+        };
+        let by_vec = Estimator::new((0..rows).collect(), move |ind: &[usize]| {
+            Ok(column_means(ind).to_vec())
+        });
+        let a: BootstrapSummary<Array1<f64>> = Bootstrap::new(by_array)
+            .n_boot(300)
+            .seed(4)
+            .bca(true)
+            .run()
+            .unwrap()
+            .summarise();
+        let v: BootstrapSummary<Vec<f64>> = Bootstrap::new(by_vec)
+            .n_boot(300)
+            .seed(4)
+            .bca(true)
+            .run()
+            .unwrap()
+            .summarise();
+        let (a, v) = (a.statistics.unwrap(), v.statistics.unwrap());
+        assert_eq!(a.len(), 2);
+        for j in 0..2 {
+            assert_eq!(a[j].mean, v[j].mean);
+            assert_eq!(a[j].ci_95_basic.unwrap().low, v[j].ci_95_basic.unwrap().low);
+            assert_eq!(a[j].ci_95_bca.unwrap().high, v[j].ci_95_bca.unwrap().high);
+        }
+        assert_eq!(a.covariance(), v.covariance());
+        assert_eq!(Array1::<f64>::standard_error(&a).len(), 2);
+    }
+
+    #[test]
+    fn array_arithmetic_is_elementwise() {
+        let (x, y) = (array![1.0, 4.0], array![3.0, 5.0]);
+        assert_eq!(x.add(&y), array![4.0, 9.0]);
+        assert_eq!(x.mul(&y), array![3.0, 20.0]);
+        assert_eq!(Elementwise::sqrt(&x), array![1.0, 2.0]);
+        assert_eq!(x.scale(0.5), array![0.5, 2.0]);
+        let mut z = Array1::<f64>::zero(2);
+        z.add_assign(&x);
+        assert_eq!(z, x);
+    }
+}
@@ -0,0 +1,83 @@
+//! Building blocks of the bias-corrected and accelerated (BCa) interval,
+//! for assembling BCa-style intervals outside [`BootstrapResult`]'s own.
+//!
+//! [`BootstrapResult`]: crate::BootstrapResult
+
+use crate::bootstrap::Estimator;
+use crate::error::BootstrapError;
+use crate::jackknife::leave_out_values;
+
+/// Acceleration constant `â` of the BCa interval from leave-one-out
+/// jackknife replicates of `estimator` over its own indices.
+///
+/// # Errors
+///
+/// If any leave-one-out replicate fails, or there are fewer than two
+/// indices.
+pub fn acceleration(estimator: &Estimator<f64>) -> Result<f64, BootstrapError> {
+    let replicates = leave_out_values(estimator, 1, true)?;
+    Ok(acceleration_from_jackknife(&replicates))
+}
+
+/// Acceleration constant from jackknife values `θ₍ᵢ₎` with mean `θ̄`:
+/// `â = Σ(θ̄ − θ₍ᵢ₎)³ / (6 [Σ(θ̄ − θ₍ᵢ₎)²]^{3/2})`. Zero when the values are
+/// all equal (or there are none), where the skewness it measures is
+/// undefined.
+pub fn acceleration_from_jackknife(jackknife: &[f64]) -> f64 {
+    if jackknife.is_empty() {
+        return 0.0;
+    }
+    let mean = jackknife.iter().sum::<f64>() / jackknife.len() as f64;
+    let (num, den) = jackknife.iter().fold((0.0, 0.0), |(num, den), x| {
+        let d = mean - x;
+        (num + d.powi(3), den + d.powi(2))
+    });
+    if den > 0.0 {
+        num / (6.0 * den.powf(1.5))
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::mean_estimator;
+
+    #[test]
+    fn acceleration_of_skewed_mean() {
+        // Jackknife values of the mean are `θ̄ − (xᵢ − x̄)/(n − 1)`, so `â`
+        // is the sample skewness `Σd³/(Σd²)^{3/2}` over 6, with `d = xᵢ − x̄`.
+        let data: Vec<f64> = (1..=20).map(|i| f64::from(i).powi(2)).collect();
+        let mean = data.iter().sum::<f64>() / 20.0;
+        let (s3, s2) = data.iter().fold((0.0, 0.0), |(s3, s2), x| {
+            let d = x - mean;
+            (s3 + d.powi(3), s2 + d.powi(2))
+        });
+        let expected = s3 / (6.0 * s2.powf(1.5));
+        let est = mean_estimator(&data);
+        let a = acceleration(&est).unwrap();
+        assert!(a > 0.0);
+        assert!((a - expected).abs() < 1e-12, "{a} vs {expected}");
+    }
+
+    #[test]
+    fn constant_jackknife_has_zero_acceleration() {
+        let est = Estimator::new((0..10).collect(), |_: &[usize]| Ok(3.0));
+        assert_eq!(acceleration(&est).unwrap(), 0.0);
+        assert_eq!(acceleration_from_jackknife(&[]), 0.0);
+        let single = Estimator::new(vec![0], |_: &[usize]| Ok(1.0));
+        assert!(acceleration(&single).is_err());
+    }
+
+    #[test]
+    fn acceleration_skips_the_full_sample_estimate() {
+        let est = Estimator::new((0..10).collect(), |ind: &[usize]| {
+            if ind.len() == 10 {
+                return Err(crate::EstimatorError::new("full sample"));
+            }
+            Ok(ind.iter().map(|&i| (i * i) as f64).sum::<f64>())
+        });
+        assert!(acceleration(&est).unwrap().is_finite());
+    }
+}
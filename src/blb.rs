@@ -0,0 +1,220 @@
+//! Bag of little bootstraps (Kleiner et al., 2014) for data too large to
+//! resample in full.
+
+use crate::bootstrap::{EstimatorResult, replica_rng};
+use crate::error::BootstrapError;
+use crate::summary::{ConfidenceInterval, calculate_stats};
+use rand::Rng;
+use rand::seq::index;
+use rand_distr::{Binomial, Distribution};
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// Bag of little bootstraps for `estimator` on a population of `n` points.
+///
+/// Draws `n_subsets` subsets of `subset_size` points without replacement.
+/// Within each, every replica gives the subset's points multinomial counts
+/// summing to the full `n`, and calls `estimator(distinct, counts)` with
+/// the points drawn at least once (ascending) and their counts, the same
+/// form as [`Estimator::from_weights`](crate::Estimator::from_weights), so
+/// one count-aware statistic serves both. The estimator therefore only
+/// ever touches `subset_size` distinct points, yet each replica mimics a
+/// full-size resample, so the subset's replica spread estimates the
+/// full-data standard error. The per-subset standard errors and 95%
+/// intervals are then averaged. `subset_size` around `n^0.6`–`n^0.8` is
+/// typical; subsets run in parallel.
+///
+/// Returns [`BootstrapError::InvalidInput`] if `subset_size` is zero or
+/// exceeds `n`, and [`BootstrapError::NoValidSamples`] if every replica of
+/// every subset failed.
+pub fn blb<F>(
+    n: usize,
+    n_subsets: usize,
+    subset_size: usize,
+    n_boot: usize,
+    estimator: F,
+    seed: Option<u64>,
+) -> Result<BlbResult, BootstrapError>
+where
+    F: Fn(&[usize], &[f64]) -> EstimatorResult<f64> + Sync,
+{
+    if n == 0 {
+        return Err(BootstrapError::EmptyIndices);
+    }
+    if subset_size == 0 || subset_size > n {
+        return Err(BootstrapError::InvalidInput(format!(
+            "subset size must lie in 1..={n}, got {subset_size}"
+        )));
+    }
+    let seed = seed.unwrap_or_else(rand::random);
+
+    let subsets: Vec<(f64, ConfidenceInterval)> = (0..n_subsets)
+        .into_par_iter()
+        .filter_map(|s| {
+            let mut rng = replica_rng(seed, s as u64);
+            let mut subset = index::sample(&mut rng, n, subset_size).into_vec();
+            subset.sort_unstable();
+            let (mut distinct, mut counts) = (
+                Vec::with_capacity(subset_size),
+                Vec::with_capacity(subset_size),
+            );
+            let mut replicas: Vec<f64> = (0..n_boot)
+                .filter_map(|_| {
+                    multinomial_counts(&subset, n, &mut distinct, &mut counts, &mut rng);
+                    estimator(&distinct, &counts).ok()
+                })
+                .collect();
+            let stats = calculate_stats(&mut replicas)?;
+            Some((stats.stddev, stats.ci_95))
+        })
+        .collect();
+
+    if subsets.is_empty() {
+        return Err(BootstrapError::NoValidSamples {
+            n_boot: n_subsets * n_boot,
+            failed_samples: n_subsets * n_boot,
+        });
+    }
+    let k = subsets.len() as f64;
+    let std_error = subsets.iter().map(|s| s.0).sum::<f64>() / k;
+    let ci_95 = ConfidenceInterval {
+        low: subsets.iter().map(|s| s.1.low).sum::<f64>() / k,
+        high: subsets.iter().map(|s| s.1.high).sum::<f64>() / k,
+    };
+    Ok(BlbResult {
+        n_subsets: subsets.len(),
+        std_error,
+        ci_95,
+        subset_std_errors: subsets.into_iter().map(|s| s.0).collect(),
+    })
+}
+
+/// Counts for `n` draws spread uniformly over `points`, as a chain of
+/// binomials: point `j` takes `Binomial(remaining, 1/(b − j))` of the
+/// draws left, which is exactly Multinomial(n, 1/b) in `O(b)` instead of
+/// `O(n)`. Points drawn zero times are left out of `distinct`/`counts`.
+fn multinomial_counts<R: Rng + ?Sized>(
+    points: &[usize],
+    n: usize,
+    distinct: &mut Vec<usize>,
+    counts: &mut Vec<f64>,
+    rng: &mut R,
+) {
+    distinct.clear();
+    counts.clear();
+    let b = points.len();
+    let mut remaining = n as u64;
+    for (j, &point) in points.iter().enumerate() {
+        let count = if j + 1 == b {
+            remaining
+        } else {
+            Binomial::new(remaining, 1.0 / (b - j) as f64)
+                .expect("probability lies in (0, 1]")
+                .sample(rng)
+        };
+        if count > 0 {
+            distinct.push(point);
+            counts.push(count as f64);
+            remaining -= count;
+        }
+    }
+}
+
+/// Output of [`blb`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct BlbResult {
+    /// Subsets that produced statistics (all of them unless every replica
+    /// of some subset failed).
+    pub n_subsets: usize,
+    /// Mean over subsets of the replica standard deviation.
+    pub std_error: f64,
+    /// Mean over subsets of the 95% percentile interval endpoints.
+    pub ci_95: ConfidenceInterval,
+    pub subset_std_errors: Vec<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::{Bootstrap, Estimator};
+    use crate::summary::{BootstrapSummary, Summarisable};
+    use crate::test_util::mean_estimator;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    use rand_distr::{Distribution, Normal};
+
+    #[test]
+    fn blb_std_error_matches_full_bootstrap() {
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let data: Vec<f64> = (0..5000).map(|_| normal.sample(&mut rng)).collect();
+        let values = data.clone();
+        let weighted_mean = move |ind: &[usize], w: &[f64]| {
+            Ok(ind.iter().zip(w).map(|(&i, w)| values[i] * w).sum::<f64>() / w.iter().sum::<f64>())
+        };
+        let result = blb(data.len(), 10, 400, 200, &weighted_mean, Some(2)).unwrap();
+        assert_eq!(result.n_subsets, 10);
+
+        let est = mean_estimator(&data);
+        let full: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(500)
+            .seed(2)
+            .run()
+            .unwrap()
+            .summarise();
+        let full_se = full.statistics.unwrap().stddev;
+        assert!(
+            (result.std_error / full_se - 1.0).abs() < 0.15,
+            "{} {full_se}",
+            result.std_error
+        );
+        assert!(result.ci_95.low < 3.0 && 3.0 < result.ci_95.high);
+
+        // The same count-aware statistic drives a full-data bootstrap.
+        let full_weighted = Bootstrap::new(Estimator::from_weights(
+            (0..data.len()).collect(),
+            weighted_mean,
+        ))
+        .n_boot(500)
+        .seed(2)
+        .run()
+        .unwrap();
+        let weighted_se = full_weighted.summarise().statistics.unwrap().stddev;
+        assert!((weighted_se / full_se - 1.0).abs() < 0.15);
+    }
+
+    #[test]
+    fn multinomial_counts_sum_to_n() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let (mut distinct, mut counts) = (Vec::new(), Vec::new());
+        let points = [2, 5, 9, 11];
+        let mut totals = [0.0; 4];
+        for _ in 0..2000 {
+            multinomial_counts(&points, 100, &mut distinct, &mut counts, &mut rng);
+            assert_eq!(counts.iter().sum::<f64>(), 100.0);
+            for (d, c) in distinct.iter().zip(&counts) {
+                totals[points.iter().position(|p| p == d).unwrap()] += c;
+            }
+        }
+        for t in totals {
+            assert!((t / 2000.0 - 25.0).abs() < 0.5, "{totals:?}");
+        }
+    }
+
+    #[test]
+    fn blb_rejects_oversized_subsets_and_reports_total_failure() {
+        let fails = |_: &[usize], _: &[f64]| Err(crate::EstimatorError::new("always"));
+        assert!(matches!(
+            blb(10, 2, 11, 5, fails, Some(1)),
+            Err(BootstrapError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            blb(10, 2, 5, 5, fails, Some(1)),
+            Err(BootstrapError::NoValidSamples {
+                n_boot: 10,
+                failed_samples: 10
+            })
+        ));
+    }
+}
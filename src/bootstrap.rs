@@ -1,11 +1,22 @@
-use crate::samplers::{Sampler, SamplerError, SamplingStrategy};
+pub use crate::error::BootstrapError;
+use crate::jackknife::leave_out_values;
+use crate::samplers::{Sampler, SamplerError, SamplingStrategy, Strata, weighted_draw};
+use crate::streaming::{
+    Accumulator, Moments, QuantileSketch, StreamingStatistic, StreamingSummary,
+};
+use crate::summary::{BootstrapSummary, Summarisable, SummaryStatistic, normal_quantile};
+use rand::distr::weighted::WeightedIndex;
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 // -----------------------------------------------------------------------
 // Errors
@@ -14,7 +25,7 @@ use std::sync::Arc;
 /// Reason for a single estimator invocation failing on a bootstrap replica
 /// (or on the central sample). Kept lightweight so it can be tallied by
 /// reason without heap-allocation churn.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct EstimatorError {
     pub reason: Cow<'static, str>,
 }
@@ -37,25 +48,96 @@ impl std::error::Error for EstimatorError {}
 
 pub type EstimatorResult<T> = Result<T, EstimatorError>;
 
-/// Errors returned by `Bootstrap::run` itself (as opposed to individual
-/// replicas). A sampler that cannot produce any valid draw at all is a
-/// configuration error, not a per-replica failure.
-#[derive(Debug, Clone)]
-pub enum BootstrapError {
-    Sampler(SamplerError),
-    EmptyIndices,
+/// Non-fatal diagnostics attached to a `BootstrapResult`. The run completed,
+/// but the numbers derived from it deserve a second look.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Warning {
+    /// `n_boot` is below [`recommended_n_boot`] for the requested interval
+    /// level, so the interval tails rest on very few replicas.
+    LowNBoot {
+        n_boot: usize,
+        recommended: usize,
+        level: f64,
+    },
+    /// Two applications of the estimator to the full sample disagreed, so
+    /// it depends on something besides the indices (typically an unseeded
+    /// RNG) and replicas mix resampling noise with its own. Only checked
+    /// with [`Bootstrap::check_determinism`].
+    NonDeterministic,
 }
 
-impl fmt::Display for BootstrapError {
+impl fmt::Display for Warning {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            BootstrapError::Sampler(e) => write!(f, "sampler configuration error: {e}"),
-            BootstrapError::EmptyIndices => f.write_str("estimator has no indices to resample"),
+            Warning::LowNBoot {
+                n_boot,
+                recommended,
+                level,
+            } => write!(
+                f,
+                "n_boot = {n_boot} is below the recommended {recommended} for {level} intervals"
+            ),
+            Warning::NonDeterministic => write!(
+                f,
+                "estimator gave different results on the same indices; it is not deterministic"
+            ),
         }
     }
 }
 
-impl std::error::Error for BootstrapError {}
+/// Smallest `n_boot` giving roughly `tail_replicas` replicas beyond the
+/// endpoints of a two-sided interval at `level`, i.e.
+/// `⌈tail_replicas / (1 − level)⌉`: with the default of ten
+/// ([`Bootstrap::tail_replicas`]), 200 at 95% and 1000 at 99%.
+///
+/// # Panics
+///
+/// If `level` is not strictly between 0 and 1.
+pub fn recommended_n_boot(level: f64, tail_replicas: usize) -> usize {
+    assert!(
+        level > 0.0 && level < 1.0,
+        "interval level must lie in (0, 1)"
+    );
+    (tail_replicas as f64 / (1.0 - level)).ceil() as usize
+}
+
+/// `n_boot` needed for the endpoints of the percentile interval at
+/// `level` to have Monte Carlo standard error `target_mc_se`, judged from
+/// a pilot run.
+///
+/// The endpoint at probability `p = (1 − level)/2` has MC standard error
+/// `√(p(1 − p)/n_boot) / f(q_p)`, with `f` the replica density at the
+/// endpoint. Taking the replicas as roughly normal with the pilot's
+/// standard deviation `σ` gives `f(q_p) = φ(z_p)/σ`, so the requirement
+/// grows as `(σ / target)²`. Heavily skewed or discrete replica
+/// distributions make this an underestimate; rerun it on the bigger run.
+///
+/// # Panics
+///
+/// If `target_mc_se` is not positive, `level` is not strictly between 0
+/// and 1, or the pilot has fewer than two replicas.
+pub fn n_boot_for_mc_se(
+    target_mc_se: f64,
+    level: f64,
+    pilot_result: &BootstrapResult<f64>,
+) -> usize {
+    assert!(target_mc_se > 0.0, "target precision must be positive");
+    assert!(
+        level > 0.0 && level < 1.0,
+        "interval level must lie in (0, 1)"
+    );
+    let samples = &pilot_result.samples;
+    assert!(samples.len() >= 2, "pilot run needs at least two replicas");
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let sd = (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let p = (1.0 - level) / 2.0;
+    let z = normal_quantile(p);
+    let density = (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt();
+    let n_boot = p * (1.0 - p) * (sd / (density * target_mc_se)).powi(2);
+    (n_boot.ceil() as usize).max(1)
+}
 
 // -----------------------------------------------------------------------
 // Arithmetic (needed only by bias correction, aggregated tallies)
@@ -68,7 +150,16 @@ pub trait Arithmetic: Sized + Clone + Send + Sync + 'static {
     fn add(&self, other: &Self) -> Self;
     fn sub(&self, other: &Self) -> Self;
     fn scale(&self, factor: f64) -> Self;
+    /// The additive identity shaped like a value of length `len`. Callers
+    /// only use it as the start of an `add_assign` sum, so sparse types
+    /// whose missing entries already read as zero may ignore `len`.
     fn zero(len: usize) -> Self;
+    /// The additive identity shaped like `self`. Defaults to
+    /// `zero(self.len())`; types with parts of their own length, such as
+    /// tuples, size each part from the matching part of `self`.
+    fn zero_like(&self) -> Self {
+        Self::zero(self.len())
+    }
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool {
         self.len() == 0
@@ -76,6 +167,15 @@ pub trait Arithmetic: Sized + Clone + Send + Sync + 'static {
     fn add_assign(&mut self, other: &Self);
 }
 
+/// Elementwise products and square roots, on top of [`Arithmetic`], for
+/// procedures that form variances from replicates, such as the
+/// [`Jackknife`](crate::jackknife::Jackknife). Kept apart so types used
+/// only for bias correction need not provide them.
+pub trait Elementwise: Arithmetic {
+    fn mul(&self, other: &Self) -> Self;
+    fn sqrt(&self) -> Self;
+}
+
 impl Arithmetic for f64 {
     fn add(&self, other: &Self) -> Self {
         *self + *other
@@ -97,6 +197,77 @@ impl Arithmetic for f64 {
     }
 }
 
+impl Elementwise for f64 {
+    fn mul(&self, other: &Self) -> Self {
+        *self * *other
+    }
+    fn sqrt(&self) -> Self {
+        f64::sqrt(*self)
+    }
+}
+
+impl Arithmetic for f32 {
+    fn add(&self, other: &Self) -> Self {
+        *self + *other
+    }
+    fn sub(&self, other: &Self) -> Self {
+        *self - *other
+    }
+    fn scale(&self, factor: f64) -> Self {
+        (f64::from(*self) * factor) as f32
+    }
+    fn zero(_len: usize) -> Self {
+        0.0
+    }
+    fn len(&self) -> usize {
+        1
+    }
+    fn add_assign(&mut self, other: &Self) {
+        *self += *other;
+    }
+}
+
+impl Elementwise for f32 {
+    fn mul(&self, other: &Self) -> Self {
+        *self * *other
+    }
+    fn sqrt(&self) -> Self {
+        f32::sqrt(*self)
+    }
+}
+
+impl Arithmetic for Vec<f32> {
+    fn add(&self, other: &Self) -> Self {
+        self.iter().zip(other).map(|(a, b)| a + b).collect()
+    }
+    fn sub(&self, other: &Self) -> Self {
+        self.iter().zip(other).map(|(a, b)| a - b).collect()
+    }
+    fn scale(&self, factor: f64) -> Self {
+        self.iter().map(|a| a.scale(factor)).collect()
+    }
+    fn zero(len: usize) -> Self {
+        vec![0.0; len]
+    }
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+    fn add_assign(&mut self, other: &Self) {
+        for (a, b) in self.iter_mut().zip(other) {
+            *a += b;
+        }
+    }
+}
+
+impl Elementwise for Vec<f32> {
+    fn mul(&self, other: &Self) -> Self {
+        self.iter().zip(other).map(|(a, b)| a * b).collect()
+    }
+    fn sqrt(&self) -> Self {
+        self.iter().map(|a| a.sqrt()).collect()
+    }
+}
+
 impl Arithmetic for Vec<f64> {
     fn add(&self, other: &Self) -> Self {
         self.iter().zip(other).map(|(a, b)| a + b).collect()
@@ -120,11 +291,197 @@ impl Arithmetic for Vec<f64> {
     }
 }
 
+impl Elementwise for Vec<f64> {
+    fn mul(&self, other: &Self) -> Self {
+        self.iter().zip(other).map(|(a, b)| a * b).collect()
+    }
+    fn sqrt(&self) -> Self {
+        self.iter().map(|a| a.sqrt()).collect()
+    }
+}
+
+/// Keyed statistics, such as per-category means where the categories
+/// present vary between resamples. Operations align on keys, treating a
+/// key missing from one side as zero, so the result holds the union of
+/// keys; `zero` is the empty map and `len` the number of keys. Summaries
+/// of maps read missing keys as zero too, so a key's summary `mean` is
+/// the mean of the maps under these operations.
+impl<K: Ord + Clone + Send + Sync + 'static> Arithmetic for BTreeMap<K, f64> {
+    fn add(&self, other: &Self) -> Self {
+        union_with(self, other, |a, b| a + b)
+    }
+    fn sub(&self, other: &Self) -> Self {
+        union_with(self, other, |a, b| a - b)
+    }
+    fn scale(&self, factor: f64) -> Self {
+        self.iter().map(|(k, v)| (k.clone(), v * factor)).collect()
+    }
+    fn zero(_len: usize) -> Self {
+        BTreeMap::new()
+    }
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+    fn add_assign(&mut self, other: &Self) {
+        for (k, v) in other {
+            *self.entry(k.clone()).or_insert(0.0) += v;
+        }
+    }
+}
+
+impl<K: Ord + Clone + Send + Sync + 'static> Elementwise for BTreeMap<K, f64> {
+    fn mul(&self, other: &Self) -> Self {
+        union_with(self, other, |a, b| a * b)
+    }
+    fn sqrt(&self) -> Self {
+        self.iter().map(|(k, v)| (k.clone(), v.sqrt())).collect()
+    }
+}
+
+fn union_with<K: Ord + Clone>(
+    a: &BTreeMap<K, f64>,
+    b: &BTreeMap<K, f64>,
+    f: impl Fn(f64, f64) -> f64,
+) -> BTreeMap<K, f64> {
+    let mut out: BTreeMap<K, f64> = a
+        .iter()
+        .map(|(k, &x)| (k.clone(), f(x, b.get(k).copied().unwrap_or(0.0))))
+        .collect();
+    for (k, &y) in b {
+        out.entry(k.clone()).or_insert_with(|| f(0.0, y));
+    }
+    out
+}
+
+/// Tuples act componentwise, like a fixed-length vector whose length is
+/// checked at compile time; `len` is the arity. As with derived structs,
+/// `zero` builds each component with `zero(1)`, so only `zero_like` gives
+/// `Vec` or array components their length.
+macro_rules! tuple_arithmetic {
+    ($($T:ident . $i:tt),+) => {
+        impl<$($T: Arithmetic),+> Arithmetic for ($($T,)+) {
+            fn add(&self, other: &Self) -> Self {
+                ($(self.$i.add(&other.$i),)+)
+            }
+            fn sub(&self, other: &Self) -> Self {
+                ($(self.$i.sub(&other.$i),)+)
+            }
+            fn scale(&self, factor: f64) -> Self {
+                ($(self.$i.scale(factor),)+)
+            }
+            fn zero(_len: usize) -> Self {
+                ($($T::zero(1),)+)
+            }
+            fn zero_like(&self) -> Self {
+                ($(self.$i.zero_like(),)+)
+            }
+            fn len(&self) -> usize {
+                [$($i),+].len()
+            }
+            fn add_assign(&mut self, other: &Self) {
+                $(self.$i.add_assign(&other.$i);)+
+            }
+        }
+
+        impl<$($T: Elementwise),+> Elementwise for ($($T,)+) {
+            fn mul(&self, other: &Self) -> Self {
+                ($(self.$i.mul(&other.$i),)+)
+            }
+            fn sqrt(&self) -> Self {
+                ($(self.$i.sqrt(),)+)
+            }
+        }
+
+        impl<$($T: SameBits),+> SameBits for ($($T,)+) {
+            fn same_bits(&self, other: &Self) -> bool {
+                $(self.$i.same_bits(&other.$i))&&+
+            }
+        }
+    };
+}
+
+tuple_arithmetic!(A.0, B.1);
+tuple_arithmetic!(A.0, B.1, C.2);
+tuple_arithmetic!(A.0, B.1, C.2, D.3);
+tuple_arithmetic!(A.0, B.1, C.2, D.3, E.4);
+tuple_arithmetic!(A.0, B.1, C.2, D.3, E.4, F.5);
+
+/// Equality for [`Bootstrap::check_determinism`]: `==`, except that
+/// floats compare by their bits, so a NaN matches the NaN a second
+/// evaluation reproduces. Containers compare element by element.
+pub trait SameBits {
+    fn same_bits(&self, other: &Self) -> bool;
+}
+
+impl SameBits for f64 {
+    fn same_bits(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl SameBits for f32 {
+    fn same_bits(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+macro_rules! same_bits_by_eq {
+    ($($T:ty),+) => {
+        $(impl SameBits for $T {
+            fn same_bits(&self, other: &Self) -> bool {
+                self == other
+            }
+        })+
+    };
+}
+
+same_bits_by_eq!(
+    bool, char, String, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize
+);
+
+impl<T: SameBits> SameBits for [T] {
+    fn same_bits(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.same_bits(b))
+    }
+}
+
+impl<T: SameBits, const N: usize> SameBits for [T; N] {
+    fn same_bits(&self, other: &Self) -> bool {
+        self[..].same_bits(&other[..])
+    }
+}
+
+impl<T: SameBits> SameBits for Vec<T> {
+    fn same_bits(&self, other: &Self) -> bool {
+        self[..].same_bits(&other[..])
+    }
+}
+
+impl<T: SameBits> SameBits for Option<T> {
+    fn same_bits(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.same_bits(b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+}
+
+impl<K: Eq, V: SameBits> SameBits for BTreeMap<K, V> {
+    fn same_bits(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|((ka, va), (kb, vb))| ka == kb && va.same_bits(vb))
+    }
+}
+
 // -----------------------------------------------------------------------
 // Estimator
 // -----------------------------------------------------------------------
 
 type EstimatorFn<T> = dyn Fn(&[usize]) -> EstimatorResult<T> + Send + Sync;
+type ReplicaCheck<T> = dyn Fn(&T) -> EstimatorResult<()> + Send + Sync;
 
 /// A function `f(indices) -> Result<T>` together with the "population"
 /// indices to be resampled. `Estimator<T>` is a nameable, `Clone`able type
@@ -134,6 +491,7 @@ type EstimatorFn<T> = dyn Fn(&[usize]) -> EstimatorResult<T> + Send + Sync;
 pub struct Estimator<T> {
     func: Arc<EstimatorFn<T>>,
     indices: Vec<usize>,
+    weights: Option<Arc<[f64]>>,
 }
 
 impl<T> Clone for Estimator<T> {
@@ -141,6 +499,7 @@ impl<T> Clone for Estimator<T> {
         Self {
             func: Arc::clone(&self.func),
             indices: self.indices.clone(),
+            weights: self.weights.clone(),
         }
     }
 }
@@ -153,6 +512,7 @@ impl<T: 'static> Estimator<T> {
         Self {
             func: Arc::new(func),
             indices,
+            weights: None,
         }
     }
 
@@ -164,10 +524,105 @@ impl<T: 'static> Estimator<T> {
         &self.indices
     }
 
+    /// Replace the indices, dropping any [`Estimator::weights`], which
+    /// belonged to the old ones.
     pub fn with_indices(mut self, indices: Vec<usize>) -> Self {
         self.indices = indices;
+        self.weights = None;
+        self
+    }
+
+    /// Sampling weight of each index, in the order of
+    /// [`Estimator::indices`], for [`SamplingStrategy::Weighted`] (e.g.
+    /// inverse-probability survey weights). Other samplers ignore them.
+    ///
+    /// # Panics
+    ///
+    /// If there is not one weight per index, or any weight is negative or
+    /// non-finite.
+    pub fn weights(mut self, weights: Vec<f64>) -> Self {
+        assert_eq!(weights.len(), self.indices.len(), "one weight per index");
+        assert!(
+            weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+            "sampling weights must be finite and non-negative"
+        );
+        self.weights = Some(weights.into());
         self
     }
+
+    /// Estimator over `indices` computed from resample counts rather than
+    /// the resample itself: `func(distinct, counts)` receives each distinct
+    /// index of the resample (ascending) with the number of times it was
+    /// drawn, as `f64` so it can be used as a weight. Natural for the
+    /// [`SamplingStrategy::Poisson`] bootstrap, and for statistics that are
+    /// cheaper as weighted sums than over repeated indices; works with any
+    /// sampler.
+    pub fn from_weights<F>(indices: Vec<usize>, func: F) -> Self
+    where
+        F: Fn(&[usize], &[f64]) -> EstimatorResult<T> + Send + Sync + 'static,
+    {
+        Estimator::new(indices, move |sample: &[usize]| {
+            let mut sorted = sample.to_vec();
+            sorted.sort_unstable();
+            let (mut distinct, mut counts) = (Vec::new(), Vec::new());
+            for run in sorted.chunk_by(|a, b| a == b) {
+                distinct.push(run[0]);
+                counts.push(run.len() as f64);
+            }
+            func(&distinct, &counts)
+        })
+    }
+}
+
+impl Estimator<f64> {
+    /// Clamp every value this estimator produces (central and replicas) to
+    /// `[min, max]`, so percentile intervals respect a physical constraint
+    /// such as a probability lying in `[0, 1]`.
+    ///
+    /// Opt-in because clamping piles probability mass onto the bounds: the
+    /// replica distribution, its mean and its spread are biased towards the
+    /// interior, and intervals touching a bound should be read as one-sided.
+    ///
+    /// # Panics
+    ///
+    /// If either bound is NaN or `min > max`.
+    pub fn clamp(self, min: f64, max: f64) -> Estimator<f64> {
+        assert!(min <= max, "clamp bounds must be ordered and not NaN");
+        let func = self.func;
+        Estimator {
+            func: Arc::new(move |ind: &[usize]| (func)(ind).map(|v| v.clamp(min, max))),
+            indices: self.indices,
+            weights: self.weights,
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> Estimator<T> {
+    /// Cache results by resample contents, so a resample that repeats an
+    /// earlier one (up to order) reuses its value instead of calling the
+    /// estimator again. Only worthwhile for expensive statistics on small
+    /// populations, where the number of distinct resamples is comparable to
+    /// `n_boot`; the cache grows with every distinct resample and is never
+    /// evicted. The statistic must not depend on the order of the indices.
+    pub fn memoized(self) -> Estimator<T> {
+        let func = self.func;
+        let cache: Mutex<HashMap<Vec<usize>, EstimatorResult<T>>> = Mutex::new(HashMap::new());
+        let new_func = move |sample: &[usize]| -> EstimatorResult<T> {
+            let mut key = sample.to_vec();
+            key.sort_unstable();
+            if let Some(hit) = cache.lock().unwrap().get(&key) {
+                return hit.clone();
+            }
+            let value = (func)(sample);
+            cache.lock().unwrap().insert(key, value.clone());
+            value
+        };
+        Estimator {
+            func: Arc::new(new_func),
+            indices: self.indices,
+            weights: self.weights,
+        }
+    }
 }
 
 impl<T: Arithmetic> Estimator<T> {
@@ -180,27 +635,47 @@ impl<T: Arithmetic> Estimator<T> {
     /// uses `Block` or `MovingBlock` for autocorrelated data: bias
     /// correction must resample the same way, or the correction is biased
     /// against the very structure it is meant to preserve.
+    ///
+    /// A replica whose inner bootstrap has fewer than half of its `n_inner`
+    /// draws succeed fails; see [`Estimator::bias_correct_with`] to change
+    /// that threshold.
+    ///
+    /// With a `seed`, the inner RNG of each call is derived from the seed
+    /// and the outer resample itself, so a seeded outer run is reproducible
+    /// whatever the thread count, while different outer resamples still get
+    /// independent inner draws.
     pub fn bias_correct(
         self,
         n_inner: usize,
         sampler: SamplingStrategy,
         seed: Option<u64>,
+    ) -> Estimator<T> {
+        self.bias_correct_with(n_inner, sampler, seed, 0.5)
+    }
+
+    /// As [`Estimator::bias_correct`], but a replica fails only when fewer
+    /// than `min_valid_fraction` of its `n_inner` inner draws succeed (at
+    /// least one must). Lower it for estimators that fail often on
+    /// resamples, at the cost of noisier corrections from fewer draws.
+    pub fn bias_correct_with(
+        self,
+        n_inner: usize,
+        sampler: SamplingStrategy,
+        seed: Option<u64>,
+        min_valid_fraction: f64,
     ) -> Estimator<T> {
         let func = self.func;
-        let indices = self.indices;
+        let (indices, weights) = (self.indices, self.weights);
 
         let new_func = move |sample: &[usize]| -> EstimatorResult<T> {
             if sample.is_empty() {
                 return Err(EstimatorError::new("empty inner sample"));
             }
             let theta_hat = (func)(sample)?;
-            let mut sum = T::zero(theta_hat.len());
+            let mut sum = theta_hat.zero_like();
             let mut valid: usize = 0;
             let mut buf = Vec::with_capacity(sample.len());
-            let mut rng = match seed {
-                Some(s) => SmallRng::seed_from_u64(mix_seed(s, sample.len() as u64)),
-                None => SmallRng::from_rng(&mut rand::rng()),
-            };
+            let mut rng = inner_rng(seed, sample);
             for _ in 0..n_inner {
                 if sampler
                     .sample_into_buffer(sample, &mut buf, &mut rng)
@@ -213,7 +688,7 @@ impl<T: Arithmetic> Estimator<T> {
                     valid += 1;
                 }
             }
-            if valid == 0 || valid * 2 < n_inner {
+            if valid == 0 || (valid as f64) < min_valid_fraction * n_inner as f64 {
                 return Err(EstimatorError::new("bias correction: too few valid draws"));
             }
             let mean_boot = sum.scale(1.0 / valid as f64);
@@ -223,6 +698,107 @@ impl<T: Arithmetic> Estimator<T> {
         Estimator {
             func: Arc::new(new_func),
             indices,
+            weights,
+        }
+    }
+}
+
+/// How [`Estimator::with_std_error`] estimates the standard error of each
+/// replicate, for studentized (bootstrap-t) intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeEstimator {
+    /// Leave-one-out jackknife over the resample itself,
+    /// `√((m − 1)/m · Σ (θ₍ᵢ₎ − θ̄)²)`. Costs `m` extra estimator calls per
+    /// replicate (O(n) for resamples of size n) and needs no tuning, but is
+    /// unreliable for non-smooth statistics such as quantiles.
+    Jackknife,
+    /// Standard deviation over `n_inner` inner resamples drawn from the
+    /// resample with `sampler`. Costs `n_inner` extra calls per replicate.
+    Bootstrap {
+        n_inner: usize,
+        sampler: SamplingStrategy,
+    },
+}
+
+/// RNG for the inner resampling of one outer resample. Seeded from the
+/// outer resample's contents rather than a replica number, which the
+/// estimator never sees; keying on the length alone would give every outer
+/// replica the same inner draws.
+fn inner_rng(seed: Option<u64>, sample: &[usize]) -> ChaCha8Rng {
+    match seed {
+        Some(s) => {
+            let key = sample
+                .iter()
+                .fold(sample.len() as u64, |h, &i| mix_seed(h, i as u64));
+            replica_rng(s, key)
+        }
+        None => ChaCha8Rng::from_rng(&mut rand::rng()),
+    }
+}
+
+fn sample_std_dev(values: &[f64]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+}
+
+impl Estimator<f64> {
+    /// Pair every value of this estimator with an estimate of its own
+    /// standard error on the same resample, as needed by the studentized
+    /// interval (see `BootstrapResult::studentized_interval`). The central
+    /// value's SE is the one that scales the interval; the replicates' SEs
+    /// studentize the pivot. `seed` only affects [`SeEstimator::Bootstrap`].
+    pub fn with_std_error(self, se: SeEstimator, seed: Option<u64>) -> Estimator<(f64, f64)> {
+        let func = self.func;
+        let new_func = move |sample: &[usize]| -> EstimatorResult<(f64, f64)> {
+            let theta = (func)(sample)?;
+            let values = match &se {
+                SeEstimator::Jackknife => {
+                    if sample.len() < 2 {
+                        return Err(EstimatorError::new("jackknife needs two observations"));
+                    }
+                    let inner = Estimator {
+                        func: Arc::clone(&func),
+                        indices: sample.to_vec(),
+                        weights: None,
+                    };
+                    leave_out_values(&inner, 1, false).map_err(|e| match e {
+                        BootstrapError::JackknifeFailed { error, .. } => error,
+                        other => EstimatorError::new(other.to_string()),
+                    })?
+                }
+                SeEstimator::Bootstrap { n_inner, sampler } => {
+                    let mut rng = inner_rng(seed, sample);
+                    let mut buf = Vec::with_capacity(sample.len());
+                    let mut values = Vec::with_capacity(*n_inner);
+                    for _ in 0..*n_inner {
+                        if sampler
+                            .sample_into_buffer(sample, &mut buf, &mut rng)
+                            .is_ok()
+                            && let Ok(v) = (func)(&buf)
+                        {
+                            values.push(v);
+                        }
+                    }
+                    if values.len() < 2 {
+                        return Err(EstimatorError::new("inner bootstrap: too few valid draws"));
+                    }
+                    values
+                }
+            };
+            let std_error = match se {
+                SeEstimator::Jackknife => {
+                    let m = values.len() as f64;
+                    sample_std_dev(&values) * (m - 1.0) / m.sqrt()
+                }
+                SeEstimator::Bootstrap { .. } => sample_std_dev(&values),
+            };
+            Ok((theta, std_error))
+        };
+        Estimator {
+            func: Arc::new(new_func),
+            indices: self.indices,
+            weights: self.weights,
         }
     }
 }
@@ -243,6 +819,30 @@ pub trait Progress: Send + Sync {
 
 impl Progress for () {}
 
+/// [`Progress`] adapter behind [`Bootstrap::on_progress`]. Completed
+/// replicas are tallied in an atomic so workers never wait on each other;
+/// the callback fires about a hundred times per run, and always on the
+/// last replica.
+struct CallbackProgress<F> {
+    callback: F,
+    completed: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl<F: Fn(usize, usize) + Send + Sync> Progress for CallbackProgress<F> {
+    fn on_start(&self, n: usize) {
+        self.completed.store(0, Ordering::Relaxed);
+        self.total.store(n, Ordering::Relaxed);
+    }
+    fn on_step(&self) {
+        let done = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        let total = self.total.load(Ordering::Relaxed);
+        if done.is_multiple_of(total.div_ceil(100).max(1)) || done == total {
+            (self.callback)(done, total);
+        }
+    }
+}
+
 #[cfg(feature = "indicatif")]
 pub use indicatif_progress::IndicatifProgress;
 
@@ -289,6 +889,54 @@ mod indicatif_progress {
     }
 }
 
+/// Live view of the successful replicas of a run in progress, for peeking
+/// at interim statistics of an expensive bootstrap.
+///
+/// Hand one to `Bootstrap::monitor` and read it from another thread (or
+/// from a `Progress` hook). Every worker pushes its replica under a mutex
+/// before calling `Progress::on_step`, so a snapshot taken after the k-th
+/// step holds at least k replicas. Replicas appear in completion order, not
+/// replica-index order; only the final `BootstrapResult` is ordered. The
+/// lock is held only for a push or a clone, but it does serialize workers
+/// briefly, so leave monitoring off for cheap estimators.
+pub struct ReplicaMonitor<T> {
+    replicas: Mutex<Vec<T>>,
+}
+
+impl<T> Default for ReplicaMonitor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ReplicaMonitor<T> {
+    pub fn new() -> Self {
+        Self {
+            replicas: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Number of successful replicas so far.
+    pub fn len(&self) -> usize {
+        self.replicas.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&self, value: T) {
+        self.replicas.lock().unwrap().push(value);
+    }
+}
+
+impl<T: Clone> ReplicaMonitor<T> {
+    /// Copy of the successful replicas so far, in completion order.
+    pub fn snapshot(&self) -> Vec<T> {
+        self.replicas.lock().unwrap().clone()
+    }
+}
+
 // -----------------------------------------------------------------------
 // Bootstrap
 // -----------------------------------------------------------------------
@@ -301,6 +949,37 @@ pub struct Bootstrap<T> {
     sampler: SamplingStrategy,
     seed: Option<u64>,
     progress: Option<Arc<dyn Progress>>,
+    compute_central: bool,
+    interval_level: f64,
+    tail_replicas: usize,
+    clamp_n_boot: bool,
+    label: Option<String>,
+    monitor: Option<Arc<ReplicaMonitor<T>>>,
+    determinism_check: Option<fn(&T, &T) -> bool>,
+    validate: Option<Arc<ReplicaCheck<T>>>,
+    min_valid_fraction: f64,
+    jackknife: bool,
+    antithetic: bool,
+    record_failures: bool,
+    threads: usize,
+    parallel: bool,
+}
+
+/// The estimator's indices, once the run is known to be able to resample
+/// them: there are some, and the sampler draws indices rather than the
+/// wild bootstrap's multipliers, which every replica would otherwise fail
+/// on.
+fn checked_indices<T>(
+    estimator: &Estimator<T>,
+    sampler: &SamplingStrategy,
+) -> Result<Vec<usize>, BootstrapError> {
+    if estimator.indices.is_empty() {
+        return Err(BootstrapError::EmptyIndices);
+    }
+    if let SamplingStrategy::Wild { .. } = sampler {
+        return Err(BootstrapError::Sampler(SamplerError::MultipliersOnly));
+    }
+    Ok(estimator.indices.clone())
 }
 
 impl<T: 'static> Bootstrap<T> {
@@ -311,9 +990,31 @@ impl<T: 'static> Bootstrap<T> {
             sampler: SamplingStrategy::Iid,
             seed: None,
             progress: None,
+            compute_central: true,
+            interval_level: 0.95,
+            tail_replicas: 10,
+            clamp_n_boot: false,
+            label: None,
+            monitor: None,
+            determinism_check: None,
+            validate: None,
+            min_valid_fraction: 0.0,
+            jackknife: false,
+            antithetic: false,
+            record_failures: false,
+            threads: 0,
+            parallel: true,
         }
     }
 
+    /// Options for a runner that draws its own replicas through
+    /// [`run_with`](Self::run_with), which never calls the estimator.
+    pub(crate) fn for_runner() -> Self {
+        Bootstrap::new(Estimator::new(Vec::new(), |_| {
+            Err(EstimatorError::new("replicas are drawn by the runner"))
+        }))
+    }
+
     pub fn n_boot(mut self, n: usize) -> Self {
         self.n_boot = n;
         self
@@ -332,31 +1033,250 @@ impl<T: 'static> Bootstrap<T> {
         self.progress = Some(p);
         self
     }
-}
-
-/// Outcome of a bootstrap. Preserves the reason for failed replicas and,
-/// unlike the previous API, does **not** silently fill in a zero when the
-/// central estimator fails.
-#[derive(Debug, Serialize)]
-#[non_exhaustive]
-pub struct BootstrapResult<T> {
-    pub n_boot: usize,
-    pub sampler: SamplingStrategy,
-    pub seed: Option<u64>,
-    pub truncated: usize,
-    pub central: EstimatorResult<T>,
-    pub samples: Vec<T>,
-    pub failures: Vec<EstimatorError>,
-}
-
-impl<T> BootstrapResult<T> {
-    /// Failed-replica count (i.e. `failures.len()`).
-    pub fn failed(&self) -> usize {
-        self.failures.len()
-    }
-
-    /// Apply a transformation to the central value and every replica.
-    pub fn map<U, F>(&self, mut f: F) -> BootstrapResult<U>
+    /// Call `f(completed, total)` periodically from inside the parallel
+    /// loop, for wiring up a progress bar of the caller's choosing. Called
+    /// from worker threads, roughly every 1% of `n_boot` and once at the
+    /// end; calls from different workers may arrive out of order. Shares
+    /// the single progress slot, so it replaces any earlier
+    /// [`progress`](Self::progress) hook and vice versa.
+    pub fn on_progress<F>(self, f: F) -> Self
+    where
+        F: Fn(usize, usize) + Send + Sync + 'static,
+    {
+        self.progress(Arc::new(CallbackProgress {
+            callback: f,
+            completed: AtomicUsize::new(0),
+            total: AtomicUsize::new(0),
+        }))
+    }
+    /// Whether to apply the estimator to the full index set for the central
+    /// value (default `true`). Turn off for expensive estimators when only
+    /// the replica distribution matters; `central` is then an error and
+    /// anything derived from it (pivot-based intervals, bias) is
+    /// unavailable.
+    pub fn compute_central(mut self, on: bool) -> Self {
+        self.compute_central = on;
+        self
+    }
+    /// The widest interval level the caller intends to report (default
+    /// 0.95). Used only to judge whether `n_boot` is large enough.
+    ///
+    /// # Panics
+    ///
+    /// If `level` is not strictly between 0 and 1.
+    pub fn interval_level(mut self, level: f64) -> Self {
+        assert!(
+            level > 0.0 && level < 1.0,
+            "interval level must lie in (0, 1)"
+        );
+        self.interval_level = level;
+        self
+    }
+    /// Replicas wanted beyond the endpoints of the interval at
+    /// [`interval_level`](Self::interval_level) (default 10), which sets
+    /// the [`recommended_n_boot`] that [`Warning::LowNBoot`] and
+    /// [`clamp_n_boot`](Self::clamp_n_boot) hold `n_boot` to. `0` turns
+    /// both off.
+    pub fn tail_replicas(mut self, n: usize) -> Self {
+        self.tail_replicas = n;
+        self
+    }
+    /// Raise `n_boot` to [`recommended_n_boot`] for the interval level
+    /// instead of warning about it.
+    pub fn clamp_n_boot(mut self, on: bool) -> Self {
+        self.clamp_n_boot = on;
+        self
+    }
+    /// Free-form name carried into the result and its serialized form, to
+    /// tell apart the outputs of a batch of runs.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    /// Publish each successful replica to `monitor` as it completes. See
+    /// [`ReplicaMonitor`].
+    pub fn monitor(mut self, monitor: Arc<ReplicaMonitor<T>>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+    /// Also compute the leave-one-out jackknife values over the original
+    /// indices and keep them in the result, which lets the summary report
+    /// BCa intervals. Costs one estimator call per index.
+    pub fn bca(mut self, on: bool) -> Self {
+        self.jackknife = on;
+        self
+    }
+    /// Draw replicas in antithetic pairs: replica `2j + 1` mirrors replica
+    /// `2j`, taking position `n − 1 − k` of the indices wherever its
+    /// partner took position `k`, which is the draw the complementary
+    /// uniforms `1 − u` would have made. The pair is negatively correlated,
+    /// and so the replica mean less noisy, when the indices are ordered by
+    /// the quantity driving the statistic (sorted data, say). The pairs are
+    /// still summarised as `n_boot` separate replicas.
+    ///
+    /// Only the iid strategies (`Iid`, `Subsample`, `SubsampleRule`,
+    /// `Thinning`) draw positions uniformly and so have a mirror image; with
+    /// any other strategy the flag has no effect. Unseeded runs pick a
+    /// random seed for the pair streams, since both halves of a pair must
+    /// be drawn from the same one.
+    pub fn antithetic(mut self, on: bool) -> Self {
+        self.antithetic = on;
+        self
+    }
+    /// Keep the resample behind every failed replica in
+    /// [`BootstrapResult::failed_resamples`], alongside its reason in
+    /// `failures`, so a failing estimator can be replayed on the exact
+    /// input. Off by default since each kept resample is a copy of the
+    /// index buffer.
+    pub fn record_failures(mut self, on: bool) -> Self {
+        self.record_failures = on;
+        self
+    }
+    /// Run the replica loop on a dedicated rayon pool of `n` workers
+    /// instead of the global pool, to cap the parallelism of an embedded
+    /// or nested bootstrap. `0` (the default) keeps the global pool. The
+    /// pool is built per run; if it cannot be built the run falls back to
+    /// the global pool. Seeded results do not depend on `n`.
+    pub fn threads(mut self, n: usize) -> Self {
+        self.threads = n;
+        self
+    }
+    /// Whether to spread replicas over rayon workers (default `true`).
+    /// With `false` every replica, and the jackknife if requested, runs in
+    /// order on the calling thread with no rayon involvement at all: for
+    /// bootstraps nested inside an estimator, step-by-step debugging, or
+    /// targets without threads. Seeded results are the same either way;
+    /// `threads` is ignored.
+    pub fn parallel(mut self, on: bool) -> Self {
+        self.parallel = on;
+        self
+    }
+    /// Apply the estimator to the full sample a second time and add
+    /// [`Warning::NonDeterministic`] if the results differ. Off by default
+    /// since it costs an extra evaluation; worth turning on while
+    /// developing an estimator. Results are compared with [`SameBits`],
+    /// so a NaN matches the NaN a second evaluation reproduces.
+    pub fn check_determinism(mut self, on: bool) -> Self
+    where
+        T: SameBits,
+    {
+        self.determinism_check = on.then_some(T::same_bits as fn(&T, &T) -> bool);
+        self
+    }
+    /// Check every replica with `f` and count those it rejects as failed,
+    /// under the error it returns, as if the estimator itself had failed
+    /// on them: they are left out of `samples` and tallied in `failures`.
+    /// Applies to the replicas of [`run`](Self::run) and the runs built on
+    /// it, [`run_moments`](Bootstrap::run_moments) and
+    /// [`run_streaming`](Bootstrap::run_streaming), not to the central
+    /// value.
+    pub fn validate<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&T) -> EstimatorResult<()> + Send + Sync + 'static,
+    {
+        self.validate = Some(Arc::new(f));
+        self
+    }
+    /// Fewest successful replicas, as a fraction of `n_boot`, for the run
+    /// to be summarised (default `0`, any). Below it
+    /// [`try_summarise`](crate::Summarisable::try_summarise) fails with
+    /// [`BootstrapError::TooFewValidSamples`] and `summarise` gives no
+    /// statistics, rather than intervals from a handful of survivors. The
+    /// threshold travels with the result as
+    /// [`BootstrapResult::min_valid_fraction`].
+    ///
+    /// # Panics
+    ///
+    /// If `fraction` is not in `[0, 1]`.
+    pub fn min_valid_fraction(mut self, fraction: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&fraction),
+            "min_valid_fraction must be in [0, 1]"
+        );
+        self.min_valid_fraction = fraction;
+        self
+    }
+}
+
+fn non_finite() -> EstimatorError {
+    EstimatorError::new("non-finite estimate")
+}
+
+impl Bootstrap<f64> {
+    /// Count NaN and infinite replicas as failures with the reason
+    /// `"non-finite estimate"` rather than letting them into the summary,
+    /// where a single NaN turns the mean and spread into NaN. See
+    /// [`validate`](Self::validate); the number dropped is the count of
+    /// that reason in `failures`.
+    pub fn reject_non_finite(self) -> Self {
+        self.validate(|v: &f64| {
+            if v.is_finite() {
+                Ok(())
+            } else {
+                Err(non_finite())
+            }
+        })
+    }
+}
+
+impl Bootstrap<Vec<f64>> {
+    /// As for scalars: a replica with any non-finite component fails.
+    pub fn reject_non_finite(self) -> Self {
+        self.validate(|v: &Vec<f64>| {
+            if v.iter().all(|x| x.is_finite()) {
+                Ok(())
+            } else {
+                Err(non_finite())
+            }
+        })
+    }
+}
+
+/// Outcome of a bootstrap. Preserves the reason for failed replicas and,
+/// unlike the previous API, does **not** silently fill in a zero when the
+/// central estimator fails.
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BootstrapResult<T> {
+    pub label: Option<String>,
+    pub n_boot: usize,
+    pub sampler: SamplingStrategy,
+    pub seed: Option<u64>,
+    pub truncated: usize,
+    pub central: EstimatorResult<T>,
+    /// Successful replicas in replica order. Replica `i` is drawn from its
+    /// own sub-seed of `seed`, so a seeded run gives the same `samples`
+    /// whatever the thread count or scheduling; with no failures,
+    /// `samples[i]` is replica `i`, as drawn by [`Bootstrap::debug_run`].
+    pub samples: Vec<T>,
+    pub failures: Vec<EstimatorError>,
+    pub warnings: Vec<Warning>,
+    /// Leave-one-out values over the original indices, in index order,
+    /// when the run asked for them with [`Bootstrap::bca`] and none failed.
+    pub jackknife: Option<Vec<T>>,
+    /// With [`Bootstrap::record_failures`], the resample each failed
+    /// replica was given, in the order of `failures`. Empty for a replica
+    /// the sampler could not draw.
+    pub failed_resamples: Option<Vec<Vec<usize>>>,
+    /// Threshold set by [`Bootstrap::min_valid_fraction`]; `0` when unset.
+    #[serde(default)]
+    pub min_valid_fraction: f64,
+}
+
+impl<T> BootstrapResult<T> {
+    /// Failed-replica count (i.e. `failures.len()`).
+    pub fn failed(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Whether fewer replicas succeeded than `min_valid_fraction` of
+    /// `n_boot`.
+    pub(crate) fn too_few_valid(&self) -> bool {
+        (self.samples.len() as f64) < self.min_valid_fraction * self.n_boot as f64
+    }
+
+    /// Apply a transformation to the central value and every replica.
+    pub fn map<U, F>(&self, mut f: F) -> BootstrapResult<U>
     where
         F: FnMut(T) -> U,
         T: Clone,
@@ -365,16 +1285,53 @@ impl<T> BootstrapResult<T> {
             Ok(v) => Ok(f(v)),
             Err(e) => Err(e),
         };
-        let samples = self.samples.clone().into_iter().map(f).collect();
+        let samples = self.samples.clone().into_iter().map(&mut f).collect();
+        let jackknife = self
+            .jackknife
+            .clone()
+            .map(|j| j.into_iter().map(&mut f).collect());
         BootstrapResult {
+            label: self.label.clone(),
             n_boot: self.n_boot,
-            sampler: self.sampler,
+            sampler: self.sampler.clone(),
             seed: self.seed,
             truncated: self.truncated,
             central,
             samples,
             failures: self.failures.clone(),
+            warnings: self.warnings.clone(),
+            jackknife,
+            failed_resamples: self.failed_resamples.clone(),
+            min_valid_fraction: self.min_valid_fraction,
+        }
+    }
+}
+
+impl<T: Arithmetic> BootstrapResult<T> {
+    /// Subtract a single bias estimate, `mean(θ*) − θ̂` over the first
+    /// `n_boot` replicas (all of them if fewer), from the central value and
+    /// every replica. The central value becomes `2·θ̂ − mean(θ*)` and the
+    /// replica distribution, with its intervals, moves by the same amount.
+    ///
+    /// Far cheaper than [`Estimator::bias_correct`], which runs an inner
+    /// bootstrap per replica: here the bias is estimated once, from the
+    /// replicas already drawn. The catch is that the spread is left as is:
+    /// the replicas are of the uncorrected statistic, so intervals ignore
+    /// the extra variance the correction adds and any dependence of the
+    /// bias on the sample. Returns `None` without a central value or
+    /// replicas.
+    pub fn post_bias_correct(&self, n_boot: usize) -> Option<BootstrapResult<T>> {
+        let central = self.central.as_ref().ok()?;
+        let used = &self.samples[..n_boot.min(self.samples.len())];
+        if used.is_empty() {
+            return None;
         }
+        let mut sum = central.zero_like();
+        for v in used {
+            sum.add_assign(v);
+        }
+        let bias = sum.scale(1.0 / used.len() as f64).sub(central);
+        Some(self.map(|v| v.sub(&bias)))
     }
 }
 
@@ -388,131 +1345,891 @@ fn mix_seed(seed: u64, i: u64) -> u64 {
     z ^ (z >> 31)
 }
 
+/// RNG for replica `i` of a run seeded with `seed`. ChaCha8 rather than
+/// `SmallRng` because its output stream is fixed across platforms and `rand`
+/// releases, which is what makes seeded runs reproducible; constructing one
+/// per replica is what makes them independent of rayon's scheduling.
+#[inline]
+pub(crate) fn replica_rng(seed: u64, i: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(mix_seed(seed, i))
+}
+
+/// For [`SamplingStrategy::Balanced`]: `n_boot` copies of `indices`
+/// shuffled together, replica `i` taking the `i`-th run of `indices.len()`.
+/// Built sequentially before the parallel loop; under a seed the shuffle
+/// has its own stream, so the pool does not depend on scheduling either.
+fn balanced_pool(
+    sampler: &SamplingStrategy,
+    indices: &[usize],
+    n_boot: usize,
+    seed: Option<u64>,
+) -> Option<Vec<usize>> {
+    if !matches!(sampler, SamplingStrategy::Balanced) {
+        return None;
+    }
+    let mut pool: Vec<usize> = indices.repeat(n_boot);
+    match seed {
+        Some(s) => pool.shuffle(&mut replica_rng(s, u64::MAX)),
+        None => pool.shuffle(&mut rand::rng()),
+    }
+    Some(pool)
+}
+
+/// For [`SamplingStrategy::Weighted`]: the distribution over positions in
+/// the estimator's indices, built once per run from
+/// [`Estimator::weights`] and shared by every replica. `None` for other
+/// samplers.
+fn weighted_positions<T>(
+    estimator: &Estimator<T>,
+    sampler: &SamplingStrategy,
+) -> Result<Option<WeightedIndex<f64>>, BootstrapError> {
+    if !matches!(sampler, SamplingStrategy::Weighted) {
+        return Ok(None);
+    }
+    estimator
+        .weights
+        .as_deref()
+        .and_then(|w| WeightedIndex::new(w).ok())
+        .map(Some)
+        .ok_or(BootstrapError::Sampler(SamplerError::BadWeights))
+}
+
+/// For [`SamplingStrategy::Stratified`]: the strata of `indices`, built
+/// once per run and shared by every replica. `None` for other samplers.
+fn stratified_table(
+    sampler: &SamplingStrategy,
+    indices: &[usize],
+) -> Result<Option<Strata>, BootstrapError> {
+    match sampler {
+        SamplingStrategy::Stratified { strata } => Strata::new(indices, strata)
+            .map(Some)
+            .map_err(BootstrapError::Sampler),
+        _ => Ok(None),
+    }
+}
+
+/// Seed for the pair streams of an antithetic run, or `None` when the
+/// strategy has no mirror image and the flag is ignored.
+fn antithetic_seed(on: bool, sampler: &SamplingStrategy, seed: Option<u64>) -> Option<u64> {
+    let mirrorable = matches!(
+        sampler,
+        SamplingStrategy::Iid
+            | SamplingStrategy::Subsample { .. }
+            | SamplingStrategy::SubsampleRule { .. }
+            | SamplingStrategy::Thinning { .. }
+    );
+    (on && mirrorable).then(|| seed.unwrap_or_else(rand::random))
+}
+
+/// `v`, unless `validate` rejects it.
+fn checked<T>(validate: Option<&ReplicaCheck<T>>, v: T) -> EstimatorResult<T> {
+    match validate {
+        Some(check) => check(&v).map(|()| v),
+        None => Ok(v),
+    }
+}
+
+/// A resample with the result on it, from [`Bootstrap::debug_run`].
+type DebugReplica<T> = (Vec<usize>, EstimatorResult<T>);
+
+/// A replica with its tag, or its error with the resample kept for it.
+type Replica<T, M> = Result<(T, M), (EstimatorError, Option<Vec<usize>>)>;
+
+/// Per-worker scratch for drawing replicas: the resample buffer, for
+/// unseeded runs the worker's own RNG, for balanced runs the shared pool
+/// of pre-drawn resamples, for weighted runs the shared distribution over
+/// positions, for stratified runs the shared strata, and for antithetic runs the pair streams' seed with a
+/// scratch list of positions.
+struct ReplicaDraw<'a> {
+    seed: Option<u64>,
+    buf: Vec<usize>,
+    thread_rng: Option<SmallRng>,
+    pool: Option<&'a [usize]>,
+    weighted: Option<&'a WeightedIndex<f64>>,
+    strata: Option<&'a Strata>,
+    antithetic: Option<u64>,
+    positions: Vec<usize>,
+}
+
+impl<'a> ReplicaDraw<'a> {
+    fn new(seed: Option<u64>, capacity: usize, pool: Option<&'a [usize]>) -> Self {
+        let thread_rng = match seed {
+            Some(_) => None,
+            None => Some(SmallRng::from_rng(&mut rand::rng())),
+        };
+        Self {
+            seed,
+            buf: Vec::with_capacity(capacity),
+            thread_rng,
+            pool,
+            weighted: None,
+            strata: None,
+            antithetic: None,
+            positions: Vec::new(),
+        }
+    }
+
+    fn antithetic(mut self, pair_seed: Option<u64>) -> Self {
+        self.antithetic = pair_seed;
+        self
+    }
+
+    fn weighted(mut self, positions: Option<&'a WeightedIndex<f64>>) -> Self {
+        self.weighted = positions;
+        self
+    }
+
+    fn strata(mut self, strata: Option<&'a Strata>) -> Self {
+        self.strata = strata;
+        self
+    }
+
+    /// Resample `indices` for replica `i` into `buf`.
+    fn draw(
+        &mut self,
+        i: usize,
+        sampler: &SamplingStrategy,
+        indices: &[usize],
+    ) -> Result<(), SamplerError> {
+        let n = indices.len();
+        if let Some(slice) = self.pool.and_then(|p| p.get(i * n..(i + 1) * n)) {
+            self.buf.clear();
+            self.buf.extend_from_slice(slice);
+            return Ok(());
+        }
+        if let Some(s) = self.antithetic {
+            // Both halves of a pair draw positions from the even replica's
+            // stream; the odd one reflects them.
+            self.positions.clear();
+            self.positions.extend(0..n);
+            let mut r = replica_rng(s, (i & !1) as u64);
+            sampler.sample_into_buffer(&self.positions, &mut self.buf, &mut r)?;
+            let mirror = i % 2 == 1;
+            for k in &mut self.buf {
+                *k = indices[if mirror { n - 1 - *k } else { *k }];
+            }
+            return Ok(());
+        }
+        match self.seed {
+            Some(s) => self.draw_with(sampler, indices, &mut replica_rng(s, i as u64)),
+            None => {
+                let mut r = self.thread_rng.take().unwrap();
+                let drawn = self.draw_with(sampler, indices, &mut r);
+                self.thread_rng = Some(r);
+                drawn
+            }
+        }
+    }
+
+    /// Call `f` with replica `i`'s random stream.
+    fn with_rng<U>(&mut self, i: usize, f: impl FnOnce(&mut dyn rand::RngCore) -> U) -> U {
+        match self.seed {
+            Some(s) => f(&mut replica_rng(s, i as u64)),
+            None => f(self.thread_rng.as_mut().unwrap()),
+        }
+    }
+
+    /// Resample `indices` into `buf` from `rng`, through the run's shared
+    /// table when it has one.
+    fn draw_with<R: rand::Rng + ?Sized>(
+        &mut self,
+        sampler: &SamplingStrategy,
+        indices: &[usize],
+        rng: &mut R,
+    ) -> Result<(), SamplerError> {
+        if let Some(positions) = self.weighted {
+            weighted_draw(indices, positions, &mut self.buf, rng);
+        } else if let Some(strata) = self.strata {
+            strata.draw(&mut self.buf, rng);
+        } else {
+            return sampler.sample_into_buffer(indices, &mut self.buf, rng);
+        }
+        Ok(())
+    }
+
+    /// Resample `indices` for replica `i`, apply `func` and check the
+    /// result with `validate`, if any.
+    fn replica<T>(
+        &mut self,
+        i: usize,
+        sampler: &SamplingStrategy,
+        indices: &[usize],
+        func: &EstimatorFn<T>,
+        validate: Option<&ReplicaCheck<T>>,
+    ) -> EstimatorResult<T> {
+        match self.draw(i, sampler, indices) {
+            Ok(()) => checked(validate, (func)(&self.buf)?),
+            Err(e) => {
+                self.buf.clear();
+                Err(EstimatorError::new(e.to_string()))
+            }
+        }
+    }
+}
+
+/// Outcome of [`Bootstrap::run_moments`]: moments of the replicas without
+/// the replicas themselves.
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct ReplicaMoments {
+    pub label: Option<String>,
+    pub n_boot: usize,
+    pub seed: Option<u64>,
+    pub central: EstimatorResult<f64>,
+    /// Number of successful replicas.
+    pub n: usize,
+    pub failed: usize,
+    pub mean: f64,
+    /// Sample standard deviation, as in `Statistics::stddev`.
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub warnings: Vec<Warning>,
+}
+
+/// Replicas folded by [`Bootstrap::run_streaming`] or
+/// [`Bootstrap::run_moments`], with what their outcomes report.
+struct Folded<T> {
+    label: Option<String>,
+    n_boot: usize,
+    seed: Option<u64>,
+    central: EstimatorResult<T>,
+    warnings: Vec<Warning>,
+    acc: Accumulator,
+}
+
+/// Run `f` on a dedicated pool of `threads` workers, or directly (on the
+/// global pool) when `threads` is 0 or the pool cannot be built.
+fn with_threads<R: Send>(threads: usize, f: impl FnOnce() -> R + Send) -> R {
+    if threads == 0 {
+        return f();
+    }
+    match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+        Ok(pool) => pool.install(f),
+        Err(_) => f(),
+    }
+}
+
 impl<T> Bootstrap<T>
 where
     T: Clone + Send + Sync + 'static,
 {
     pub fn run(self) -> Result<BootstrapResult<T>, BootstrapError> {
-        let Bootstrap {
-            estimator,
-            n_boot,
-            sampler,
-            seed,
-            progress,
-        } = self;
+        self.run_tagged(|_| ()).map(|(result, _)| result)
+    }
 
-        let indices = estimator.indices.clone();
-        if indices.is_empty() {
-            return Err(BootstrapError::EmptyIndices);
-        }
+    /// As [`Bootstrap::run`], also tagging each replica with `meta_fn` of
+    /// its replica number, e.g. the batch or subsample it came from. The
+    /// returned metadata parallels `samples`: `meta[k]` belongs to
+    /// `samples[k]`, and failed replicas have no entry. Replica numbers are
+    /// those of [`Bootstrap::debug_run`], so under a seed a tag identifies
+    /// its resample.
+    pub fn run_with_meta<M, F>(
+        self,
+        meta_fn: F,
+    ) -> Result<(BootstrapResult<T>, Vec<M>), BootstrapError>
+    where
+        M: Send,
+        F: Fn(usize) -> M + Sync,
+    {
+        self.run_tagged(meta_fn)
+    }
+
+    fn run_tagged<M, F>(self, meta_fn: F) -> Result<(BootstrapResult<T>, Vec<M>), BootstrapError>
+    where
+        M: Send,
+        F: Fn(usize) -> M + Sync,
+    {
+        let threads = if self.parallel { self.threads } else { 0 };
+        with_threads(threads, || self.run_replicas(&meta_fn))
+    }
+
+    fn run_replicas<M, F>(
+        mut self,
+        meta_fn: F,
+    ) -> Result<(BootstrapResult<T>, Vec<M>), BootstrapError>
+    where
+        M: Send,
+        F: Fn(usize) -> M + Sync,
+    {
+        let mut warnings = self.settle_n_boot();
+
+        let estimator = &self.estimator;
+        let sampler = &self.sampler;
+        let indices = checked_indices(estimator, sampler)?;
+        let weighted = weighted_positions(estimator, sampler)?;
+        let strata = stratified_table(sampler, &indices)?;
         let truncated = sampler.truncation_for(indices.len());
 
         // Do the central-value application first. Its failure is *not* fatal
         // to the run — we still produce replicas — but it is preserved
         // verbatim in the result.
-        let central = estimator.apply(&indices);
-
-        if let Some(p) = progress.as_ref() {
-            p.on_start(n_boot);
+        let central = if self.compute_central {
+            estimator.apply(&indices)
+        } else {
+            Err(EstimatorError::new("central value not computed"))
+        };
+        if let Some(same) = self.determinism_check {
+            let first = match &central {
+                Ok(v) => Ok(v.clone()),
+                Err(_) => estimator.apply(&indices),
+            };
+            if let (Ok(a), Ok(b)) = (first, estimator.apply(&indices))
+                && !same(&a, &b)
+            {
+                warnings.push(Warning::NonDeterministic);
+            }
         }
 
-        let func = Arc::clone(&estimator.func);
-        let capacity = indices.len();
+        let jackknife = self
+            .jackknife
+            .then(|| leave_out_values(estimator, 1, self.parallel).ok())
+            .flatten();
 
-        let replicas: Vec<EstimatorResult<T>> = (0..n_boot)
-            .into_par_iter()
-            .map_init(
-                || {
-                    let rng = match seed {
-                        Some(_) => None,
-                        None => Some(SmallRng::from_rng(&mut rand::rng())),
-                    };
-                    (Vec::with_capacity(capacity), rng)
-                },
-                |(buf, thread_rng), i| {
-                    let result = match seed {
-                        Some(s) => {
-                            let mut r = SmallRng::seed_from_u64(mix_seed(s, i as u64));
-                            match sampler.sample_into_buffer(&indices, buf, &mut r) {
-                                Ok(()) => (func)(buf),
-                                Err(e) => Err(EstimatorError::new(e.to_string())),
-                            }
-                        }
-                        None => {
-                            let r = thread_rng.as_mut().unwrap();
-                            match sampler.sample_into_buffer(&indices, buf, r) {
-                                Ok(()) => (func)(buf),
-                                Err(e) => Err(EstimatorError::new(e.to_string())),
-                            }
-                        }
-                    };
-                    if let Some(p) = progress.as_ref() {
-                        p.on_step();
-                    }
-                    result
+        let func = &*estimator.func;
+        let validate = self.validate.as_deref();
+        let (seed, record_failures) = (self.seed, self.record_failures);
+        let pool = balanced_pool(sampler, &indices, self.n_boot, seed);
+        let pair_seed = antithetic_seed(self.antithetic, sampler, seed);
+
+        let init = || {
+            ReplicaDraw::new(seed, indices.len(), pool.as_deref())
+                .antithetic(pair_seed)
+                .weighted(weighted.as_ref())
+                .strata(strata.as_ref())
+        };
+        let step = |draw: &mut ReplicaDraw<'_>, i: usize| -> Replica<T, M> {
+            match draw.replica(i, sampler, &indices, func, validate) {
+                Ok(v) => Ok((v, meta_fn(i))),
+                Err(e) => Err((e, record_failures.then(|| draw.buf.clone()))),
+            }
+        };
+        let replicas = self.replica_loop(init, step);
+        Ok(self.into_result(central, truncated, jackknife, warnings, replicas))
+    }
+
+    /// Run the replica loop of [`run`](Self::run) with replica `i`
+    /// computed as `replica(i, rng)` from the replica's own random stream,
+    /// for runners whose replicas are not the estimator on an index
+    /// resample, such as [`WildBootstrap`](crate::WildBootstrap). Threads,
+    /// parallelism, the `n_boot` policy, progress, monitor, validation and
+    /// `min_valid_fraction` apply as in `run`; the estimator and sampler
+    /// are not used, and `central` becomes the result's central value.
+    pub(crate) fn run_with<F>(
+        mut self,
+        central: EstimatorResult<T>,
+        replica: F,
+    ) -> BootstrapResult<T>
+    where
+        F: Fn(usize, &mut dyn rand::RngCore) -> EstimatorResult<T> + Sync + Send,
+    {
+        let threads = if self.parallel { self.threads } else { 0 };
+        with_threads(threads, move || {
+            let warnings = self.settle_n_boot();
+            let (seed, validate) = (self.seed, self.validate.as_deref());
+            let replicas = self.replica_loop(
+                || ReplicaDraw::new(seed, 0, None),
+                |draw: &mut ReplicaDraw<'_>, i: usize| -> Replica<T, ()> {
+                    draw.with_rng(i, |rng| replica(i, rng))
+                        .and_then(|v| checked(validate, v))
+                        .map(|v| (v, ()))
+                        .map_err(|e| (e, None))
                 },
-            )
-            .collect();
+            );
+            self.into_result(central, 0, None, warnings, replicas).0
+        })
+    }
 
-        if let Some(p) = progress.as_ref() {
+    /// Apply the `n_boot` policy shared by every run: below
+    /// [`recommended_n_boot`] for the interval level, raise `n_boot` to it
+    /// under [`clamp_n_boot`](Self::clamp_n_boot) or warn.
+    fn settle_n_boot(&mut self) -> Vec<Warning> {
+        let recommended = recommended_n_boot(self.interval_level, self.tail_replicas);
+        if self.n_boot >= recommended {
+            Vec::new()
+        } else if self.clamp_n_boot {
+            self.n_boot = recommended;
+            Vec::new()
+        } else {
+            vec![Warning::LowNBoot {
+                n_boot: self.n_boot,
+                recommended,
+                level: self.interval_level,
+            }]
+        }
+    }
+
+    /// Replicas `0..n_boot` from `step`, each worker starting from
+    /// `init()`, spread over rayon workers unless the run is sequential.
+    /// Successful replicas go to the monitor, and every replica is
+    /// reported to the progress hook.
+    fn replica_loop<S, M, I, F>(&self, init: I, step: F) -> Vec<Replica<T, M>>
+    where
+        M: Send,
+        I: Fn() -> S + Sync + Send,
+        F: Fn(&mut S, usize) -> Replica<T, M> + Sync + Send,
+    {
+        let (monitor, progress) = (self.monitor.as_deref(), self.progress.as_deref());
+        if let Some(p) = progress {
+            p.on_start(self.n_boot);
+        }
+        let step = |state: &mut S, i: usize| {
+            let replica = step(state, i);
+            if let (Some(m), Ok((v, _))) = (monitor, replica.as_ref()) {
+                m.push(v.clone());
+            }
+            if let Some(p) = progress {
+                p.on_step();
+            }
+            replica
+        };
+        let replicas = if self.parallel {
+            (0..self.n_boot)
+                .into_par_iter()
+                .map_init(init, step)
+                .collect()
+        } else {
+            let mut state = init();
+            (0..self.n_boot).map(|i| step(&mut state, i)).collect()
+        };
+        if let Some(p) = progress {
             p.on_finish();
         }
+        replicas
+    }
 
+    /// Sort `replicas` into samples, their tags and failures, and assemble
+    /// the result.
+    fn into_result<M>(
+        self,
+        central: EstimatorResult<T>,
+        truncated: usize,
+        jackknife: Option<Vec<T>>,
+        warnings: Vec<Warning>,
+        replicas: Vec<Replica<T, M>>,
+    ) -> (BootstrapResult<T>, Vec<M>) {
         let mut samples = Vec::with_capacity(replicas.len());
+        let mut meta = Vec::with_capacity(replicas.len());
         let mut failures = Vec::new();
+        let mut failed_resamples = self.record_failures.then(Vec::new);
         for r in replicas {
             match r {
-                Ok(v) => samples.push(v),
-                Err(e) => failures.push(e),
+                Ok((v, m)) => {
+                    samples.push(v);
+                    meta.push(m);
+                }
+                Err((e, resample)) => {
+                    failures.push(e);
+                    if let (Some(kept), Some(resample)) = (failed_resamples.as_mut(), resample) {
+                        kept.push(resample);
+                    }
+                }
             }
         }
 
-        Ok(BootstrapResult {
-            n_boot,
-            sampler,
-            seed,
+        let result = BootstrapResult {
+            label: self.label,
+            n_boot: self.n_boot,
+            sampler: self.sampler,
+            seed: self.seed,
             truncated,
             central,
             samples,
             failures,
-        })
+            warnings,
+            jackknife,
+            failed_resamples,
+            min_valid_fraction: self.min_valid_fraction,
+        };
+        (result, meta)
+    }
+
+    /// Draw the first `k` replicas one after another on the calling thread
+    /// and return each resample with the estimator's result on it, for
+    /// stepping through a misbehaving estimator. With a seed the pairs are
+    /// exactly replicas `0..k` of [`Bootstrap::run`] under that seed;
+    /// without one they are fresh random draws. A resample the sampler
+    /// rejected comes back empty, with the sampler's error as the result,
+    /// and results are checked with [`validate`](Self::validate) as in
+    /// `run`. Fails as `run` does on an estimator or sampler it cannot
+    /// draw from. Progress and monitor hooks are not called.
+    pub fn debug_run(&self, k: usize) -> Result<Vec<DebugReplica<T>>, BootstrapError> {
+        let (estimator, sampler) = (&self.estimator, &self.sampler);
+        let indices = checked_indices(estimator, sampler)?;
+        let weighted = weighted_positions(estimator, sampler)?;
+        let strata = stratified_table(sampler, &indices)?;
+        let n_boot = if self.clamp_n_boot {
+            self.n_boot
+                .max(recommended_n_boot(self.interval_level, self.tail_replicas))
+        } else {
+            self.n_boot
+        };
+        let pool = balanced_pool(sampler, &indices, n_boot, self.seed);
+        let pair_seed = antithetic_seed(self.antithetic, sampler, self.seed);
+        let mut draw = ReplicaDraw::new(self.seed, indices.len(), pool.as_deref())
+            .antithetic(pair_seed)
+            .weighted(weighted.as_ref())
+            .strata(strata.as_ref());
+        let validate = self.validate.as_deref();
+        Ok((0..k)
+            .map(|i| {
+                let result = draw.replica(i, sampler, &indices, &*estimator.func, validate);
+                (draw.buf.clone(), result)
+            })
+            .collect())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::samplers::SamplingStrategy;
+impl Bootstrap<f64> {
+    /// Run the bootstrap keeping only running moments of the replicas, so
+    /// memory stays bounded by the number of worker threads rather than
+    /// growing with `n_boot`. This is the fold behind
+    /// [`Bootstrap::run_streaming`]: each worker folds its share of
+    /// replicas into running moments and those are merged pairwise, so
+    /// the mean and standard deviation match [`Bootstrap::run`] followed by
+    /// a summary up to rounding. Quantiles need the replicas and are not
+    /// available here. Failure reasons are counted, not kept; the `n_boot`
+    /// policy, label, progress and monitor apply as in `run`.
+    pub fn run_moments(self) -> Result<ReplicaMoments, BootstrapError> {
+        let threads = if self.parallel { self.threads } else { 0 };
+        with_threads(threads, || self.fold_moments())
+    }
 
-    #[test]
-    fn mean_estimator_runs() {
-        let data: Vec<f64> = (1..=100).map(|x| x as f64).collect();
-        let est = Estimator::new((0..data.len()).collect(), move |ind| {
-            let s: f64 = ind.iter().map(|&i| data[i]).sum();
-            Ok(s / ind.len() as f64)
-        });
-        let out = Bootstrap::new(est)
-            .n_boot(500)
-            .sampler(SamplingStrategy::Iid)
-            .seed(1)
-            .run()
-            .unwrap();
-        assert_eq!(out.samples.len(), 500);
-        assert!(out.central.is_ok());
-        assert_eq!(out.failures.len(), 0);
-        // mean of 1..=100 is 50.5; central value should equal that exactly
-        assert!((out.central.unwrap() - 50.5).abs() < 1e-9);
+    fn fold_moments(self) -> Result<ReplicaMoments, BootstrapError> {
+        // The smallest sketch: only the moments are read back.
+        let Folded {
+            label,
+            n_boot,
+            seed,
+            central,
+            warnings,
+            acc,
+        } = self.fold_accumulator(QuantileSketch::Reservoir(1))?;
+        let Moments {
+            n,
+            mean,
+            m2,
+            min,
+            max,
+            ..
+        } = acc.moments();
+        Ok(ReplicaMoments {
+            label,
+            n_boot,
+            seed,
+            central,
+            n,
+            failed: acc.failed,
+            mean,
+            stddev: (m2 / (n as f64 - 1.0).max(1.0)).sqrt(),
+            min,
+            max,
+            warnings,
+        })
     }
 
-    #[test]
-    fn seed_makes_run_reproducible() {
-        let data: Vec<f64> = (0..50).map(|i| i as f64).collect();
-        let make_est = || {
-            let d = data.clone();
-            Estimator::new((0..d.len()).collect(), move |ind| {
-                Ok(ind.iter().map(|&i| d[i]).sum::<f64>() / ind.len() as f64)
-            })
-        };
+    /// Double bootstrap: for every outer resample, draw `n_inner` inner
+    /// resamples *from that resample* with the same sampler and hand the
+    /// inner replicas (with the central value) to `reduce`. Returns each
+    /// outer replica paired with its reduction; those whose estimate or
+    /// reduction fails are dropped, and if that leaves none the run fails
+    /// with [`BootstrapError::NoValidSamples`]. Outer replicas are drawn
+    /// exactly as by [`Bootstrap::run`], balanced pool, antithetic pairs,
+    /// `n_boot` policy and validation included, so under a seed they are
+    /// the replicas `run` would keep. Inner draws get the same options
+    /// over the outer resample, from a stream of their own per outer
+    /// replica; a [`Weighted`](SamplingStrategy::Weighted) outer resample
+    /// already reflects the weights and is resampled uniformly. The inner
+    /// loop runs sequentially inside each outer rayon task, so nesting
+    /// never spawns nested parallelism.
+    pub(crate) fn run_nested<R, F>(
+        self,
+        n_inner: usize,
+        reduce: F,
+    ) -> Result<Vec<(f64, R)>, BootstrapError>
+    where
+        R: Send,
+        F: Fn(f64, &mut [f64]) -> Option<R> + Sync,
+    {
+        let threads = if self.parallel { self.threads } else { 0 };
+        with_threads(threads, || self.nested_replicas(n_inner, &reduce))
+    }
+
+    fn nested_replicas<R, F>(
+        mut self,
+        n_inner: usize,
+        reduce: F,
+    ) -> Result<Vec<(f64, R)>, BootstrapError>
+    where
+        R: Send,
+        F: Fn(f64, &mut [f64]) -> Option<R> + Sync,
+    {
+        self.settle_n_boot();
+        let estimator = &self.estimator;
+        let sampler = &self.sampler;
+        let indices = checked_indices(estimator, sampler)?;
+        let weighted = weighted_positions(estimator, sampler)?;
+        let strata = stratified_table(sampler, &indices)?;
+        let central = estimator
+            .apply(&indices)
+            .map_err(BootstrapError::CentralFailed)?;
+        let (n_boot, seed, antithetic) = (self.n_boot, self.seed, self.antithetic);
+        let key = seed.unwrap_or_else(rand::random);
+        let func = &*estimator.func;
+        let validate = self.validate.as_deref();
+        let pool = balanced_pool(sampler, &indices, n_boot, seed);
+        let pair_seed = antithetic_seed(antithetic, sampler, seed);
+        let inner_sampler = match sampler {
+            SamplingStrategy::Weighted => &SamplingStrategy::Iid,
+            other => other,
+        };
+
+        let init = || {
+            let outer = ReplicaDraw::new(seed, indices.len(), pool.as_deref())
+                .antithetic(pair_seed)
+                .weighted(weighted.as_ref())
+                .strata(strata.as_ref());
+            (outer, Vec::with_capacity(n_inner))
+        };
+        let step = |(outer, inner): &mut (ReplicaDraw<'_>, Vec<f64>), i: usize| {
+            let theta = outer.replica(i, sampler, &indices, func, validate).ok()?;
+            let resample = &outer.buf;
+            let inner_seed = mix_seed(key, i as u64);
+            let inner_pool = balanced_pool(inner_sampler, resample, n_inner, Some(inner_seed));
+            let inner_strata = stratified_table(inner_sampler, resample).ok()?;
+            let mut draw =
+                ReplicaDraw::new(Some(inner_seed), resample.len(), inner_pool.as_deref())
+                    .antithetic(antithetic_seed(antithetic, inner_sampler, Some(inner_seed)))
+                    .strata(inner_strata.as_ref());
+            inner.clear();
+            inner.extend((0..n_inner).filter_map(|j| {
+                draw.replica(j, inner_sampler, resample, func, validate)
+                    .ok()
+            }));
+            Some((theta, reduce(central, inner)?))
+        };
+        let nested: Vec<(f64, R)> = if self.parallel {
+            (0..n_boot)
+                .into_par_iter()
+                .map_init(init, step)
+                .flatten()
+                .collect()
+        } else {
+            let mut state = init();
+            (0..n_boot).filter_map(|i| step(&mut state, i)).collect()
+        };
+        if nested.is_empty() {
+            return Err(BootstrapError::NoValidSamples {
+                n_boot,
+                failed_samples: n_boot,
+            });
+        }
+        Ok(nested)
+    }
+}
+
+impl<T: StreamingStatistic> Bootstrap<T> {
+    /// Run the bootstrap folding every replica into running moments and a
+    /// quantile sketch instead of storing it, so memory is bounded by the
+    /// sketch and the number of workers rather than by `n_boot` — for
+    /// large vector statistics or very long runs. `mean`, `stddev`,
+    /// skewness, kurtosis, `min` and `max` match [`Bootstrap::run`]
+    /// followed by a summary up to rounding; the median, IQR, MAD and
+    /// intervals come from `sketch` (see [`QuantileSketch`] for their
+    /// accuracy). Basic intervals are filled in from the central value;
+    /// BCa needs stored replicas and is not available. Failure reasons are
+    /// counted, not kept; the `n_boot` policy, label, progress and monitor
+    /// apply as in `run`. Fails with [`BootstrapError::InvalidInput`] if
+    /// a reservoir sketch has capacity 0, or a t-digest's compression is
+    /// not finite or below 10.
+    pub fn run_streaming(
+        self,
+        sketch: QuantileSketch,
+    ) -> Result<StreamingSummary<T>, BootstrapError> {
+        match sketch {
+            QuantileSketch::Reservoir(0) => {
+                return Err(BootstrapError::InvalidInput(
+                    "reservoir capacity must be positive".into(),
+                ));
+            }
+            #[cfg(feature = "tdigest")]
+            QuantileSketch::TDigest { compression }
+                if !(compression.is_finite() && compression >= 10.0) =>
+            {
+                return Err(BootstrapError::InvalidInput(format!(
+                    "t-digest compression must be finite and at least 10, got {compression}"
+                )));
+            }
+            _ => {}
+        }
+        let threads = if self.parallel { self.threads } else { 0 };
+        with_threads(threads, || self.fold_streaming(sketch))
+    }
+
+    fn fold_streaming(self, sketch: QuantileSketch) -> Result<StreamingSummary<T>, BootstrapError> {
+        let Folded {
+            label,
+            n_boot,
+            seed,
+            central,
+            warnings,
+            acc,
+        } = self.fold_accumulator(sketch)?;
+        let failed = acc.failed;
+        let statistics = acc.finish::<T>(central.as_ref().ok());
+        Ok(StreamingSummary {
+            label,
+            n_boot,
+            seed,
+            central,
+            failed,
+            statistics,
+            warnings,
+        })
+    }
+
+    /// Fold every replica into per-worker accumulators and merge them;
+    /// shared by [`Bootstrap::run_streaming`] and [`Bootstrap::run_moments`].
+    fn fold_accumulator(mut self, sketch: QuantileSketch) -> Result<Folded<T>, BootstrapError> {
+        let warnings = self.settle_n_boot();
+        let estimator = &self.estimator;
+        let sampler = &self.sampler;
+        let indices = checked_indices(estimator, sampler)?;
+        let weighted = weighted_positions(estimator, sampler)?;
+        let strata = stratified_table(sampler, &indices)?;
+        let central = if self.compute_central {
+            estimator.apply(&indices)
+        } else {
+            Err(EstimatorError::new("central value not computed"))
+        };
+        let (n_boot, seed) = (self.n_boot, self.seed);
+        let key_seed = seed.unwrap_or_else(rand::random);
+        let func = &*estimator.func;
+        let validate = self.validate.as_deref();
+        let (monitor, progress) = (self.monitor.as_deref(), self.progress.as_deref());
+        let pool = balanced_pool(sampler, &indices, n_boot, seed);
+        let pair_seed = antithetic_seed(self.antithetic, sampler, seed);
+
+        if let Some(p) = progress {
+            p.on_start(n_boot);
+        }
+        let init = || {
+            let draw = ReplicaDraw::new(seed, indices.len(), pool.as_deref())
+                .antithetic(pair_seed)
+                .weighted(weighted.as_ref())
+                .strata(strata.as_ref());
+            (draw, Accumulator::new(sketch))
+        };
+        let step = |(draw, acc): &mut (ReplicaDraw<'_>, Accumulator), i: usize| {
+            match draw.replica(i, sampler, &indices, func, validate) {
+                Ok(v) => {
+                    acc.push(mix_seed(key_seed, i as u64), v.components());
+                    if let Some(m) = monitor {
+                        m.push(v);
+                    }
+                }
+                Err(_) => acc.failed += 1,
+            }
+            if let Some(p) = progress {
+                p.on_step();
+            }
+        };
+        let acc = if self.parallel {
+            (0..n_boot)
+                .into_par_iter()
+                .fold(init, |mut state, i| {
+                    step(&mut state, i);
+                    state
+                })
+                .map(|(_, acc)| acc)
+                .reduce(|| Accumulator::new(sketch), Accumulator::merge)
+        } else {
+            let mut state = init();
+            (0..n_boot).for_each(|i| step(&mut state, i));
+            state.1
+        };
+        if let Some(p) = progress {
+            p.on_finish();
+        }
+        Ok(Folded {
+            label: self.label,
+            n_boot,
+            seed,
+            central,
+            warnings,
+            acc,
+        })
+    }
+}
+
+impl<T: SummaryStatistic> Bootstrap<T> {
+    /// Run and summarise, also tracing how the summary settles as replicas
+    /// accrue, for choosing `n_boot`: the statistics of the first `first`
+    /// successful replicas, then the first `2·first`, `4·first`, … and
+    /// finally all of them. Replica `i` comes from its own sub-seed, so
+    /// each prefix is itself an ordinary bootstrap of that size, and with
+    /// no failures under a seed it matches a run with that `n_boot`;
+    /// `Balanced` is the exception, as its replicas are cut from a pool
+    /// sized for the whole run, so a prefix is neither balanced nor what a
+    /// shorter run would draw. The intervals traced are the percentile
+    /// ones; those needing the central value or jackknife appear only in
+    /// the final summary. Plotting, say, the `ci_95` width against `n`
+    /// shows where it stops moving. The trace is empty when too few
+    /// replicas succeeded for the summary to carry statistics (see
+    /// [`min_valid_fraction`](Self::min_valid_fraction)).
+    ///
+    /// # Panics
+    ///
+    /// If `first` is zero.
+    pub fn run_with_trace(
+        self,
+        first: usize,
+    ) -> Result<(BootstrapSummary<T>, Vec<T::Stats>), BootstrapError> {
+        assert!(first > 0, "first checkpoint must be positive");
+        let result = self.run()?;
+        let n = if result.too_few_valid() {
+            0
+        } else {
+            result.samples.len()
+        };
+        let checkpoints = std::iter::successors(Some(first), |&k| k.checked_mul(2))
+            .take_while(|&k| k < n)
+            .chain((n > 0).then_some(n));
+        let trace = checkpoints
+            .filter_map(|k| T::compute_stats(&result.samples[..k]))
+            .collect();
+        Ok((result.summarise(), trace))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::SamplingStrategy;
+    use crate::summary::{BootstrapSummary, Summarisable};
+    use crate::test_util::{mean_estimator, mean_of};
+
+    #[test]
+    fn mean_estimator_runs() {
+        let data: Vec<f64> = (1..=100).map(|x| x as f64).collect();
+        let est = mean_estimator(&data);
+        let out = Bootstrap::new(est)
+            .n_boot(500)
+            .sampler(SamplingStrategy::Iid)
+            .seed(1)
+            .run()
+            .unwrap();
+        assert_eq!(out.samples.len(), 500);
+        assert!(out.central.is_ok());
+        assert_eq!(out.failures.len(), 0);
+        // mean of 1..=100 is 50.5; central value should equal that exactly
+        assert!((out.central.unwrap() - 50.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn seed_makes_run_reproducible() {
+        let data: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        let make_est = || mean_estimator(&data);
         let a = Bootstrap::new(make_est())
             .seed(1234)
             .n_boot(200)
@@ -526,6 +2243,287 @@ mod tests {
         assert_eq!(a.samples, b.samples);
     }
 
+    #[test]
+    fn seeded_run_independent_of_thread_count() {
+        let run_on = |threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                let est = Estimator::new((0..64).collect(), |ind| {
+                    Ok(ind.iter().map(|&i| i as f64).sum::<f64>() / ind.len() as f64)
+                });
+                Bootstrap::new(est).seed(99).n_boot(500).run().unwrap()
+            })
+        };
+        assert_eq!(run_on(1).samples, run_on(8).samples);
+    }
+
+    #[test]
+    fn replicas_follow_their_sub_seeds_under_any_thread_count() {
+        let data: Vec<f64> = (0..50).map(|i| (f64::from(i) * 0.9).sin()).collect();
+        let est = mean_estimator(&data);
+        for sampler in [
+            SamplingStrategy::Iid,
+            SamplingStrategy::Block { block_size: 5 },
+            SamplingStrategy::Poisson,
+            SamplingStrategy::Balanced,
+        ] {
+            let run = |threads: usize| {
+                Bootstrap::new(est.clone())
+                    .sampler(sampler.clone())
+                    .seed(21)
+                    .n_boot(200)
+                    .threads(threads)
+                    .run()
+                    .unwrap()
+                    .samples
+            };
+            let single = run(1);
+            for threads in [2, 3, 8] {
+                assert_eq!(run(threads), single, "{sampler:?} on {threads} threads");
+            }
+            let debug = Bootstrap::new(est.clone())
+                .sampler(sampler.clone())
+                .seed(21)
+                .n_boot(200)
+                .debug_run(20)
+                .unwrap();
+            for (i, (_, value)) in debug.into_iter().enumerate() {
+                assert_eq!(value.unwrap(), single[i], "{sampler:?} replica {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn non_finite_replicas_counted_as_failures() {
+        use crate::summary::{BootstrapSummary, Summarisable};
+        // NaN whenever the resample starts with index 0, about 1 in 20.
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            if ind[0] == 0 {
+                return Ok(f64::NAN);
+            }
+            Ok(ind.iter().sum::<usize>() as f64 / ind.len() as f64)
+        });
+        let plain = Bootstrap::new(est.clone())
+            .n_boot(400)
+            .seed(12)
+            .run()
+            .unwrap();
+        let nans = plain.samples.iter().filter(|v| v.is_nan()).count();
+        assert!(nans > 0);
+        let plain: BootstrapSummary<f64> = plain.summarise();
+        assert!(plain.statistics.unwrap().mean.is_nan());
+
+        let checked = Bootstrap::new(est)
+            .n_boot(400)
+            .seed(12)
+            .reject_non_finite()
+            .run()
+            .unwrap();
+        assert_eq!(checked.failed(), nans);
+        assert!(
+            checked
+                .failures
+                .iter()
+                .all(|e| e.reason == "non-finite estimate")
+        );
+        assert_eq!(checked.samples.len(), 400 - nans);
+        let summary: BootstrapSummary<f64> = checked.summarise();
+        assert!(summary.statistics.unwrap().mean.is_finite());
+
+        let vector = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            Ok(vec![1.0, if ind[0] == 0 { f64::INFINITY } else { 2.0 }])
+        });
+        let checked = Bootstrap::new(vector)
+            .n_boot(400)
+            .seed(12)
+            .reject_non_finite()
+            .run()
+            .unwrap();
+        assert_eq!(checked.failed(), nans);
+    }
+
+    #[test]
+    fn validation_applies_to_folded_runs() {
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            if ind[0] == 0 {
+                return Ok(f64::NAN);
+            }
+            Ok(ind.iter().sum::<usize>() as f64 / ind.len() as f64)
+        });
+        let run =
+            |est: Estimator<f64>| Bootstrap::new(est).n_boot(400).seed(12).reject_non_finite();
+        let nans = run(est.clone()).run().unwrap().failed();
+        assert!(nans > 0);
+
+        let moments = run(est.clone()).run_moments().unwrap();
+        assert_eq!((moments.failed, moments.n), (nans, 400 - nans));
+        assert!(moments.mean.is_finite() && moments.stddev.is_finite());
+
+        let streamed = run(est)
+            .run_streaming(QuantileSketch::Reservoir(400))
+            .unwrap();
+        assert_eq!(streamed.failed, nans);
+        assert!(streamed.statistics.unwrap().mean.is_finite());
+    }
+
+    #[test]
+    fn min_valid_fraction_guards_degenerate_runs() {
+        use crate::summary::{BootstrapSummary, Summarisable};
+        // Fails unless the resample starts with an even index: about half.
+        let est = Estimator::new((0..30).collect(), |ind: &[usize]| {
+            if ind[0] % 2 == 1 {
+                return Err(EstimatorError::new("odd start"));
+            }
+            Ok(ind.iter().sum::<usize>() as f64)
+        });
+        let run = |fraction: f64| {
+            Bootstrap::new(est.clone())
+                .n_boot(200)
+                .seed(3)
+                .min_valid_fraction(fraction)
+                .run()
+                .unwrap()
+        };
+        let valid = run(0.0).samples.len();
+        assert!(valid > 60 && valid < 140);
+
+        assert!(run(0.3).try_summarise().is_ok());
+        match run(0.9).try_summarise() {
+            Err(BootstrapError::TooFewValidSamples {
+                n_boot,
+                valid: v,
+                min_valid_fraction,
+            }) => assert_eq!((n_boot, v, min_valid_fraction), (200, valid, 0.9)),
+            other => panic!("expected TooFewValidSamples, got {other:?}"),
+        }
+        let summary: BootstrapSummary<f64> = run(0.9).summarise();
+        assert!(summary.statistics.is_none());
+        assert_eq!(summary.replicas.len(), valid);
+    }
+
+    #[test]
+    fn trace_checkpoints_match_shorter_runs() {
+        let data: Vec<f64> = (0..60)
+            .map(|i| (f64::from(i) * 0.41).exp().ln_1p())
+            .collect();
+        let est = mean_estimator(&data);
+        let (summary, trace) = Bootstrap::new(est.clone())
+            .n_boot(1000)
+            .seed(31)
+            .run_with_trace(100)
+            .unwrap();
+        let ns: Vec<usize> = trace.iter().map(|s| s.n).collect();
+        assert_eq!(ns, [100, 200, 400, 800, 1000]);
+        let last = trace.last().unwrap();
+        let stats = summary.statistics.unwrap();
+        assert_eq!(last.ci_95.low, stats.ci_95.low);
+        assert_eq!(last.ci_95.high, stats.ci_95.high);
+
+        let short: BootstrapSummary<f64> = Bootstrap::new(est.clone())
+            .n_boot(200)
+            .seed(31)
+            .run()
+            .unwrap()
+            .summarise();
+        let short = short.statistics.unwrap();
+        assert_eq!(trace[1].ci_95.low, short.ci_95.low);
+        assert_eq!(trace[1].mean, short.mean);
+
+        let (_, few) = Bootstrap::new(est)
+            .n_boot(50)
+            .seed(31)
+            .run_with_trace(100)
+            .unwrap();
+        assert_eq!(few.iter().map(|s| s.n).collect::<Vec<_>>(), [50]);
+    }
+
+    #[test]
+    fn trace_is_empty_when_too_few_replicas_succeed() {
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            if ind[0] < 10 {
+                return Err(EstimatorError::new("rejected"));
+            }
+            Ok(ind.iter().sum::<usize>() as f64)
+        });
+        let (summary, trace) = Bootstrap::new(est)
+            .n_boot(400)
+            .seed(5)
+            .min_valid_fraction(0.9)
+            .run_with_trace(50)
+            .unwrap();
+        assert!(summary.statistics.is_none());
+        assert!(trace.is_empty());
+    }
+
+    #[test]
+    fn threads_caps_the_pool_without_changing_replicas() {
+        let make_est = || {
+            Estimator::new((0..64).collect(), |ind: &[usize]| {
+                Ok((
+                    ind.iter().sum::<usize>() as f64,
+                    rayon::current_num_threads(),
+                ))
+            })
+        };
+        let capped = Bootstrap::new(make_est())
+            .seed(5)
+            .n_boot(300)
+            .threads(3)
+            .run()
+            .unwrap();
+        assert!(capped.samples.iter().all(|&(_, n)| n == 3));
+        let global = Bootstrap::new(make_est())
+            .seed(5)
+            .n_boot(300)
+            .threads(0)
+            .run()
+            .unwrap();
+        let values = |r: &BootstrapResult<(f64, usize)>| -> Vec<f64> {
+            r.samples.iter().map(|s| s.0).collect()
+        };
+        assert_eq!(values(&capped), values(&global));
+        assert!(
+            global
+                .samples
+                .iter()
+                .all(|&(_, n)| n == rayon::current_num_threads())
+        );
+    }
+
+    #[test]
+    fn sequential_run_stays_on_calling_thread() {
+        let caller = std::thread::current().id();
+        let data: Vec<f64> = (0..40).map(|i| (i as f64).ln_1p()).collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            assert_eq!(std::thread::current().id(), caller);
+            Ok(mean_of(&data, ind))
+        });
+        let sequential = Bootstrap::new(est.clone())
+            .seed(6)
+            .n_boot(200)
+            .bca(true)
+            .parallel(false)
+            .run()
+            .unwrap();
+        assert_eq!(sequential.jackknife.as_ref().map(Vec::len), Some(40));
+        let moments = Bootstrap::new(est.clone())
+            .seed(6)
+            .n_boot(200)
+            .parallel(false)
+            .run_moments()
+            .unwrap();
+        assert_eq!(moments.n, 200);
+
+        let free = Estimator::new((0..40).collect(), |ind: &[usize]| {
+            Ok(ind.iter().map(|&i| (i as f64).ln_1p()).sum::<f64>() / ind.len() as f64)
+        });
+        let parallel = Bootstrap::new(free).seed(6).n_boot(200).run().unwrap();
+        assert_eq!(sequential.samples, parallel.samples);
+    }
+
     #[test]
     fn failures_are_preserved_and_do_not_zero_central() {
         let est: Estimator<f64> =
@@ -548,9 +2546,7 @@ mod tests {
         // Not a numerical accuracy test — just verifies the wrapped
         // estimator runs and produces the right number of replicas.
         let data: Vec<f64> = (0..40).map(|i| i as f64).collect();
-        let est = Estimator::new((0..data.len()).collect(), move |ind| {
-            Ok(ind.iter().map(|&i| data[i]).sum::<f64>() / ind.len() as f64)
-        });
+        let est = mean_estimator(&data);
         let corrected = est.bias_correct(50, SamplingStrategy::Block { block_size: 4 }, Some(7));
         let out = Bootstrap::new(corrected)
             .sampler(SamplingStrategy::Block { block_size: 4 })
@@ -561,6 +2557,819 @@ mod tests {
         assert_eq!(out.samples.len() + out.failures.len(), 50);
     }
 
+    #[test]
+    fn inner_valid_threshold_controls_outer_survival() {
+        // Two thirds of all (inner and outer) resamples fail.
+        let est = Estimator::new((0..30).collect(), |ind: &[usize]| {
+            if ind.iter().sum::<usize>() % 3 != 0 {
+                Err(EstimatorError::new("unlucky resample"))
+            } else {
+                Ok(ind.len() as f64)
+            }
+        });
+        let survivors = |fraction: f64| {
+            let corrected =
+                est.clone()
+                    .bias_correct_with(60, SamplingStrategy::Iid, Some(2), fraction);
+            Bootstrap::new(corrected)
+                .n_boot(200)
+                .seed(2)
+                .run()
+                .unwrap()
+                .samples
+                .len()
+        };
+        let (strict, lenient) = (survivors(0.5), survivors(0.1));
+        assert!(strict < 5, "{strict}");
+        assert!(lenient > 30, "{lenient}");
+    }
+
+    #[test]
+    fn compute_central_false_skips_full_sample() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let est = Estimator::new((0..10).collect(), move |ind| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Ok(ind.len() as f64)
+        });
+        let out = Bootstrap::new(est)
+            .n_boot(30)
+            .compute_central(false)
+            .seed(1)
+            .run()
+            .unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 30);
+        assert!(out.central.is_err());
+        assert_eq!(out.samples.len(), 30);
+    }
+
+    #[test]
+    fn recommended_n_boot_scales_inverse_square_in_precision() {
+        let data: Vec<f64> = (0..50).map(|i| (i as f64 * 0.9).sin()).collect();
+        let est = mean_estimator(&data);
+        let pilot = Bootstrap::new(est).n_boot(300).seed(8).run().unwrap();
+        let coarse = n_boot_for_mc_se(0.02, 0.95, &pilot);
+        let fine = n_boot_for_mc_se(0.01, 0.95, &pilot);
+        let ratio = fine as f64 / coarse as f64;
+        assert!((ratio - 4.0).abs() < 0.05, "{coarse} {fine}");
+        // The thinner tails at 99% need more replicas for the same precision.
+        assert!(n_boot_for_mc_se(0.02, 0.99, &pilot) > coarse);
+    }
+
+    #[test]
+    fn random_estimator_flagged_as_non_deterministic() {
+        let random = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            Ok(ind.len() as f64 + rand::random::<f64>())
+        });
+        let out = Bootstrap::new(random)
+            .n_boot(200)
+            .check_determinism(true)
+            .run()
+            .unwrap();
+        assert_eq!(out.warnings, vec![Warning::NonDeterministic]);
+
+        let fixed = Estimator::new((0..20).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+        let out = Bootstrap::new(fixed)
+            .n_boot(200)
+            .check_determinism(true)
+            .run()
+            .unwrap();
+        assert!(out.warnings.is_empty());
+
+        let nan = Estimator::new((0..20).collect(), |_: &[usize]| Ok(vec![1.0, f64::NAN]));
+        let out = Bootstrap::new(nan)
+            .n_boot(200)
+            .check_determinism(true)
+            .run()
+            .unwrap();
+        assert!(out.warnings.is_empty());
+
+        let noisy_nan = Estimator::new((0..20).collect(), |_: &[usize]| {
+            Ok((f64::NAN, vec![f64::NAN, rand::random::<f64>()]))
+        });
+        let out = Bootstrap::new(noisy_nan)
+            .n_boot(200)
+            .check_determinism(true)
+            .run()
+            .unwrap();
+        assert_eq!(out.warnings, vec![Warning::NonDeterministic]);
+    }
+
+    #[test]
+    fn replica_metadata_recoverable_per_replica() {
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            if ind[0] == 0 {
+                return Err(EstimatorError::new("starts at zero"));
+            }
+            Ok(ind.iter().sum::<usize>() as f64)
+        });
+        let boot = Bootstrap::new(est).n_boot(400).seed(6);
+        let replayed = boot.debug_run(400).unwrap();
+        let (result, batches) = boot.run_with_meta(|i| i / 100).unwrap();
+        assert!(!result.failures.is_empty());
+        assert_eq!(batches.len(), result.samples.len());
+        let expected: Vec<(f64, usize)> = replayed
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, (_, r))| r.ok().map(|v| (v, i / 100)))
+            .collect();
+        let tagged: Vec<(f64, usize)> = result.samples.into_iter().zip(batches).collect();
+        assert_eq!(tagged, expected);
+    }
+
+    #[test]
+    fn antithetic_pairs_mirror_and_reduce_mean_noise() {
+        let data: Vec<f64> = (0..40).map(|i| f64::from(i).powf(1.5)).collect();
+        let est = mean_estimator(&data);
+        let boot = Bootstrap::new(est.clone())
+            .n_boot(400)
+            .seed(5)
+            .antithetic(true);
+        let pairs = boot.debug_run(4).unwrap();
+        for j in [0, 2] {
+            let mirrored: Vec<usize> = pairs[j].0.iter().map(|k| 39 - k).collect();
+            assert_eq!(pairs[j + 1].0, mirrored);
+        }
+
+        // Spread of the replica mean across seeds, with and without pairing.
+        let spread = |antithetic: bool| {
+            let means: Vec<f64> = (0..40)
+                .map(|seed| {
+                    let r = Bootstrap::new(est.clone())
+                        .n_boot(100)
+                        .seed(seed)
+                        .antithetic(antithetic)
+                        .run()
+                        .unwrap();
+                    r.samples.iter().sum::<f64>() / 100.0
+                })
+                .collect();
+            sample_std_dev(&means)
+        };
+        let (plain, paired) = (spread(false), spread(true));
+        assert!(paired < 0.5 * plain, "{paired} vs {plain}");
+        assert_eq!(boot.run().unwrap().samples.len(), 400);
+    }
+
+    #[test]
+    fn up_weighted_point_dominates_weighted_resample_mean() {
+        let data = [1.0, 2.0, 3.0, 4.0, 100.0];
+        let est = mean_estimator(&data);
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 96.0];
+        let result = Bootstrap::new(est.clone().weights(weights))
+            .n_boot(500)
+            .sampler(SamplingStrategy::Weighted)
+            .seed(2)
+            .run()
+            .unwrap();
+        let mean = result.samples.iter().sum::<f64>() / result.samples.len() as f64;
+        // Expected replica mean 0.96·100 + 0.01·(1 + 2 + 3 + 4) = 96.1.
+        assert!((mean - 96.1).abs() < 1.5, "{mean}");
+
+        let unweighted = Bootstrap::new(est)
+            .sampler(SamplingStrategy::Weighted)
+            .run()
+            .unwrap_err();
+        assert!(matches!(
+            unweighted,
+            BootstrapError::Sampler(SamplerError::BadWeights)
+        ));
+    }
+
+    #[test]
+    fn stratified_run_keeps_counts_and_rejects_unlabelled_indices() {
+        let strata: Vec<usize> = (0..12).map(|i| i % 3).collect();
+        let sampler = SamplingStrategy::Stratified {
+            strata: strata.clone(),
+        };
+        let est = Estimator::new((0..12).collect(), move |ind: &[usize]| {
+            Ok(ind.iter().filter(|&&i| strata[i] == 0).count())
+        });
+        let result = Bootstrap::new(est)
+            .n_boot(50)
+            .sampler(sampler.clone())
+            .seed(3)
+            .run()
+            .unwrap();
+        assert!(result.samples.iter().all(|&n| n == 4));
+
+        let short = Estimator::new((0..13).collect(), |ind: &[usize]| Ok(ind.len()));
+        let err = Bootstrap::new(short).sampler(sampler).run().unwrap_err();
+        assert!(matches!(
+            err,
+            BootstrapError::Sampler(SamplerError::NoStratum {
+                index: 12,
+                n_strata: 12
+            })
+        ));
+    }
+
+    #[test]
+    fn nested_outer_replicas_are_the_run_replicas() {
+        let data: Vec<f64> = (0..30).map(|i| (f64::from(i) * 0.7).sin()).collect();
+        let est = mean_estimator(&data);
+        let configured = |sampler: SamplingStrategy| {
+            Bootstrap::new(est.clone())
+                .n_boot(40)
+                .clamp_n_boot(true)
+                .sampler(sampler)
+                .seed(12)
+                .validate(|m: &f64| {
+                    (*m < 0.1)
+                        .then_some(())
+                        .ok_or_else(|| EstimatorError::new("large mean"))
+                })
+        };
+        for sampler in [
+            SamplingStrategy::Balanced,
+            SamplingStrategy::Block { block_size: 5 },
+        ] {
+            let run = configured(sampler.clone()).run().unwrap();
+            let nested = configured(sampler)
+                .run_nested(20, |_, inner| Some(inner.len()))
+                .unwrap();
+            let outer: Vec<f64> = nested.iter().map(|o| o.0).collect();
+            assert_eq!(run.n_boot, 200);
+            assert!(run.failed() > 0 && !run.samples.is_empty());
+            assert_eq!(outer, run.samples);
+            assert!(nested.iter().all(|o| o.1 <= 20));
+        }
+
+        let weighted = Bootstrap::new(est.weights(vec![1.0; 30]))
+            .n_boot(50)
+            .sampler(SamplingStrategy::Weighted)
+            .seed(3)
+            .run_nested(10, |_, inner| Some(inner.len()))
+            .unwrap();
+        assert_eq!(weighted.len(), 50);
+        assert!(weighted.iter().all(|o| o.1 == 10));
+    }
+
+    #[test]
+    fn balanced_bootstrap_uses_each_index_n_boot_times() {
+        let data: Vec<f64> = (0..15).map(|i| f64::from(i).powi(2)).collect();
+        let sample_mean = data.iter().sum::<f64>() / data.len() as f64;
+        let est = mean_estimator(&data);
+        let boot = Bootstrap::new(est)
+            .n_boot(200)
+            .sampler(SamplingStrategy::Balanced)
+            .seed(12);
+        let mut counts = [0usize; 15];
+        for (resample, _) in boot.debug_run(200).unwrap() {
+            assert_eq!(resample.len(), 15);
+            for i in resample {
+                counts[i] += 1;
+            }
+        }
+        assert!(counts.iter().all(|&c| c == 200));
+        // Balance makes the mean of the replica means the sample mean.
+        let result = boot.run().unwrap();
+        let mean = result.samples.iter().sum::<f64>() / 200.0;
+        assert!((mean - sample_mean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn debug_run_replays_first_replicas() {
+        let data: Vec<f64> = (0..12).map(|i| (i * i) as f64).collect();
+        let est = mean_estimator(&data);
+        let boot = Bootstrap::new(est.clone()).n_boot(3).seed(21);
+        let pairs = boot.debug_run(3).unwrap();
+        assert_eq!(pairs.len(), 3);
+        for (i, (resample, result)) in pairs.iter().enumerate() {
+            let mut manual = Vec::new();
+            SamplingStrategy::Iid
+                .sample_into_buffer(&est.indices, &mut manual, &mut replica_rng(21, i as u64))
+                .unwrap();
+            assert_eq!(resample, &manual);
+            assert_eq!(result.as_ref().unwrap(), &est.apply(&manual).unwrap());
+        }
+        let run = boot.run().unwrap();
+        let replayed: Vec<f64> = pairs.into_iter().map(|(_, r)| r.unwrap()).collect();
+        assert_eq!(run.samples, replayed);
+
+        // A clamped balanced run shuffles a pool sized for the clamped n_boot.
+        let balanced = Bootstrap::new(est.clone())
+            .n_boot(3)
+            .sampler(SamplingStrategy::Balanced)
+            .clamp_n_boot(true)
+            .seed(21);
+        let pairs = balanced.debug_run(3).unwrap();
+        let run = balanced.run().unwrap();
+        let replayed: Vec<f64> = pairs.into_iter().map(|(_, r)| r.unwrap()).collect();
+        assert_eq!(run.samples[..3], replayed[..]);
+
+        let wild = SamplingStrategy::Wild {
+            distribution: crate::WildDist::Rademacher,
+        };
+        assert!(matches!(
+            Bootstrap::new(est.clone()).sampler(wild).debug_run(3),
+            Err(BootstrapError::Sampler(SamplerError::MultipliersOnly))
+        ));
+        let rejecting = Bootstrap::new(est)
+            .seed(21)
+            .validate(|_| Err(EstimatorError::new("rejected")));
+        let pairs = rejecting.debug_run(3).unwrap();
+        assert!(
+            pairs
+                .iter()
+                .all(|(resample, r)| resample.len() == 12 && r.is_err())
+        );
+    }
+
+    #[test]
+    fn run_moments_matches_full_run() {
+        let data: Vec<f64> = (0..30).map(|i| (i as f64 * 0.8).cos()).collect();
+        let est = mean_estimator(&data);
+        let full: BootstrapSummary<f64> = Bootstrap::new(est.clone())
+            .n_boot(5000)
+            .seed(4)
+            .run()
+            .unwrap()
+            .summarise();
+        let stats = full.statistics.unwrap();
+        let moments = Bootstrap::new(est)
+            .n_boot(5000)
+            .seed(4)
+            .run_moments()
+            .unwrap();
+        assert_eq!(moments.n, 5000);
+        assert!((moments.mean - stats.mean).abs() < 1e-12);
+        assert!((moments.stddev - stats.stddev).abs() < 1e-12);
+        assert_eq!((moments.min, moments.max), (stats.min, stats.max));
+    }
+
+    #[test]
+    fn run_streaming_rejects_an_unusable_sketch() {
+        let est = Estimator::new((0..10).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+        let err = Bootstrap::new(est.clone())
+            .run_streaming(QuantileSketch::Reservoir(0))
+            .unwrap_err();
+        assert!(matches!(err, BootstrapError::InvalidInput(_)));
+        #[cfg(feature = "tdigest")]
+        for compression in [5.0, f64::NAN] {
+            let err = Bootstrap::new(est.clone())
+                .run_streaming(QuantileSketch::TDigest { compression })
+                .unwrap_err();
+            assert!(matches!(err, BootstrapError::InvalidInput(_)));
+        }
+    }
+
+    #[test]
+    fn folded_runs_apply_the_run_setup() {
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            Ok(ind.iter().sum::<usize>() as f64 / ind.len() as f64)
+        });
+        let moments = Bootstrap::new(est.clone())
+            .n_boot(50)
+            .label("moments")
+            .seed(5)
+            .run_moments()
+            .unwrap();
+        assert_eq!(moments.n, 50);
+        assert_eq!(moments.label.as_deref(), Some("moments"));
+        assert!(matches!(
+            moments.warnings.as_slice(),
+            [Warning::LowNBoot { n_boot: 50, .. }]
+        ));
+
+        let last = Arc::new(Mutex::new((0, 0)));
+        let seen = Arc::clone(&last);
+        let streamed = Bootstrap::new(est)
+            .n_boot(50)
+            .clamp_n_boot(true)
+            .seed(5)
+            .on_progress(move |done, total| {
+                let mut last = seen.lock().unwrap();
+                *last = (*last).max((done, total));
+            })
+            .run_streaming(QuantileSketch::Reservoir(100))
+            .unwrap();
+        assert_eq!(streamed.n_boot, 200);
+        assert!(streamed.warnings.is_empty());
+        assert_eq!(*last.lock().unwrap(), (200, 200));
+    }
+
+    #[test]
+    #[ignore = "a million replicas; run with --ignored"]
+    fn run_moments_handles_a_million_replicas() {
+        // Never held in memory at once.
+        let est = Estimator::new((0..10).collect(), |ind: &[usize]| {
+            Ok(ind.iter().sum::<usize>() as f64 / ind.len() as f64)
+        });
+        let big = Bootstrap::new(est)
+            .n_boot(1_000_000)
+            .seed(4)
+            .run_moments()
+            .unwrap();
+        assert_eq!(big.n + big.failed, 1_000_000);
+        assert!(big.stddev > 0.0);
+    }
+
+    #[test]
+    fn run_streaming_matches_full_run() {
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.3).exp().ln_1p()).collect();
+        let est = mean_estimator(&data);
+        let full: BootstrapSummary<f64> = Bootstrap::new(est.clone())
+            .n_boot(20_000)
+            .seed(9)
+            .run()
+            .unwrap()
+            .summarise();
+        let stats = full.statistics.unwrap();
+        let close = |a: f64, b: f64, tol: f64| (a - b).abs() <= tol * (1.0 + b.abs());
+
+        // With room for every replica the sketch is exact.
+        let exact = Bootstrap::new(est.clone())
+            .n_boot(20_000)
+            .seed(9)
+            .run_streaming(QuantileSketch::Reservoir(20_000))
+            .unwrap();
+        assert_eq!(exact.failed, 0);
+        let streamed = exact.statistics.unwrap();
+        assert_eq!(streamed.n, 20_000);
+        for (a, b) in [
+            (streamed.mean, stats.mean),
+            (streamed.stddev, stats.stddev),
+            (streamed.skewness, stats.skewness),
+            (streamed.excess_kurtosis, stats.excess_kurtosis),
+            (streamed.median, stats.median),
+            (streamed.ci_95.low, stats.ci_95.low),
+            (streamed.ci_99.high, stats.ci_99.high),
+            (
+                streamed.ci_95_basic.unwrap().low,
+                stats.ci_95_basic.unwrap().low,
+            ),
+        ] {
+            assert!(close(a, b, 1e-9), "{a} vs {b}");
+        }
+
+        // A small reservoir approximates the quantiles but keeps the
+        // moments exact, and does not depend on the thread count.
+        let sketch = |threads: usize| {
+            Bootstrap::new(est.clone())
+                .n_boot(20_000)
+                .seed(9)
+                .threads(threads)
+                .run_streaming(QuantileSketch::Reservoir(2000))
+                .unwrap()
+                .statistics
+                .unwrap()
+        };
+        let small = sketch(3);
+        assert!(close(small.stddev, stats.stddev, 1e-9));
+        assert_eq!((small.min, small.max), (stats.min, stats.max));
+        let width = stats.ci_95.high - stats.ci_95.low;
+        assert!((small.ci_95.low - stats.ci_95.low).abs() < 0.1 * width);
+        assert!((small.ci_95.high - stats.ci_95.high).abs() < 0.1 * width);
+        assert_eq!(small.ci_95.low, sketch(1).ci_95.low);
+    }
+
+    #[cfg(feature = "tdigest")]
+    #[test]
+    fn run_streaming_tdigest_tracks_exact_intervals() {
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.3).exp().ln_1p()).collect();
+        let est = mean_estimator(&data);
+        let exact = Bootstrap::new(est.clone())
+            .n_boot(50_000)
+            .seed(12)
+            .run_streaming(QuantileSketch::Reservoir(50_000))
+            .unwrap()
+            .statistics
+            .unwrap();
+        let digest = Bootstrap::new(est)
+            .n_boot(50_000)
+            .seed(12)
+            .run_streaming(QuantileSketch::TDigest { compression: 200.0 })
+            .unwrap()
+            .statistics
+            .unwrap();
+        assert_eq!(digest.n, 50_000);
+        assert!((digest.mean - exact.mean).abs() < 1e-9);
+        let width = exact.ci_99.high - exact.ci_99.low;
+        for (a, b) in [
+            (digest.median, exact.median),
+            (digest.ci_68.low, exact.ci_68.low),
+            (digest.ci_95.high, exact.ci_95.high),
+            (digest.ci_99.low, exact.ci_99.low),
+            (digest.ci_99.high, exact.ci_99.high),
+            (digest.mad, exact.mad),
+        ] {
+            assert!((a - b).abs() < 0.01 * width, "{a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn run_streaming_vector_marginals() {
+        let est = Estimator::new((0..25).collect(), |ind: &[usize]| {
+            let mean = ind.iter().sum::<usize>() as f64 / ind.len() as f64;
+            if mean > 14.5 {
+                return Err(EstimatorError::new("rejected"));
+            }
+            Ok(vec![mean, -2.0 * mean])
+        });
+        let out = Bootstrap::new(est)
+            .n_boot(3000)
+            .seed(2)
+            .run_streaming(QuantileSketch::Reservoir(500))
+            .unwrap();
+        let stats = out.statistics.unwrap();
+        assert_eq!(stats.len(), 2);
+        assert!(out.failed > 0);
+        assert_eq!(stats[0].n + out.failed, 3000);
+        assert!((stats[1].mean + 2.0 * stats[0].mean).abs() < 1e-9);
+        assert!((stats[1].ci_95.high + 2.0 * stats[0].ci_95.low).abs() < 1e-9);
+        let (cov, var) = (stats.covariance(), stats[0].stddev.powi(2));
+        assert!((cov[0][0] - var).abs() < 1e-12);
+        assert!((cov[0][1] + 2.0 * var).abs() < 1e-12);
+        assert_eq!(cov[0][1], cov[1][0]);
+    }
+
+    #[test]
+    fn poisson_bootstrap_se_matches_classical() {
+        let data: Vec<f64> = (0..2000).map(|i| (i as f64 * 0.37).sin() * 2.0).collect();
+        let classical = mean_estimator(&data);
+        let weighted = Estimator::from_weights((0..data.len()).collect(), move |ind, w| {
+            let total: f64 = w.iter().sum();
+            Ok(ind.iter().zip(w).map(|(&i, w)| w * data[i]).sum::<f64>() / total)
+        });
+        let se = |est: Estimator<f64>, sampler| {
+            let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+                .n_boot(1000)
+                .sampler(sampler)
+                .seed(6)
+                .run()
+                .unwrap()
+                .summarise();
+            summary.statistics.unwrap().stddev
+        };
+        let (iid, poisson) = (
+            se(classical, SamplingStrategy::Iid),
+            se(weighted, SamplingStrategy::Poisson),
+        );
+        assert!((poisson / iid - 1.0).abs() < 0.1, "{iid} {poisson}");
+    }
+
+    #[test]
+    fn low_n_boot_warns_for_level() {
+        let est: Estimator<f64> = Estimator::new((0..10).collect(), |ind| Ok(ind.len() as f64));
+        let out = Bootstrap::new(est.clone())
+            .n_boot(50)
+            .interval_level(0.99)
+            .run()
+            .unwrap();
+        assert!(matches!(
+            out.warnings[..],
+            [Warning::LowNBoot {
+                n_boot: 50,
+                recommended: 1000,
+                ..
+            }]
+        ));
+        let clamped = Bootstrap::new(est.clone())
+            .n_boot(50)
+            .interval_level(0.99)
+            .clamp_n_boot(true)
+            .run()
+            .unwrap();
+        assert_eq!(clamped.n_boot, 1000);
+        assert!(clamped.warnings.is_empty());
+
+        let strict = Bootstrap::new(est.clone())
+            .n_boot(50)
+            .interval_level(0.99)
+            .tail_replicas(25)
+            .clamp_n_boot(true)
+            .run()
+            .unwrap();
+        assert_eq!(strict.n_boot, 2500);
+        let unchecked = Bootstrap::new(est)
+            .n_boot(50)
+            .interval_level(0.99)
+            .tail_replicas(0)
+            .run()
+            .unwrap();
+        assert!(unchecked.warnings.is_empty());
+    }
+
+    #[test]
+    fn wild_sampler_rejected_before_any_replica() {
+        let est = Estimator::new((0..10).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+        let wild = SamplingStrategy::Wild {
+            distribution: crate::WildDist::default(),
+        };
+        let err = Bootstrap::new(est.clone())
+            .sampler(wild.clone())
+            .run()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BootstrapError::Sampler(SamplerError::MultipliersOnly)
+        ));
+        assert!(Bootstrap::new(est).sampler(wild).run_moments().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "interval level must lie in (0, 1)")]
+    fn interval_level_of_one_is_rejected() {
+        let est: Estimator<f64> = Estimator::new((0..10).collect(), |ind| Ok(ind.len() as f64));
+        let _ = Bootstrap::new(est).interval_level(1.0);
+    }
+
+    #[test]
+    fn on_progress_reports_completed_of_total() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+        let seen = Arc::clone(&calls);
+        Bootstrap::new(est)
+            .n_boot(1050)
+            .seed(1)
+            .on_progress(move |done, total| seen.lock().unwrap().push((done, total)))
+            .run()
+            .unwrap();
+        let mut calls = calls.lock().unwrap().clone();
+        calls.sort_unstable();
+        assert!(calls.iter().all(|&(_, total)| total == 1050));
+        assert_eq!(calls.last(), Some(&(1050, 1050)));
+        assert!(calls.len() > 50 && calls.len() <= 101);
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn monitor_snapshot_mid_run() {
+        struct Peek {
+            monitor: Arc<ReplicaMonitor<f64>>,
+            steps: AtomicUsize,
+            at: usize,
+            seen: Mutex<Option<Vec<f64>>>,
+        }
+        impl Progress for Peek {
+            fn on_step(&self) {
+                if self.steps.fetch_add(1, Ordering::SeqCst) + 1 == self.at {
+                    *self.seen.lock().unwrap() = Some(self.monitor.snapshot());
+                }
+            }
+        }
+
+        let data: Vec<f64> = (0..100).map(|i| (i as f64).sqrt()).collect();
+        let est = mean_estimator(&data);
+        let monitor = Arc::new(ReplicaMonitor::new());
+        let peek = Arc::new(Peek {
+            monitor: Arc::clone(&monitor),
+            steps: AtomicUsize::new(0),
+            at: 1000,
+            seen: Mutex::new(None),
+        });
+        let out = Bootstrap::new(est)
+            .n_boot(4000)
+            .seed(8)
+            .monitor(Arc::clone(&monitor))
+            .progress(peek.clone())
+            .run()
+            .unwrap();
+
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        let snapshot = peek.seen.lock().unwrap().take().unwrap();
+        assert!(snapshot.len() >= 1000 && snapshot.len() < out.samples.len());
+        assert_eq!(monitor.len(), out.samples.len());
+        let spread = out
+            .samples
+            .iter()
+            .fold(0.0_f64, |a, &x| a.max((x - mean(&out.samples)).abs()));
+        assert!((mean(&snapshot) - mean(&out.samples)).abs() < 0.1 * spread);
+    }
+
+    #[test]
+    fn clamp_keeps_prevalence_in_unit_interval() {
+        // Rogan–Gladen prevalence corrected for a 5% false-positive rate
+        // goes negative when few positives are observed.
+        let positives: Vec<bool> = (0..40).map(|i| i < 3).collect();
+        let prevalence = move |ind: &[usize]| {
+            let p = ind.iter().filter(|&&i| positives[i]).count() as f64 / ind.len() as f64;
+            Ok((p - 0.05) / 0.95)
+        };
+        let raw = Bootstrap::new(Estimator::new((0..40).collect(), prevalence.clone()))
+            .seed(3)
+            .run()
+            .unwrap();
+        assert!(raw.samples.iter().any(|&p| p < 0.0));
+
+        let clamped = Bootstrap::new(Estimator::new((0..40).collect(), prevalence).clamp(0.0, 1.0))
+            .seed(3)
+            .run()
+            .unwrap();
+        assert!(clamped.samples.iter().all(|p| (0.0..=1.0).contains(p)));
+        let summary: BootstrapSummary<f64> = clamped.summarise();
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.low >= 0.0 && ci.high <= 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "clamp bounds")]
+    fn clamp_rejects_reversed_bounds() {
+        let _ = Estimator::new((0..4).collect(), |_: &[usize]| Ok(0.0)).clamp(1.0, 0.0);
+    }
+
+    #[test]
+    fn memoized_estimator_reuses_repeated_resamples() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let calls = Arc::new(AtomicUsize::new(0));
+        let data = [1.0, 2.0, 4.0];
+        let counter = Arc::clone(&calls);
+        let est = Estimator::new((0..data.len()).collect(), move |ind| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Ok(mean_of(&data, ind))
+        });
+        let plain = Bootstrap::new(est.clone())
+            .n_boot(200)
+            .seed(7)
+            .run()
+            .unwrap();
+        calls.store(0, Ordering::Relaxed);
+        let cached = Bootstrap::new(est.memoized())
+            .n_boot(200)
+            .seed(7)
+            .run()
+            .unwrap();
+        // A population of 3 has only 10 distinct resamples of size 3.
+        assert!(calls.load(Ordering::Relaxed) <= 10);
+        assert_eq!(plain.samples, cached.samples);
+    }
+
+    #[test]
+    fn inner_rng_depends_on_resample_not_just_length() {
+        use rand::RngCore;
+        let draw = |sample: &[usize]| inner_rng(Some(9), sample).next_u64();
+        assert_eq!(draw(&[0, 1, 1, 3]), draw(&[0, 1, 1, 3]));
+        assert_ne!(draw(&[0, 1, 1, 3]), draw(&[0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn seeded_bias_correction_independent_of_thread_count() {
+        let data: Vec<f64> = (0..30).map(|i| ((i * 7) % 11) as f64).collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            let m = mean_of(&data, ind);
+            Ok(m * m)
+        })
+        .bias_correct(30, SamplingStrategy::Iid, Some(4));
+        let run_on = |threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    Bootstrap::new(est.clone())
+                        .n_boot(64)
+                        .seed(8)
+                        .run()
+                        .unwrap()
+                        .samples
+                })
+        };
+        assert_eq!(run_on(1), run_on(4));
+    }
+
+    #[test]
+    fn post_bias_correction_is_cheap_but_shifts_like_nested() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        // The plug-in variance (divisor n) is biased low by a factor (n−1)/n.
+        let data: Vec<f64> = (0..12).map(|i| (i as f64 * 1.7).sin() * 3.0).collect();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let est = Estimator::new((0..data.len()).collect(), move |ind| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            let n = ind.len() as f64;
+            let m = ind.iter().map(|&i| data[i]).sum::<f64>() / n;
+            Ok(ind.iter().map(|&i| (data[i] - m).powi(2)).sum::<f64>() / n)
+        });
+        let raw = Bootstrap::new(est.clone())
+            .n_boot(200)
+            .seed(3)
+            .run()
+            .unwrap();
+        let post_calls = calls.swap(0, Ordering::Relaxed);
+        let post = raw.post_bias_correct(200).unwrap();
+        let nested = Bootstrap::new(est.bias_correct(50, SamplingStrategy::Iid, Some(3)))
+            .n_boot(200)
+            .seed(3)
+            .run()
+            .unwrap();
+        let nested_calls = calls.load(Ordering::Relaxed);
+        assert!(nested_calls > 40 * post_calls);
+
+        let theta = *raw.central.as_ref().unwrap();
+        let (p, q) = (
+            *post.central.as_ref().unwrap(),
+            *nested.central.as_ref().unwrap(),
+        );
+        assert!(p > theta && q > theta);
+        // Both corrections move the estimate by a similar amount.
+        assert!((p - q).abs() < 0.5 * (p - theta), "{theta} {p} {q}");
+        // The shifted replicas are centred on the uncorrected estimate.
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+        assert!((mean(&post.samples) - theta).abs() < 1e-9);
+    }
+
     #[test]
     fn truncation_reported() {
         let est: Estimator<f64> =
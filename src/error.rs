@@ -0,0 +1,74 @@
+//! Errors for whole bootstrap runs and their summaries, as opposed to the
+//! per-replica [`EstimatorError`].
+
+use crate::bootstrap::EstimatorError;
+use crate::samplers::SamplerError;
+use std::fmt;
+
+/// Errors for a bootstrap run as a whole (as opposed to individual
+/// replicas). A sampler that cannot produce any valid draw at all is a
+/// configuration error, not a per-replica failure.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum BootstrapError {
+    Sampler(SamplerError),
+    EmptyIndices,
+    /// The procedure's inputs cannot support it, e.g. a group too small
+    /// to estimate a variance. The message says which input and why.
+    InvalidInput(String),
+    /// The procedure needs the central value and the estimator failed on
+    /// the full sample.
+    CentralFailed(EstimatorError),
+    /// A jackknife needs every leave-out value, and the estimator failed on
+    /// one of them.
+    JackknifeFailed {
+        left_out: usize,
+        error: EstimatorError,
+    },
+    /// Every replica failed, so there is nothing to summarise. Returned by
+    /// [`Summarisable::try_summarise`](crate::Summarisable::try_summarise).
+    NoValidSamples {
+        n_boot: usize,
+        failed_samples: usize,
+    },
+    /// Fewer replicas succeeded than the run's
+    /// [`min_valid_fraction`](crate::Bootstrap::min_valid_fraction) of
+    /// `n_boot`.
+    TooFewValidSamples {
+        n_boot: usize,
+        valid: usize,
+        min_valid_fraction: f64,
+    },
+}
+
+impl fmt::Display for BootstrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapError::Sampler(e) => write!(f, "sampler configuration error: {e}"),
+            BootstrapError::EmptyIndices => f.write_str("estimator has no indices to resample"),
+            BootstrapError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            BootstrapError::CentralFailed(e) => write!(f, "central estimate failed: {e}"),
+            BootstrapError::JackknifeFailed { left_out, error } => {
+                write!(f, "jackknife replicate {left_out} failed: {error}")
+            }
+            BootstrapError::NoValidSamples {
+                n_boot,
+                failed_samples,
+            } => write!(
+                f,
+                "no valid bootstrap samples: {failed_samples} of {n_boot} replicas failed"
+            ),
+            BootstrapError::TooFewValidSamples {
+                n_boot,
+                valid,
+                min_valid_fraction,
+            } => write!(
+                f,
+                "only {valid} of {n_boot} replicas succeeded, below the minimum fraction \
+                 {min_valid_fraction}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BootstrapError {}
@@ -0,0 +1,793 @@
+//! Ready-made estimators for common statistics. Each helper takes ownership
+//! of the data and returns an `Estimator` over all of its indices, ready to
+//! hand to `Bootstrap::new`.
+
+use crate::bootstrap::{Estimator, EstimatorError};
+use crate::summary::quantile_sorted;
+
+fn gather(data: &[f64], indices: &[usize]) -> Vec<f64> {
+    indices.iter().map(|&i| data[i]).collect()
+}
+
+/// Mean of the resampled values after discarding the lowest and highest
+/// `trim` fraction (rounded down) of them.
+///
+/// # Panics
+///
+/// If `trim` is not in `[0, 0.5)`.
+pub fn trimmed_mean(data: Vec<f64>, trim: f64) -> Estimator<f64> {
+    assert!(
+        (0.0..0.5).contains(&trim),
+        "trim fraction must lie in [0, 0.5), got {trim}"
+    );
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        let mut values = gather(&data, ind);
+        values.sort_unstable_by(f64::total_cmp);
+        let k = (trim * values.len() as f64).floor() as usize;
+        let kept = &values[k..values.len() - k];
+        if kept.is_empty() {
+            return Err(EstimatorError::new("trimmed mean of an empty sample"));
+        }
+        Ok(mean(kept))
+    })
+}
+
+/// Gini coefficient of the resampled values,
+/// `Σ (2i − n − 1)·x₍ᵢ₎ / (n·Σ x)` over the sorted values `x₍ᵢ₎`. The data
+/// are expected to be non-negative; a replica with a non-positive total is
+/// reported as a failure.
+pub fn gini(data: Vec<f64>) -> Estimator<f64> {
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        let mut values = gather(&data, ind);
+        values.sort_unstable_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let total: f64 = values.iter().sum();
+        if values.is_empty() || total <= 0.0 {
+            return Err(EstimatorError::new("gini requires a positive total"));
+        }
+        let weighted: f64 = values
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (2.0 * (i + 1) as f64 - n - 1.0) * x)
+            .sum();
+        Ok(weighted / (n * total))
+    })
+}
+
+/// Proportion of successes among the resampled observations. Pair with
+/// [`BootstrapSummary::logit_interval`](crate::summary::BootstrapSummary::logit_interval)
+/// for an interval that respects the `[0, 1]` boundaries.
+pub fn proportion(successes: Vec<bool>) -> Estimator<f64> {
+    Estimator::new((0..successes.len()).collect(), move |ind| {
+        if ind.is_empty() {
+            return Err(EstimatorError::new("proportion of an empty sample"));
+        }
+        let hits = ind.iter().filter(|&&i| successes[i]).count();
+        Ok(hits as f64 / ind.len() as f64)
+    })
+}
+
+/// Coefficient of variation `s / x̄` of the resampled values, with `s` the
+/// sample standard deviation. Small-sample CVs are biased low and skewed;
+/// chain [`Estimator::bias_correct`] for a bias-corrected version. A replica
+/// with fewer than two values or a zero mean fails.
+pub fn coefficient_of_variation(data: Vec<f64>) -> Estimator<f64> {
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        let values = gather(&data, ind);
+        let m = mean(&values);
+        if values.len() < 2 || m == 0.0 {
+            return Err(EstimatorError::new("coefficient of variation undefined"));
+        }
+        let var = values.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+        Ok(var.sqrt() / m)
+    })
+}
+
+/// Gaussian kernel density estimate at `x`,
+/// `(1/(n·h)) Σ φ((x − xᵢ)/h)`, over the resampled values. Bootstrapping it
+/// gives pointwise intervals for the density at `x`; the bandwidth stays
+/// fixed across replicas, so the intervals describe variability around the
+/// smoothed density, not its smoothing bias.
+///
+/// # Panics
+///
+/// If `bandwidth` is not positive and finite.
+pub fn kde_at(data: Vec<f64>, x: f64, bandwidth: f64) -> Estimator<f64> {
+    assert!(
+        bandwidth.is_finite() && bandwidth > 0.0,
+        "bandwidth must be positive, got {bandwidth}"
+    );
+    let norm = (2.0 * std::f64::consts::PI).sqrt() * bandwidth;
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        if ind.is_empty() {
+            return Err(EstimatorError::new("density estimate of an empty sample"));
+        }
+        let total: f64 = ind
+            .iter()
+            .map(|&i| (-0.5 * ((x - data[i]) / bandwidth).powi(2)).exp())
+            .sum();
+        Ok(total / (ind.len() as f64 * norm))
+    })
+}
+
+/// Kaplan–Meier estimate of the survival probability `S(t)` over the
+/// resampled subjects. `times[i]` is subject `i`'s event or censoring time
+/// and `events[i]` whether the event was observed (`false` = censored).
+/// Resampling whole subjects keeps each time with its censoring flag.
+///
+/// `S(t) = Π (1 − dⱼ/rⱼ)` over the distinct event times `tⱼ ≤ t`, with `dⱼ`
+/// events at `tⱼ` and `rⱼ` subjects still at risk; subjects censored at
+/// `tⱼ` count as at risk there. A replica with no subjects fails.
+///
+/// # Panics
+///
+/// If `times` and `events` differ in length.
+pub fn kaplan_meier_at(times: Vec<f64>, events: Vec<bool>, t: f64) -> Estimator<f64> {
+    assert_eq!(times.len(), events.len(), "one event flag per time");
+    Estimator::new((0..times.len()).collect(), move |ind| {
+        if ind.is_empty() {
+            return Err(EstimatorError::new("survival estimate of an empty sample"));
+        }
+        let mut subjects: Vec<(f64, bool)> = ind.iter().map(|&i| (times[i], events[i])).collect();
+        subjects.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        let mut survival = 1.0;
+        let mut at_risk = subjects.len();
+        for group in subjects.chunk_by(|a, b| a.0 == b.0) {
+            if group[0].0 > t {
+                break;
+            }
+            let deaths = group.iter().filter(|s| s.1).count();
+            survival *= 1.0 - deaths as f64 / at_risk as f64;
+            at_risk -= group.len();
+        }
+        Ok(survival)
+    })
+}
+
+/// Difference `Q(q_high) − Q(q_low)` of two quantiles of the resampled
+/// values, e.g. `(0.1, 0.9)` for the 10–90 spread. Both quantiles come from
+/// the same resample, so the replicas keep their correlation: bootstrapping
+/// each quantile separately and combining the intervals as if independent
+/// overstates the spread's uncertainty. Quantiles follow the same rule as
+/// the summary statistics.
+///
+/// # Panics
+///
+/// If either quantile is outside `[0, 1]`.
+pub fn quantile_difference(data: Vec<f64>, q_low: f64, q_high: f64) -> Estimator<f64> {
+    assert!(
+        (0.0..=1.0).contains(&q_low) && (0.0..=1.0).contains(&q_high),
+        "quantiles must lie in [0, 1], got {q_low} and {q_high}"
+    );
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        if ind.is_empty() {
+            return Err(EstimatorError::new("quantiles of an empty sample"));
+        }
+        let mut values = gather(&data, ind);
+        values.sort_unstable_by(f64::total_cmp);
+        Ok(quantile_sorted(&values, q_high) - quantile_sorted(&values, q_low))
+    })
+}
+
+/// Theil–Sen slope of `y` on `x`: the median of the slopes
+/// `(yⱼ − yᵢ)/(xⱼ − xᵢ)` over all pairs of resampled points with distinct
+/// `x`. Robust to up to about 29% of the points being outliers. Costs
+/// O(n²) per replica; a replica with no pair of distinct `x` fails.
+///
+/// # Panics
+///
+/// If `x` and `y` differ in length.
+pub fn theil_sen(x: Vec<f64>, y: Vec<f64>) -> Estimator<f64> {
+    assert_eq!(x.len(), y.len(), "one y per x");
+    Estimator::new((0..x.len()).collect(), move |ind| {
+        let mut slopes = Vec::with_capacity(ind.len() * ind.len().saturating_sub(1) / 2);
+        for (k, &i) in ind.iter().enumerate() {
+            for &j in &ind[k + 1..] {
+                if x[i] != x[j] {
+                    slopes.push((y[j] - y[i]) / (x[j] - x[i]));
+                }
+            }
+        }
+        if slopes.is_empty() {
+            return Err(EstimatorError::new("theil-sen needs two distinct x values"));
+        }
+        slopes.sort_unstable_by(f64::total_cmp);
+        let mid = slopes.len() / 2;
+        Ok(if slopes.len().is_multiple_of(2) {
+            (slopes[mid - 1] + slopes[mid]) / 2.0
+        } else {
+            slopes[mid]
+        })
+    })
+}
+
+/// Hodges–Lehmann location estimate: the median of the Walsh averages
+/// `(xᵢ + xⱼ)/2`, `i ≤ j`, over the resampled values. As robust as the
+/// median but nearly as efficient as the mean on normal data.
+///
+/// The `n(n + 1)/2` averages are never materialised: the median is
+/// selected exactly by bisecting over the floats between the smallest and
+/// largest value, counting the averages below each candidate in O(n) on
+/// the sorted resample. A replica therefore costs O(n log n) time and
+/// O(n) memory at any size. Non-finite values are an error.
+pub fn hodges_lehmann(data: Vec<f64>) -> Estimator<f64> {
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        if ind.is_empty() {
+            return Err(EstimatorError::new("hodges-lehmann of no values"));
+        }
+        let mut values = gather(&data, ind);
+        if !values.iter().all(|v| v.is_finite()) {
+            return Err(EstimatorError::new("hodges-lehmann of non-finite values"));
+        }
+        values.sort_unstable_by(f64::total_cmp);
+        let n = values.len();
+        let total = n * (n + 1) / 2;
+        let mid = total / 2;
+        Ok(if total.is_multiple_of(2) {
+            (walsh_select(&values, mid - 1) + walsh_select(&values, mid)) / 2.0
+        } else {
+            walsh_select(&values, mid)
+        })
+    })
+}
+
+/// The `k`-th smallest (from 0) Walsh average of sorted, finite `sorted`.
+/// Bisects on the ordered bit patterns of the floats, so it lands on the
+/// exact average after at most 64 counting passes.
+fn walsh_select(sorted: &[f64], k: usize) -> f64 {
+    // Monotone map from finite floats to integers and back.
+    let key = |x: f64| {
+        let bits = x.to_bits();
+        if bits >> 63 == 1 {
+            !bits
+        } else {
+            bits | 1 << 63
+        }
+    };
+    let float = |key: u64| {
+        f64::from_bits(if key >> 63 == 1 {
+            key & !(1 << 63)
+        } else {
+            !key
+        })
+    };
+    let (mut lo, mut hi) = (key(sorted[0]), key(sorted[sorted.len() - 1]));
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if walsh_count_le(sorted, float(mid)) > k {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    float(lo)
+}
+
+/// Number of Walsh averages `(sorted[i] + sorted[j])/2`, `i ≤ j`, that are
+/// at most `t`, by a two-pointer sweep: the largest partner `j` of each
+/// `i` only shrinks as `i` grows.
+fn walsh_count_le(sorted: &[f64], t: f64) -> usize {
+    let mut count = 0;
+    let mut end = sorted.len();
+    for (i, &a) in sorted.iter().enumerate() {
+        while end > i && (a + sorted[end - 1]) / 2.0 > t {
+            end -= 1;
+        }
+        if end <= i {
+            break;
+        }
+        count += end - i;
+    }
+    count
+}
+
+/// Self-normalised importance-weighted mean `Σ wᵢ·xᵢ / Σ wᵢ` over the
+/// resampled observations.
+///
+/// Use when `data` was drawn from a proposal distribution `q` but the target
+/// is `p`: `weights[i]` is the likelihood ratio `p(xᵢ)/q(xᵢ)`, known up to a
+/// constant since the normalisation cancels. Observations are resampled
+/// uniformly as usual and carry their weight with them.
+///
+/// # Panics
+///
+/// If `weights` and `data` differ in length, or any weight is negative or
+/// non-finite.
+pub fn importance_mean(data: Vec<f64>, weights: Vec<f64>) -> Estimator<f64> {
+    assert_eq!(data.len(), weights.len(), "one weight per observation");
+    assert!(
+        weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+        "importance weights must be finite and non-negative"
+    );
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        let total: f64 = ind.iter().map(|&i| weights[i]).sum();
+        if total <= 0.0 {
+            return Err(EstimatorError::new("importance weights sum to zero"));
+        }
+        Ok(ind.iter().map(|&i| weights[i] * data[i]).sum::<f64>() / total)
+    })
+}
+
+/// Area under the ROC curve of `scores` against binary `labels`, as the
+/// Mann–Whitney statistic `P(score⁺ > score⁻) + ½·P(score⁺ = score⁻)` over
+/// the resampled observations. A replica without both classes fails.
+///
+/// # Panics
+///
+/// If `scores` and `labels` differ in length.
+pub fn auc(scores: Vec<f64>, labels: Vec<bool>) -> Estimator<f64> {
+    assert_eq!(scores.len(), labels.len(), "one label per score");
+    Estimator::new((0..scores.len()).collect(), move |ind| {
+        let pairs: Vec<(f64, bool)> = ind.iter().map(|&i| (scores[i], labels[i])).collect();
+        mann_whitney_auc(pairs)
+    })
+}
+
+/// Rank-sum AUC with midranks for ties, O(n log n).
+fn mann_whitney_auc(mut pairs: Vec<(f64, bool)>) -> Result<f64, EstimatorError> {
+    let n_pos = pairs.iter().filter(|p| p.1).count();
+    let n_neg = pairs.len() - n_pos;
+    if n_pos == 0 || n_neg == 0 {
+        return Err(EstimatorError::new(
+            "auc needs both positive and negative labels",
+        ));
+    }
+    pairs.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+    let mut rank_sum = 0.0;
+    let mut start = 0;
+    while start < pairs.len() {
+        let mut end = start;
+        while end < pairs.len() && pairs[end].0 == pairs[start].0 {
+            end += 1;
+        }
+        let midrank = (start + end + 1) as f64 / 2.0;
+        let positives = pairs[start..end].iter().filter(|p| p.1).count();
+        rank_sum += midrank * positives as f64;
+        start = end;
+    }
+    let (np, nn) = (n_pos as f64, n_neg as f64);
+    Ok((rank_sum - np * (np + 1.0) / 2.0) / (np * nn))
+}
+
+/// DeLong's analytic variance of the empirical AUC, for comparison with
+/// the bootstrap standard error of [`auc`]. `None` unless both classes
+/// have at least two members. O(n⁺·n⁻).
+pub fn delong_variance(scores: &[f64], labels: &[bool]) -> Option<f64> {
+    let pos: Vec<f64> = scores
+        .iter()
+        .zip(labels)
+        .filter(|p| *p.1)
+        .map(|p| *p.0)
+        .collect();
+    let neg: Vec<f64> = scores
+        .iter()
+        .zip(labels)
+        .filter(|p| !*p.1)
+        .map(|p| *p.0)
+        .collect();
+    if pos.len() < 2 || neg.len() < 2 {
+        return None;
+    }
+    let psi = |x: f64, y: f64| match x.total_cmp(&y) {
+        std::cmp::Ordering::Greater => 1.0,
+        std::cmp::Ordering::Equal => 0.5,
+        std::cmp::Ordering::Less => 0.0,
+    };
+    let v10: Vec<f64> = pos
+        .iter()
+        .map(|&x| neg.iter().map(|&y| psi(x, y)).sum::<f64>() / neg.len() as f64)
+        .collect();
+    let v01: Vec<f64> = neg
+        .iter()
+        .map(|&y| pos.iter().map(|&x| psi(x, y)).sum::<f64>() / pos.len() as f64)
+        .collect();
+    let var = |v: &[f64]| {
+        let m = mean(v);
+        v.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (v.len() - 1) as f64
+    };
+    Some(var(&v10) / pos.len() as f64 + var(&v01) / neg.len() as f64)
+}
+
+/// Per-group aggregate: resample observations, split the resample by
+/// `group_of[i]` (labels `0..k`) and apply `within` to each group's values,
+/// giving one component per group. A replica in which some group drew no
+/// observations fails; use a stratified sampler to rule that out.
+///
+/// # Panics
+///
+/// If `group_of` and `data` differ in length.
+pub fn grouped<F>(data: Vec<f64>, group_of: Vec<usize>, within: F) -> Estimator<Vec<f64>>
+where
+    F: Fn(&[f64]) -> f64 + Send + Sync + 'static,
+{
+    assert_eq!(
+        data.len(),
+        group_of.len(),
+        "one group label per observation"
+    );
+    let n_groups = group_of.iter().max().map_or(0, |g| g + 1);
+    Estimator::new((0..data.len()).collect(), move |ind| {
+        let mut groups = vec![Vec::new(); n_groups];
+        for &i in ind {
+            groups[group_of[i]].push(data[i]);
+        }
+        if groups.iter().any(Vec::is_empty) {
+            return Err(EstimatorError::new("a group is empty in this resample"));
+        }
+        Ok(groups.iter().map(|g| within(g)).collect())
+    })
+}
+
+/// Difference-in-differences estimate
+/// `(ȳ_treated,post − ȳ_treated,pre) − (ȳ_control,post − ȳ_control,pre)`
+/// for panel data, resampling whole units.
+///
+/// Observation `i` has outcome `outcomes[i]`, group `treated[i]`, period
+/// `post[i]` and belongs to unit `unit_of[i]` (labels `0..k`). The
+/// estimator's indices are the *units*, so each resampled unit brings all of
+/// its pre and post observations along and within-unit correlation is
+/// preserved. A replica with an empty group×period cell fails.
+///
+/// # Panics
+///
+/// If the four vectors differ in length.
+pub fn diff_in_diff(
+    outcomes: Vec<f64>,
+    treated: Vec<bool>,
+    post: Vec<bool>,
+    unit_of: Vec<usize>,
+) -> Estimator<f64> {
+    let n = outcomes.len();
+    assert!(
+        treated.len() == n && post.len() == n && unit_of.len() == n,
+        "one treated/post/unit entry per outcome"
+    );
+    let n_units = unit_of.iter().max().map_or(0, |u| u + 1);
+    let mut observations = vec![Vec::new(); n_units];
+    for (i, &u) in unit_of.iter().enumerate() {
+        observations[u].push(i);
+    }
+    Estimator::new((0..n_units).collect(), move |units| {
+        // Cells indexed by 2·treated + post.
+        let mut sums = [0.0; 4];
+        let mut counts = [0usize; 4];
+        for &u in units {
+            for &i in &observations[u] {
+                let cell = 2 * usize::from(treated[i]) + usize::from(post[i]);
+                sums[cell] += outcomes[i];
+                counts[cell] += 1;
+            }
+        }
+        if counts.contains(&0) {
+            return Err(EstimatorError::new("empty group/period cell"));
+        }
+        let m = |c: usize| sums[c] / counts[c] as f64;
+        Ok((m(3) - m(2)) - (m(1) - m(0)))
+    })
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::Bootstrap;
+    use crate::summary::{BootstrapSummary, Summarisable};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    use rand_distr::{Distribution, Normal};
+
+    fn normal_sample(n: usize, seed: u64) -> Vec<f64> {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut rng = SmallRng::seed_from_u64(seed);
+        (0..n).map(|_| normal.sample(&mut rng)).collect()
+    }
+
+    fn summarise(est: Estimator<f64>) -> BootstrapSummary<f64> {
+        Bootstrap::new(est)
+            .n_boot(1000)
+            .seed(5)
+            .run()
+            .unwrap()
+            .summarise()
+    }
+
+    #[test]
+    fn trimmed_mean_resists_outliers() {
+        let mut data = normal_sample(95, 1);
+        data.extend([40.0; 5]);
+        let plain = summarise(Estimator::new((0..data.len()).collect(), {
+            let data = data.clone();
+            move |ind| Ok(mean(&gather(&data, ind)))
+        }));
+        let trimmed = summarise(trimmed_mean(data, 0.1));
+        let (p, t) = (plain.statistics.unwrap(), trimmed.statistics.unwrap());
+        assert!(trimmed.central.unwrap().abs() < plain.central.unwrap().abs());
+        assert!(t.ci_95.high - t.ci_95.low < p.ci_95.high - p.ci_95.low);
+    }
+
+    #[test]
+    fn gini_of_uniform_is_one_third() {
+        // Gini coefficient of U(0, 1) is 1/3.
+        let data: Vec<f64> = (1..=1000).map(|i| i as f64 / 1000.0).collect();
+        let summary = summarise(gini(data));
+        assert!((summary.central.unwrap() - 1.0 / 3.0).abs() < 1e-2);
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.high > ci.low);
+    }
+
+    #[test]
+    fn logit_interval_of_rare_proportion_stays_in_unit_interval() {
+        let successes: Vec<bool> = (0..40).map(|i| i % 15 == 0).collect();
+        let summary = summarise(proportion(successes));
+        let p = *summary.central.as_ref().unwrap();
+        assert_eq!(p, 3.0 / 40.0);
+        let stats = summary.statistics.as_ref().unwrap();
+        // The symmetric normal interval on the raw scale crosses zero.
+        assert!(p - 1.96 * stats.stddev < 0.0);
+        let ci = summary.logit_interval(40, 0.95).unwrap();
+        assert!(ci.low > 0.0 && ci.high < 1.0);
+        assert!(ci.low < p && p < ci.high);
+    }
+
+    #[test]
+    fn coefficient_of_variation_and_its_bias_correction() {
+        use crate::samplers::SamplingStrategy;
+        let data: Vec<f64> = normal_sample(15, 7)
+            .iter()
+            .map(|x| 10.0 + 2.0 * x)
+            .collect();
+        let m = mean(&data);
+        let sd = (data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / 14.0).sqrt();
+        let plain = summarise(coefficient_of_variation(data.clone()));
+        assert!((plain.central.as_ref().unwrap() - sd / m).abs() < 1e-12);
+        let ci = plain.statistics.as_ref().unwrap().ci_95;
+        assert!(ci.high - ci.low > 0.0);
+        let corrected = summarise(coefficient_of_variation(data).bias_correct(
+            200,
+            SamplingStrategy::Iid,
+            Some(1),
+        ));
+        // The sample CV is biased low, so the correction raises it.
+        assert!(corrected.central.unwrap() > plain.central.unwrap());
+    }
+
+    #[test]
+    fn kde_at_mode_of_normal() {
+        let summary = summarise(kde_at(normal_sample(500, 8), 0.0, 0.3));
+        // φ(0) = 0.399, smoothed by the kernel to 1/√(2π·(1 + h²)) = 0.382.
+        let density = *summary.central.as_ref().unwrap();
+        assert!((density - 0.382).abs() < 0.05, "{density}");
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.low > 0.0 && ci.high > ci.low);
+    }
+
+    #[test]
+    fn kaplan_meier_recovers_exponential_survival() {
+        use rand_distr::Exp1;
+        let mut rng = SmallRng::seed_from_u64(4);
+        let (mut times, mut events) = (Vec::new(), Vec::new());
+        for _ in 0..300 {
+            let death: f64 = Exp1.sample(&mut rng);
+            let censor: f64 = Exp1.sample(&mut rng);
+            let censor = 3.0 * censor;
+            times.push(death.min(censor));
+            events.push(death <= censor);
+        }
+        assert!(events.iter().any(|e| !e));
+        let summary = summarise(kaplan_meier_at(times, events, 1.0));
+        let s = *summary.central.as_ref().unwrap();
+        assert!((0.0..=1.0).contains(&s));
+        // S(1) = e⁻¹ for unit-rate exponential lifetimes.
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(
+            ci.low < (-1.0f64).exp() && (-1.0f64).exp() < ci.high,
+            "{ci:?}"
+        );
+        assert!(ci.low >= 0.0 && ci.high <= 1.0 && ci.high - ci.low < 0.2);
+    }
+
+    #[test]
+    fn kaplan_meier_censoring_at_event_time_stays_at_risk() {
+        // Deaths at 1 (one of three at risk, the tied censoring included),
+        // then at 2 (one of one).
+        let est = kaplan_meier_at(vec![1.0, 1.0, 2.0], vec![true, false, true], 1.5);
+        assert!((est.apply(&[0, 1, 2]).unwrap() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn quantile_difference_keeps_within_resample_correlation() {
+        let data = normal_sample(400, 10);
+        let quantile = |q: f64| {
+            let data = data.clone();
+            summarise(Estimator::new((0..data.len()).collect(), move |ind| {
+                let mut values = gather(&data, ind);
+                values.sort_unstable_by(f64::total_cmp);
+                Ok(quantile_sorted(&values, q))
+            }))
+        };
+        let sd = |s: &BootstrapSummary<f64>| s.statistics.as_ref().unwrap().stddev;
+        let (low, high) = (quantile(0.4), quantile(0.6));
+        let joint = summarise(quantile_difference(data, 0.4, 0.6));
+        let ci = joint.statistics.as_ref().unwrap().ci_95;
+        assert!(ci.low > 0.0);
+        let independent = (sd(&low).powi(2) + sd(&high).powi(2)).sqrt();
+        assert!(
+            sd(&joint) < 0.8 * independent,
+            "{} {independent}",
+            sd(&joint)
+        );
+    }
+
+    #[test]
+    fn theil_sen_resists_outliers() {
+        let noise = normal_sample(60, 11);
+        let x: Vec<f64> = (0..60).map(|i| i as f64 / 6.0).collect();
+        let mut y: Vec<f64> = x.iter().zip(&noise).map(|(x, e)| 2.0 * x + e).collect();
+        for i in [50, 54, 58] {
+            y[i] -= 40.0;
+        }
+        let (mx, my) = (mean(&x), mean(&y));
+        let ols = x
+            .iter()
+            .zip(&y)
+            .map(|(a, b)| (a - mx) * (b - my))
+            .sum::<f64>()
+            / x.iter().map(|a| (a - mx).powi(2)).sum::<f64>();
+        let summary = summarise(theil_sen(x, y));
+        let slope = *summary.central.as_ref().unwrap();
+        assert!((slope - 2.0).abs() < 0.2, "{slope}");
+        assert!((slope - 2.0).abs() < (ols - 2.0).abs());
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.low < slope && slope < ci.high, "{ci:?}");
+        assert!(ci.high - ci.low < 1.0);
+    }
+
+    #[test]
+    fn hodges_lehmann_matches_median_on_symmetric_data() {
+        let data = normal_sample(101, 12);
+        let mut sorted = data.clone();
+        sorted.sort_unstable_by(f64::total_cmp);
+        let median = sorted[50];
+        let summary = summarise(hodges_lehmann(data));
+        let hl = *summary.central.as_ref().unwrap();
+        assert!((hl - median).abs() < 0.15, "{hl} vs {median}");
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.high - ci.low > 0.0);
+        assert!(ci.low < hl && hl < ci.high);
+
+        let tiny = hodges_lehmann(vec![1.0, 2.0, 10.0]);
+        // Walsh averages 1, 1.5, 5.5, 2, 6, 10: median (2 + 5.5)/2.
+        assert_eq!(tiny.apply(&[0, 1, 2]).unwrap(), 3.75);
+    }
+
+    #[test]
+    fn hodges_lehmann_selection_matches_brute_force() {
+        let brute = |values: &[f64]| {
+            let mut averages = Vec::new();
+            for (k, a) in values.iter().enumerate() {
+                averages.extend(values[k..].iter().map(|b| (a + b) / 2.0));
+            }
+            averages.sort_unstable_by(f64::total_cmp);
+            let mid = averages.len() / 2;
+            if averages.len().is_multiple_of(2) {
+                (averages[mid - 1] + averages[mid]) / 2.0
+            } else {
+                averages[mid]
+            }
+        };
+        for (n, seed) in [(1, 1), (2, 2), (7, 3), (40, 4), (81, 5)] {
+            // Rounded so that ties occur.
+            let data: Vec<f64> = normal_sample(n, seed)
+                .iter()
+                .map(|v| (v * 4.0).round() / 4.0 - 0.5)
+                .collect();
+            let indices: Vec<usize> = (0..n).collect();
+            let hl = hodges_lehmann(data.clone()).apply(&indices).unwrap();
+            assert_eq!(hl, brute(&data), "n = {n}");
+        }
+        assert!(hodges_lehmann(vec![1.0, f64::NAN]).apply(&[0, 1]).is_err());
+    }
+
+    #[test]
+    fn importance_mean_undoes_oversampled_tail() {
+        // Target: uniform on 0..100. The proposal oversampled the tail
+        // (values >= 80) four-fold, so those carry weight 1/4.
+        let mut data = Vec::new();
+        let mut weights = Vec::new();
+        for v in 0..100 {
+            let copies = if v >= 80 { 4 } else { 1 };
+            for _ in 0..copies {
+                data.push(v as f64);
+                weights.push(1.0 / copies as f64);
+            }
+        }
+        let naive = mean(&data);
+        let summary = summarise(importance_mean(data, weights));
+        let weighted = summary.central.unwrap();
+        assert!((weighted - 49.5).abs() < 1e-9);
+        assert!(naive > 55.0);
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.low < 49.5 && ci.high > 49.5);
+    }
+
+    #[test]
+    fn auc_separable_and_random() {
+        let labels: Vec<bool> = (0..200).map(|i| i % 2 == 0).collect();
+        let separable: Vec<f64> = (0..200)
+            .map(|i| if i % 2 == 0 { 1.0 + i as f64 } else { -1.0 })
+            .collect();
+        let s = summarise(auc(separable, labels.clone()));
+        assert_eq!(s.central.unwrap(), 1.0);
+        let ci = s.statistics.unwrap().ci_95;
+        assert!(ci.high - ci.low < 1e-9);
+
+        let random = normal_sample(200, 3);
+        let delong = delong_variance(&random, &labels).unwrap();
+        let s = summarise(auc(random, labels));
+        assert!((s.central.unwrap() - 0.5).abs() < 0.1);
+        let stats = s.statistics.unwrap();
+        assert!(stats.ci_95.low < 0.5 && stats.ci_95.high > 0.5);
+        // Bootstrap SE and DeLong SE agree roughly.
+        assert!((stats.stddev / delong.sqrt() - 1.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn auc_handles_ties_with_midranks() {
+        let pairs = vec![(1.0, true), (1.0, false), (2.0, true), (0.0, false)];
+        // P(+ > -) = (1 + 0.5 + 1 + 1) / 4
+        assert_eq!(mann_whitney_auc(pairs).unwrap(), 0.875);
+    }
+
+    #[test]
+    fn grouped_means_recovered_per_group() {
+        let mut data = normal_sample(100, 4);
+        let group_of: Vec<usize> = (0..100).map(|i| i % 2).collect();
+        for (x, g) in data.iter_mut().zip(&group_of) {
+            *x += if *g == 0 { 10.0 } else { 20.0 };
+        }
+        let summary: BootstrapSummary<Vec<f64>> = Bootstrap::new(grouped(data, group_of, mean))
+            .n_boot(500)
+            .seed(5)
+            .run()
+            .unwrap()
+            .summarise();
+        let stats = summary.statistics.unwrap();
+        assert_eq!(stats.len(), 2);
+        for (s, truth) in stats.iter().zip([10.0, 20.0]) {
+            assert!(s.ci_95.low < truth && truth < s.ci_95.high);
+            assert!(s.ci_95.high - s.ci_95.low < 1.5);
+        }
+    }
+
+    #[test]
+    fn diff_in_diff_recovers_treatment_effect() {
+        let noise = normal_sample(80, 6);
+        let (mut outcomes, mut treated, mut post, mut unit_of) =
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for u in 0..40 {
+            let is_treated = u % 2 == 0;
+            let unit_effect = (u as f64 * 0.7).sin() * 5.0;
+            for period in [false, true] {
+                let effect = if is_treated && period { 3.0 } else { 0.0 };
+                let trend = if period { 2.0 } else { 0.0 };
+                outcomes.push(unit_effect + trend + effect + noise[2 * u + usize::from(period)]);
+                treated.push(is_treated);
+                post.push(period);
+                unit_of.push(u);
+            }
+        }
+        let summary = summarise(diff_in_diff(outcomes, treated, post, unit_of));
+        assert!((summary.central.unwrap() - 3.0).abs() < 1.0);
+        let ci = summary.statistics.unwrap().ci_95;
+        assert!(ci.low > 0.0 && ci.low < 3.0 && ci.high > 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn trimmed_mean_rejects_half_trim() {
+        trimmed_mean(vec![1.0, 2.0], 0.5);
+    }
+}
@@ -0,0 +1,123 @@
+//! Plain-text export of replicas for analysis elsewhere.
+
+use crate::bootstrap::BootstrapResult;
+use std::io::{self, Write};
+
+/// Replica types that flatten to a row of numbers for
+/// [`BootstrapResult::to_csv`]: one field for a scalar, one per component
+/// for a vector.
+pub trait CsvRow {
+    fn fields(&self) -> Vec<f64>;
+}
+
+impl CsvRow for f64 {
+    fn fields(&self) -> Vec<f64> {
+        vec![*self]
+    }
+}
+
+impl CsvRow for f32 {
+    fn fields(&self) -> Vec<f64> {
+        vec![f64::from(*self)]
+    }
+}
+
+impl CsvRow for Vec<f64> {
+    fn fields(&self) -> Vec<f64> {
+        self.clone()
+    }
+}
+
+impl CsvRow for Vec<f32> {
+    fn fields(&self) -> Vec<f64> {
+        self.iter().copied().map(f64::from).collect()
+    }
+}
+
+impl<T: CsvRow> BootstrapResult<T> {
+    /// Write the successful replicas as CSV, one row per replica under the
+    /// header `replica,v0,v1,…`: the replica's position in `samples`, then
+    /// its components. Values use Rust's shortest round-tripping format
+    /// (`NaN` and `inf` for non-finite values). With no replicas only the
+    /// header is written, with a single `v0` column.
+    ///
+    /// # Errors
+    ///
+    /// Errors from `w`, or [`io::ErrorKind::InvalidData`] if a replica has
+    /// a different number of components from the first.
+    pub fn to_csv<W: Write>(&self, w: W) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        let width = self.samples.first().map_or(1, |s| s.fields().len());
+        write!(w, "replica")?;
+        for j in 0..width {
+            write!(w, ",v{j}")?;
+        }
+        writeln!(w)?;
+        for (i, sample) in self.samples.iter().enumerate() {
+            let fields = sample.fields();
+            if fields.len() != width {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "replica {i} has {} components, expected {width}",
+                        fields.len()
+                    ),
+                ));
+            }
+            write!(w, "{i}")?;
+            for x in fields {
+                write!(w, ",{x}")?;
+            }
+            writeln!(w)?;
+        }
+        w.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap::{Bootstrap, Estimator};
+
+    #[test]
+    fn vector_replicas_one_column_per_component() {
+        let est = Estimator::new((0..8).collect(), |ind: &[usize]| {
+            let mean = ind.iter().sum::<usize>() as f64 / ind.len() as f64;
+            Ok(vec![mean, mean * mean, -1.5])
+        });
+        let result = Bootstrap::new(est).n_boot(25).seed(3).run().unwrap();
+        let mut out = Vec::new();
+        result.to_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "replica,v0,v1,v2");
+        assert_eq!(lines.len(), 26);
+        for (i, line) in lines[1..].iter().enumerate() {
+            let cells: Vec<&str> = line.split(',').collect();
+            assert_eq!(cells[0], i.to_string());
+            let values: Vec<f64> = cells[1..].iter().map(|c| c.parse().unwrap()).collect();
+            assert_eq!(values, result.samples[i]);
+        }
+    }
+
+    #[test]
+    fn ragged_replicas_are_rejected() {
+        let est = Estimator::new((0..8).collect(), |ind: &[usize]| {
+            Ok(vec![0.0; ind[0] % 2 + 1])
+        });
+        let result = Bootstrap::new(est).n_boot(50).seed(1).run().unwrap();
+        let err = result.to_csv(std::io::sink()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let scalar = Bootstrap::new(Estimator::new((0..4).collect(), |_: &[usize]| Ok(0.25)))
+            .n_boot(2)
+            .seed(1)
+            .run()
+            .unwrap();
+        let mut out = Vec::new();
+        scalar.to_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "replica,v0\n0,0.25\n1,0.25\n"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Bootstrap hypothesis tests built on the replica distribution.
+
+use crate::bootstrap::Bootstrap;
+use crate::error::BootstrapError;
+use crate::summary::BootstrapSummary;
+use serde::Serialize;
+
+/// Fractions of `replicas` at or below, and at or above, `theta_0`.
+fn tail_fractions(replicas: &[f64], theta_0: f64) -> (f64, f64) {
+    let n = replicas.len() as f64;
+    let below = replicas.iter().filter(|&&x| x <= theta_0).count() as f64;
+    let above = replicas.iter().filter(|&&x| x >= theta_0).count() as f64;
+    (below / n, above / n)
+}
+
+/// Two-sided percentile p-value for `H₀: θ = theta_0`,
+/// `min(1, 2·min(P*(θ* ≤ θ₀), P*(θ* ≥ θ₀)))`. `None` without replicas.
+pub fn two_sided_p_value(replicas: &[f64], theta_0: f64) -> Option<f64> {
+    if replicas.is_empty() {
+        return None;
+    }
+    let (below, above) = tail_fractions(replicas, theta_0);
+    Some((2.0 * below.min(above)).min(1.0))
+}
+
+/// Naive and double-bootstrap-calibrated two-sided p-values. See
+/// [`Bootstrap::calibrated_p_value`].
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct CalibratedPValue {
+    pub naive: f64,
+    pub calibrated: f64,
+    /// Outer replicas that contributed to the calibration.
+    pub n_valid: usize,
+}
+
+impl Bootstrap<f64> {
+    /// Two-sided p-value for `H₀: θ = theta_0`, calibrated by a double
+    /// bootstrap (Beran's prepivoting).
+    ///
+    /// The naive p-value `p̂` comes from the outer replicas. Its actual
+    /// null distribution is not exactly uniform, so each outer resample is
+    /// itself bootstrapped `n_inner` times and tested against the value that
+    /// is true in the bootstrap world, `θ̂`, giving `p*_b`. The calibrated
+    /// p-value is the fraction of `p*_b ≤ p̂`, i.e. `p̂` pushed through the
+    /// estimated null distribution of the p-value. Costs `n_boot·n_inner`
+    /// estimator calls. Fails with [`BootstrapError::NoValidSamples`] when
+    /// no outer replica yields an inner p-value.
+    pub fn calibrated_p_value(
+        self,
+        theta_0: f64,
+        n_inner: usize,
+    ) -> Result<CalibratedPValue, BootstrapError> {
+        let nested =
+            self.run_nested(n_inner, |central, inner| two_sided_p_value(inner, central))?;
+        let outer: Vec<f64> = nested.iter().map(|o| o.0).collect();
+        let naive = two_sided_p_value(&outer, theta_0)
+            .expect("a nested run keeps at least one outer replica");
+        let n_valid = nested.len();
+        let calibrated = nested.iter().filter(|o| o.1 <= naive).count() as f64 / n_valid as f64;
+        Ok(CalibratedPValue {
+            naive,
+            calibrated,
+            n_valid,
+        })
+    }
+}
+
+/// Outcome of a two one-sided tests (TOST) equivalence test. See
+/// [`BootstrapSummary::equivalence_test`].
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct EquivalenceTest {
+    /// p-value for `H₀: δ ≤ −margin`.
+    pub p_lower: f64,
+    /// p-value for `H₀: δ ≥ margin`.
+    pub p_upper: f64,
+    /// Both nulls rejected at the requested `alpha`.
+    pub equivalent: bool,
+}
+
+impl BootstrapSummary<f64> {
+    /// Two-sided achieved significance level for `H₀: θ = theta_0`; see
+    /// [`two_sided_p_value`]. `NaN` without replicas.
+    pub fn p_value(&self, theta_0: f64) -> f64 {
+        two_sided_p_value(&self.replicas, theta_0).unwrap_or(f64::NAN)
+    }
+
+    /// One-sided p-value for `H₀: θ ≤ theta_0` against `θ > theta_0`: the
+    /// fraction of replicas at or below `theta_0`. `NaN` without replicas.
+    pub fn p_value_greater(&self, theta_0: f64) -> f64 {
+        if self.replicas.is_empty() {
+            return f64::NAN;
+        }
+        tail_fractions(&self.replicas, theta_0).0
+    }
+
+    /// One-sided p-value for `H₀: θ ≥ theta_0` against `θ < theta_0`: the
+    /// fraction of replicas at or above `theta_0`. `NaN` without replicas.
+    pub fn p_value_less(&self, theta_0: f64) -> f64 {
+        if self.replicas.is_empty() {
+            return f64::NAN;
+        }
+        tail_fractions(&self.replicas, theta_0).1
+    }
+
+    /// Bootstrap distribution of `self − other` from two independent
+    /// bootstraps, pairing replicas by position (the extra replicas of the
+    /// longer run are ignored). Valid because the two runs resample
+    /// independent groups, so any pairing gives draws of the difference.
+    pub fn difference_replicas(&self, other: &Self) -> Vec<f64> {
+        self.replicas
+            .iter()
+            .zip(&other.replicas)
+            .map(|(a, b)| a - b)
+            .collect()
+    }
+
+    /// Two one-sided tests that the difference `δ = self − other` lies
+    /// within `(−margin, margin)`. Each p-value is the fraction of
+    /// difference replicas on the wrong side of its margin; equivalence is
+    /// declared when both are below `alpha`, which matches the `1 − 2α`
+    /// percentile interval of `δ` lying inside the margins. `None` without
+    /// replicas.
+    pub fn equivalence_test(
+        &self,
+        other: &Self,
+        margin: f64,
+        alpha: f64,
+    ) -> Option<EquivalenceTest> {
+        let diff = self.difference_replicas(other);
+        if diff.is_empty() {
+            return None;
+        }
+        let (p_lower, _) = tail_fractions(&diff, -margin);
+        let (_, p_upper) = tail_fractions(&diff, margin);
+        Some(EquivalenceTest {
+            p_lower,
+            p_upper,
+            equivalent: p_lower < alpha && p_upper < alpha,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::Estimator;
+    use crate::test_util::mean_estimator;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    use rand_distr::{Distribution, Exp1};
+
+    #[test]
+    fn p_value_tails() {
+        let replicas: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        assert_eq!(two_sided_p_value(&replicas, 4.0), Some(0.1));
+        assert_eq!(two_sided_p_value(&replicas, 49.5), Some(1.0));
+        assert_eq!(two_sided_p_value(&[], 0.0), None);
+    }
+
+    #[test]
+    fn summary_p_values_on_shifted_mean() {
+        use crate::summary::Summarisable;
+        let mut rng = SmallRng::seed_from_u64(8);
+        let data: Vec<f64> = (0..60).map(|_| Exp1.sample(&mut rng)).collect();
+        let est = mean_estimator(&data);
+        let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(2000)
+            .seed(1)
+            .run()
+            .unwrap()
+            .summarise();
+        let central = *summary.central.as_ref().unwrap();
+        assert!(summary.p_value(central) > 0.8);
+        assert!(summary.p_value(central + 1.0) < 0.01);
+        assert!(summary.p_value_greater(central - 1.0) < 0.005);
+        assert!(summary.p_value_less(central - 1.0) > 0.99);
+        let two = summary.p_value(central + 0.2);
+        let one = summary.p_value_less(central + 0.2);
+        assert!((two - 2.0 * one).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tost_declares_near_identical_groups_equivalent() {
+        use crate::summary::Summarisable;
+        let group = |shift: f64, seed: u64| {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let data: Vec<f64> = (0..200)
+                .map(|_| Distribution::<f64>::sample(&Exp1, &mut rng) + shift)
+                .collect();
+            let est = mean_estimator(&data);
+            let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+                .n_boot(2000)
+                .seed(seed)
+                .run()
+                .unwrap()
+                .summarise();
+            summary
+        };
+        let (a, b) = (group(0.0, 1), group(0.02, 2));
+        let tost = a.equivalence_test(&b, 0.5, 0.05).unwrap();
+        assert!(tost.equivalent, "{tost:?}");
+        let shifted = group(1.0, 3);
+        assert!(!a.equivalence_test(&shifted, 0.5, 0.05).unwrap().equivalent);
+    }
+
+    #[test]
+    fn calibration_brings_size_closer_to_nominal() {
+        // Mean of a small exponential sample under the true null θ₀ = 1:
+        // the naive percentile test over-rejects.
+        let alpha = 0.1;
+        let trials = 150;
+        let mut rng = SmallRng::seed_from_u64(21);
+        let (mut naive, mut calibrated) = (0, 0);
+        for t in 0..trials {
+            let data: Vec<f64> = (0..12).map(|_| Exp1.sample(&mut rng)).collect();
+            let est = mean_estimator(&data);
+            let p = Bootstrap::new(est)
+                .n_boot(150)
+                .seed(t)
+                .calibrated_p_value(1.0, 60)
+                .unwrap();
+            naive += usize::from(p.naive < alpha);
+            calibrated += usize::from(p.calibrated < alpha);
+        }
+        let err = |r: usize| (r as f64 / trials as f64 - alpha).abs();
+        assert!(
+            err(calibrated) < err(naive),
+            "naive {naive}, calibrated {calibrated}"
+        );
+    }
+
+    #[test]
+    fn calibrated_p_value_without_valid_replicas_is_an_error() {
+        // Only the original sample, with no index repeated, succeeds.
+        let est = Estimator::new((0..20).collect(), |ind: &[usize]| {
+            let mut distinct = ind.to_vec();
+            distinct.sort_unstable();
+            distinct.dedup();
+            if distinct.len() == ind.len() {
+                Ok(1.0)
+            } else {
+                Err(crate::EstimatorError::new("repeated index"))
+            }
+        });
+        let err = Bootstrap::new(est)
+            .n_boot(50)
+            .seed(3)
+            .calibrated_p_value(1.0, 20)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BootstrapError::NoValidSamples {
+                n_boot: 50,
+                failed_samples: 50
+            }
+        ));
+    }
+}
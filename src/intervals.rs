@@ -0,0 +1,476 @@
+//! Confidence intervals beyond the plain percentile intervals reported in
+//! `Statistics`.
+
+use crate::bootstrap::{Bootstrap, BootstrapResult, Estimator};
+use crate::error::BootstrapError;
+use crate::jackknife::leave_out_values;
+use crate::summary::{
+    ConfidenceInterval, ONE_SIGMA, THREE_SIGMA, TWO_SIGMA, bca_interval, fraction_below,
+    normal_quantile, quantile_sorted,
+};
+use serde::Serialize;
+
+fn sorted(mut values: Vec<f64>) -> Vec<f64> {
+    values.sort_unstable_by(f64::total_cmp);
+    values
+}
+
+/// The four classic bootstrap intervals at one level, side by side. See
+/// [`BootstrapResult::interval_table`].
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct IntervalTable {
+    pub level: f64,
+    /// Replica quantiles `[θ*α/₂, θ*₁₋α/₂]`.
+    pub percentile: ConfidenceInterval,
+    /// Percentile interval reflected about the estimate,
+    /// `[2θ̂ − θ*₁₋α/₂, 2θ̂ − θ*α/₂]`.
+    pub basic: ConfidenceInterval,
+    /// `θ̂ ± z·se` with `se` the replica standard deviation; symmetric by
+    /// construction.
+    pub normal: ConfidenceInterval,
+    /// Bias-corrected and accelerated percentile interval; `None` when the
+    /// jackknife fails or every replica lies on one side of `θ̂`.
+    pub bca: Option<ConfidenceInterval>,
+}
+
+/// Percentile intervals calibrated by a double bootstrap, at the same one-,
+/// two- and three-sigma levels as [`Statistics`](crate::Statistics). See
+/// [`Bootstrap::calibrated`].
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct CalibratedIntervals {
+    /// Outer replicas whose estimate and inner bootstrap both succeeded.
+    pub n: usize,
+    pub ci_68: ConfidenceInterval,
+    pub ci_95: ConfidenceInterval,
+    pub ci_99: ConfidenceInterval,
+}
+
+/// Sorted outer replicas and their sorted prepivoted roots.
+type Prepivoted = (Vec<f64>, Vec<f64>);
+
+/// Outer-replica quantiles at the `α/2` and `1 − α/2` quantiles of the
+/// prepivoted roots, both sorted.
+fn calibrated_interval(outer: &[f64], roots: &[f64], level: f64) -> ConfidenceInterval {
+    let alpha = 1.0 - level;
+    ConfidenceInterval {
+        low: quantile_sorted(outer, quantile_sorted(roots, alpha / 2.0)),
+        high: quantile_sorted(outer, quantile_sorted(roots, 1.0 - alpha / 2.0)),
+    }
+}
+
+impl BootstrapResult<f64> {
+    /// Fraction of replicas strictly below the central value: about ½ for
+    /// a median-unbiased statistic, and the source of BCa's bias correction
+    /// `z₀ = Φ⁻¹(fraction)`. `None` without a central value or replicas.
+    pub fn fraction_below_central(&self) -> Option<f64> {
+        let theta = *self.central.as_ref().ok()?;
+        if self.samples.is_empty() {
+            return None;
+        }
+        Some(fraction_below(&self.samples, theta))
+    }
+
+    /// Percentile interval at `level` read off the mid-p ECDF of the
+    /// replicas, `F(x) = P*(θ* < x) + ½·P*(θ* = x)`, which counts each
+    /// discrete jump as half passed at its own value. The interval runs from
+    /// the smallest replica value with `F(x) ≥ α/2` to the largest with
+    /// `F(x) ≤ 1 − α/2`.
+    ///
+    /// For discrete statistics (counts, proportions of small samples) the
+    /// replicas pile up on a few values, and the plain percentile interval
+    /// keeps a whole extreme atom as soon as any part of it reaches the
+    /// tail, which makes it conservative; the mid-p version keeps an atom
+    /// only if its midpoint does, giving coverage closer to nominal. For
+    /// continuous statistics the two agree. Returns `None` without
+    /// replicas or for a level outside `(0, 1)`.
+    pub fn mid_p_interval(&self, level: f64) -> Option<ConfidenceInterval> {
+        if self.samples.is_empty() || !(level > 0.0 && level < 1.0) {
+            return None;
+        }
+        let replicas = sorted(self.samples.clone());
+        let total = replicas.len() as f64;
+        let mut below = 0;
+        let ecdf: Vec<(f64, f64)> = replicas
+            .chunk_by(|a, b| a == b)
+            .map(|tied| {
+                let mid = (below as f64 + tied.len() as f64 / 2.0) / total;
+                below += tied.len();
+                (tied[0], mid)
+            })
+            .collect();
+        let alpha = 1.0 - level;
+        let first = ecdf.partition_point(|&(_, m)| m < alpha / 2.0);
+        let last = ecdf.partition_point(|&(_, m)| m <= 1.0 - alpha / 2.0);
+        // An atom holding both tails is the whole interval.
+        let first = first.min(ecdf.len() - 1);
+        let last = last.saturating_sub(1).max(first);
+        Some(ConfidenceInterval {
+            low: ecdf[first].0,
+            high: ecdf[last].0,
+        })
+    }
+
+    /// Percentile, basic, normal and BCa intervals at `level` from one run,
+    /// for comparing methods. `estimator` must be the one that produced
+    /// this result: BCa's acceleration comes from its jackknife over the
+    /// full data, which costs `n` more estimator calls. Returns `None`
+    /// without a central value, with fewer than two replicas, or for a
+    /// level outside `(0, 1)`.
+    pub fn interval_table(&self, estimator: &Estimator<f64>, level: f64) -> Option<IntervalTable> {
+        if !(level > 0.0 && level < 1.0) || self.samples.len() < 2 {
+            return None;
+        }
+        let theta = *self.central.as_ref().ok()?;
+        let replicas = sorted(self.samples.clone());
+        let alpha = 1.0 - level;
+        let (lo, hi) = (
+            quantile_sorted(&replicas, alpha / 2.0),
+            quantile_sorted(&replicas, 1.0 - alpha / 2.0),
+        );
+        let m = replicas.len() as f64;
+        let mean = replicas.iter().sum::<f64>() / m;
+        let se = (replicas.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (m - 1.0)).sqrt();
+        let z = normal_quantile(1.0 - alpha / 2.0);
+
+        let bca = leave_out_values(estimator, 1, true)
+            .ok()
+            .and_then(|jack| bca_interval(&replicas, theta, &jack, level));
+
+        Some(IntervalTable {
+            level,
+            percentile: ConfidenceInterval { low: lo, high: hi },
+            basic: ConfidenceInterval {
+                low: 2.0 * theta - hi,
+                high: 2.0 * theta - lo,
+            },
+            normal: ConfidenceInterval {
+                low: theta - z * se,
+                high: theta + z * se,
+            },
+            bca,
+        })
+    }
+}
+
+impl Bootstrap<f64> {
+    /// Percentile interval at `level` with coverage calibrated by a double
+    /// bootstrap (Beran's prepivoting), whose coverage error is an order
+    /// smaller than the plain percentile interval's.
+    ///
+    /// Each outer resample is bootstrapped `n_inner` more times and
+    /// `u_b = P*(θ** ≤ θ̂)` recorded: where the central value falls in that
+    /// resample's own bootstrap distribution. If the percentile method were
+    /// exact the `u_b` would be uniform; their `α/2` and `1 − α/2` quantiles
+    /// are the nominal levels that actually achieve `level` coverage, and
+    /// the interval takes the outer replicas' quantiles at those levels.
+    /// Costs `n_boot·n_inner` estimator calls.
+    pub fn prepivoted_interval(
+        self,
+        level: f64,
+        n_inner: usize,
+    ) -> Result<Option<ConfidenceInterval>, BootstrapError> {
+        if !(level > 0.0 && level < 1.0) {
+            return Ok(None);
+        }
+        Ok(self
+            .prepivoted_roots(n_inner)?
+            .map(|(outer, roots)| calibrated_interval(&outer, &roots, level)))
+    }
+
+    /// Iterated bootstrap: the one-, two- and three-sigma percentile
+    /// intervals, each calibrated as in
+    /// [`prepivoted_interval`](Self::prepivoted_interval) from a single
+    /// nested run, in place of nesting a `Bootstrap` inside the estimator
+    /// by hand. The inner bootstraps run sequentially within each outer
+    /// replica, so only the outer loop is parallel (and none of it with
+    /// [`parallel(false)`](Self::parallel)). Costs
+    /// `n_boot·inner_n_boot` estimator calls. Returns `None` if no outer
+    /// replica yields an inner bootstrap.
+    pub fn calibrated(
+        self,
+        inner_n_boot: usize,
+    ) -> Result<Option<CalibratedIntervals>, BootstrapError> {
+        Ok(self
+            .prepivoted_roots(inner_n_boot)?
+            .map(|(outer, roots)| CalibratedIntervals {
+                n: outer.len(),
+                ci_68: calibrated_interval(&outer, &roots, ONE_SIGMA),
+                ci_95: calibrated_interval(&outer, &roots, TWO_SIGMA),
+                ci_99: calibrated_interval(&outer, &roots, THREE_SIGMA),
+            }))
+    }
+
+    /// Sorted outer replicas and sorted roots `u_b`, or `None` if there
+    /// are none.
+    fn prepivoted_roots(self, n_inner: usize) -> Result<Option<Prepivoted>, BootstrapError> {
+        let nested = match self.run_nested(n_inner, |central, inner| {
+            (!inner.is_empty()).then(|| {
+                inner.iter().filter(|&&x| x <= central).count() as f64 / inner.len() as f64
+            })
+        }) {
+            Err(BootstrapError::NoValidSamples { .. }) => return Ok(None),
+            nested => nested?,
+        };
+        let (outer, roots): (Vec<f64>, Vec<f64>) = nested.into_iter().unzip();
+        Ok(Some((sorted(outer), sorted(roots))))
+    }
+}
+
+impl BootstrapResult<(f64, f64)> {
+    /// The studentized pivots `t* = (θ* − θ̂)/SE*`, in replica order, for
+    /// building intervals other than [`studentized_interval`](Self::studentized_interval)
+    /// from them. Replicates with a zero or non-finite SE are skipped; empty
+    /// without a central value.
+    pub fn pivot_distribution(&self) -> Vec<f64> {
+        let Ok(&(theta, _)) = self.central.as_ref() else {
+            return Vec::new();
+        };
+        self.samples
+            .iter()
+            .filter(|(_, s)| s.is_finite() && *s > 0.0)
+            .map(|(t, s)| (t - theta) / s)
+            .collect()
+    }
+
+    /// Studentized (bootstrap-t) interval at `level` from a run of an
+    /// estimator built with [`Estimator::with_std_error`](crate::Estimator::with_std_error).
+    ///
+    /// The pivots `t* = (θ* − θ̂)/SE*` stand in for the distribution of
+    /// `(θ̂ − θ)/SE`, giving `[θ̂ − t*₁₋α/₂·SE, θ̂ − t*α/₂·SE]` with `SE` the
+    /// central value's own standard error. Replicates with a zero or
+    /// non-finite SE are skipped. Returns `None` without a central value,
+    /// without usable replicates, or for a level outside `(0, 1)`.
+    pub fn studentized_interval(&self, level: f64) -> Option<ConfidenceInterval> {
+        if !(level > 0.0 && level < 1.0) {
+            return None;
+        }
+        let &(theta, se) = self.central.as_ref().ok()?;
+        let pivots = sorted(self.pivot_distribution());
+        if pivots.is_empty() {
+            return None;
+        }
+        let alpha = 1.0 - level;
+        Some(ConfidenceInterval {
+            low: theta - quantile_sorted(&pivots, 1.0 - alpha / 2.0) * se,
+            high: theta - quantile_sorted(&pivots, alpha / 2.0) * se,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bootstrap::{Bootstrap, Estimator, EstimatorError, SeEstimator};
+    use crate::samplers::SamplingStrategy;
+    use crate::summary::quantile_sorted;
+    use crate::test_util::mean_estimator;
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    use rand_distr::{Distribution, Exp1};
+
+    #[test]
+    fn prepivoting_improves_coverage_for_skewed_mean() {
+        let level = 0.9;
+        let trials = 120;
+        let mut rng = SmallRng::seed_from_u64(17);
+        let (mut plain, mut prepivot) = (0, 0);
+        for t in 0..trials {
+            let data: Vec<f64> = (0..10).map(|_| Exp1.sample(&mut rng)).collect();
+            let est = mean_estimator(&data);
+            let mut replicas = Bootstrap::new(est.clone())
+                .n_boot(200)
+                .seed(t)
+                .run()
+                .unwrap()
+                .samples;
+            replicas.sort_unstable_by(f64::total_cmp);
+            let (lo, hi) = (
+                quantile_sorted(&replicas, 0.05),
+                quantile_sorted(&replicas, 0.95),
+            );
+            plain += usize::from(lo <= 1.0 && 1.0 <= hi);
+            let ci = Bootstrap::new(est)
+                .n_boot(200)
+                .seed(t)
+                .prepivoted_interval(level, 80)
+                .unwrap()
+                .unwrap();
+            prepivot += usize::from(ci.low <= 1.0 && 1.0 <= ci.high);
+        }
+        let err = |hits: usize| (hits as f64 / trials as f64 - level).abs();
+        assert!(
+            err(prepivot) < err(plain),
+            "plain {plain}, prepivoted {prepivot}"
+        );
+    }
+
+    #[test]
+    fn calibrated_matches_prepivoted_intervals() {
+        let data: Vec<f64> = (0..15).map(|i| f64::from(i).powf(1.5)).collect();
+        let est = mean_estimator(&data);
+        let run = || Bootstrap::new(est.clone()).n_boot(150).seed(8);
+        let calibrated = run().calibrated(60).unwrap().unwrap();
+        assert_eq!(calibrated.n, 150);
+        let ci_95 = run()
+            .prepivoted_interval(crate::summary::TWO_SIGMA, 60)
+            .unwrap()
+            .unwrap();
+        assert_eq!(calibrated.ci_95.low, ci_95.low);
+        assert_eq!(calibrated.ci_95.high, ci_95.high);
+        assert!(calibrated.ci_68.low >= calibrated.ci_99.low);
+        assert!(calibrated.ci_68.high <= calibrated.ci_99.high);
+
+        let sequential = run().parallel(false).calibrated(60).unwrap().unwrap();
+        assert_eq!(sequential.ci_99.low, calibrated.ci_99.low);
+    }
+
+    #[test]
+    fn calibrated_intervals_draw_like_the_outer_run() {
+        let data: Vec<f64> = (0..40).map(|i| f64::from(i % 9).sqrt()).collect();
+        let est = mean_estimator(&data);
+        for sampler in [
+            SamplingStrategy::Balanced,
+            SamplingStrategy::Block { block_size: 4 },
+        ] {
+            let run = || {
+                Bootstrap::new(est.clone())
+                    .n_boot(200)
+                    .sampler(sampler.clone())
+                    .seed(21)
+                    .validate(|m: &f64| {
+                        (*m <= 1.9)
+                            .then_some(())
+                            .ok_or_else(|| EstimatorError::new("above the cap"))
+                    })
+            };
+            let mut kept = run().run().unwrap().samples;
+            kept.sort_unstable_by(f64::total_cmp);
+            assert!(kept.len() < 200, "{sampler:?} rejected nothing");
+
+            let calibrated = run().calibrated(40).unwrap().unwrap();
+            assert_eq!(calibrated.n, kept.len());
+            let (lo, hi) = (kept[0], kept[kept.len() - 1]);
+            for ci in [calibrated.ci_68, calibrated.ci_95, calibrated.ci_99] {
+                assert!(lo <= ci.low && ci.high <= hi, "{sampler:?}: {ci:?}");
+            }
+            let ci = run().prepivoted_interval(0.9, 40).unwrap().unwrap();
+            assert!(lo <= ci.low && ci.high <= 1.9, "{sampler:?}: {ci:?}");
+        }
+    }
+
+    #[test]
+    fn pivot_distribution_centred_near_zero() {
+        let mut rng = SmallRng::seed_from_u64(5);
+        let data: Vec<f64> = (0..200)
+            .map(|_| Distribution::<f64>::sample(&Exp1, &mut rng))
+            .collect();
+        let est = mean_estimator(&data);
+        let result = Bootstrap::new(est.with_std_error(SeEstimator::Jackknife, None))
+            .n_boot(2000)
+            .seed(4)
+            .run()
+            .unwrap();
+        let pivots = result.pivot_distribution();
+        assert_eq!(pivots.len(), 2000);
+        let n = pivots.len() as f64;
+        let mean = pivots.iter().sum::<f64>() / n;
+        let var = pivots.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        assert!(mean.abs() < 0.15, "{mean}");
+        assert!((var - 1.0).abs() < 0.2, "{var}");
+    }
+
+    #[test]
+    fn jackknife_and_nested_studentization_agree() {
+        let mut rng = SmallRng::seed_from_u64(8);
+        let data: Vec<f64> = (0..40).map(|_| Exp1.sample(&mut rng)).collect();
+        let est = mean_estimator(&data);
+        let interval = |se: SeEstimator| {
+            Bootstrap::new(est.clone().with_std_error(se, Some(3)))
+                .n_boot(1000)
+                .seed(3)
+                .run()
+                .unwrap()
+                .studentized_interval(0.9)
+                .unwrap()
+        };
+        let jack = interval(SeEstimator::Jackknife);
+        let nested = interval(SeEstimator::Bootstrap {
+            n_inner: 100,
+            sampler: SamplingStrategy::Iid,
+        });
+        let width = nested.high - nested.low;
+        assert!(jack.low < jack.high);
+        assert!(
+            (jack.low - nested.low).abs() < 0.2 * width,
+            "{jack:?} {nested:?}"
+        );
+        assert!(
+            (jack.high - nested.high).abs() < 0.2 * width,
+            "{jack:?} {nested:?}"
+        );
+    }
+
+    #[test]
+    fn interval_table_reports_all_four_methods() {
+        let mut rng = SmallRng::seed_from_u64(12);
+        let data: Vec<f64> = (0..30).map(|_| Exp1.sample(&mut rng)).collect();
+        let est = mean_estimator(&data);
+        let result = Bootstrap::new(est.clone())
+            .n_boot(2000)
+            .seed(4)
+            .run()
+            .unwrap();
+        let theta = *result.central.as_ref().unwrap();
+        let table = result.interval_table(&est, 0.95).unwrap();
+        let bca = table.bca.unwrap();
+        for ci in [table.percentile, table.basic, table.normal, bca] {
+            assert!(ci.low < theta && theta < ci.high, "{ci:?}");
+        }
+        let normal = table.normal;
+        assert!(((theta - normal.low) - (normal.high - theta)).abs() < 1e-12);
+        // The mean of exponential data is right-skewed, so the percentile
+        // interval reaches further above the estimate than below it.
+        let p = table.percentile;
+        assert!(p.high - theta > theta - p.low);
+    }
+
+    #[test]
+    fn fraction_below_central_off_half_for_skewed_statistic() {
+        // The sample variance of exponential data has a right-skewed
+        // sampling distribution, so most replicas fall below the estimate.
+        let mut rng = SmallRng::seed_from_u64(21);
+        let data: Vec<f64> = (0..25).map(|_| Exp1.sample(&mut rng)).collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind| {
+            let n = ind.len() as f64;
+            let m = ind.iter().map(|&i| data[i]).sum::<f64>() / n;
+            Ok(ind.iter().map(|&i| (data[i] - m).powi(2)).sum::<f64>() / (n - 1.0))
+        });
+        let result = Bootstrap::new(est).n_boot(2000).seed(2).run().unwrap();
+        let fraction = result.fraction_below_central().unwrap();
+        assert!(fraction > 0.55, "{fraction}");
+    }
+
+    #[test]
+    fn mid_p_interval_narrower_for_binomial_count() {
+        // Number of successes among 12 trials with 3 successes.
+        let successes: Vec<bool> = (0..12).map(|i| i % 4 == 0).collect();
+        let est = Estimator::new((0..successes.len()).collect(), move |ind| {
+            Ok(ind.iter().filter(|&&i| successes[i]).count() as f64)
+        });
+        let result = Bootstrap::new(est).n_boot(2000).seed(5).run().unwrap();
+        let mut replicas = result.samples.clone();
+        replicas.sort_unstable_by(f64::total_cmp);
+        let plain = (
+            quantile_sorted(&replicas, 0.025),
+            quantile_sorted(&replicas, 0.975),
+        );
+        let mid = result.mid_p_interval(0.95).unwrap();
+        assert!(mid.low < 3.0 && 3.0 < mid.high);
+        assert!(
+            mid.low >= plain.0 && mid.high <= plain.1,
+            "{mid:?} {plain:?}"
+        );
+        assert!(mid.high - mid.low < plain.1 - plain.0, "{mid:?} {plain:?}");
+    }
+}
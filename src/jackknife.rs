@@ -0,0 +1,220 @@
+//! Leave-one-out and block jackknife, as a deterministic counterpart to
+//! `Bootstrap` for bias and standard-error estimates.
+
+use crate::bootstrap::{Elementwise, Estimator};
+use crate::error::BootstrapError;
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// Jackknife of an [`Estimator`] over its own indices.
+///
+/// Each replicate leaves out one block of `block_size` consecutive indices
+/// (one index by default; any remainder at the front is always dropped, as
+/// in [`generate_block_jackknife_indices`](crate::samplers::generate_block_jackknife_indices)). With `g` replicates `θ₍ₖ₎` and
+/// their mean `θ̄`, the bias estimate is `(g − 1)(θ̄ − θ̂)` and the standard
+/// error `√((g − 1)/g · Σ(θ₍ₖ₎ − θ̄)²)`, componentwise for vector
+/// statistics.
+pub struct Jackknife<T> {
+    estimator: Estimator<T>,
+    block_size: usize,
+}
+
+impl<T: Elementwise> Jackknife<T> {
+    pub fn new(estimator: Estimator<T>) -> Self {
+        Self {
+            estimator,
+            block_size: 1,
+        }
+    }
+
+    /// Leave out blocks of `size` consecutive indices at a time, for
+    /// autocorrelated data.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    pub fn block_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "jackknife block size must be positive");
+        self.block_size = size;
+        self
+    }
+
+    /// Apply the estimator to the full sample and to every leave-out set.
+    /// Fails if the full-sample estimate or any replicate fails, or if
+    /// there are fewer than two blocks.
+    pub fn run(self) -> Result<JackknifeSummary<T>, BootstrapError> {
+        let Jackknife {
+            estimator,
+            block_size,
+        } = self;
+        let replicates = leave_out_values(&estimator, block_size, true)?;
+        let central = estimator
+            .apply(estimator.indices())
+            .map_err(BootstrapError::CentralFailed)?;
+
+        let g = replicates.len() as f64;
+        let mut sum = central.zero_like();
+        for r in &replicates {
+            sum.add_assign(r);
+        }
+        let mean = sum.scale(1.0 / g);
+        let mut sum_sq = central.zero_like();
+        for r in &replicates {
+            let d = r.sub(&mean);
+            sum_sq.add_assign(&d.mul(&d));
+        }
+        Ok(JackknifeSummary {
+            n: replicates.len(),
+            bias: mean.sub(&central).scale(g - 1.0),
+            std_error: sum_sq.scale((g - 1.0) / g).sqrt(),
+            central,
+            mean,
+            replicates,
+        })
+    }
+}
+
+/// The estimates behind [`Jackknife::run`]: replicate `k` leaves out the
+/// `k`-th block of `block_size` consecutive indices, after dropping any
+/// remainder at the front. Each leave-out set is built only when its turn
+/// comes. Runs on the current rayon pool when `parallel`, otherwise in
+/// order on the calling thread.
+pub(crate) fn leave_out_values<T: 'static + Send>(
+    estimator: &Estimator<T>,
+    block_size: usize,
+    parallel: bool,
+) -> Result<Vec<T>, BootstrapError> {
+    let indices = estimator.indices();
+    if indices.is_empty() {
+        return Err(BootstrapError::EmptyIndices);
+    }
+    let (start, n_blocks) = (indices.len() % block_size, indices.len() / block_size);
+    if n_blocks < 2 {
+        return Err(BootstrapError::InvalidInput(format!(
+            "jackknife needs at least two blocks, but {} indices make {n_blocks} of size {block_size}",
+            indices.len()
+        )));
+    }
+    let leave_out = |kept: &mut Vec<usize>, k: usize| {
+        let (lo, hi) = (start + k * block_size, start + (k + 1) * block_size);
+        kept.clear();
+        kept.extend_from_slice(&indices[start..lo]);
+        kept.extend_from_slice(&indices[hi..]);
+        estimator
+            .apply(kept)
+            .map_err(|error| BootstrapError::JackknifeFailed { left_out: k, error })
+    };
+    let capacity = indices.len() - start - block_size;
+    if parallel {
+        (0..n_blocks)
+            .into_par_iter()
+            .map_init(|| Vec::with_capacity(capacity), leave_out)
+            .collect()
+    } else {
+        let mut kept = Vec::with_capacity(capacity);
+        (0..n_blocks).map(|k| leave_out(&mut kept, k)).collect()
+    }
+}
+
+/// Output of [`Jackknife::run`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct JackknifeSummary<T> {
+    /// Number of replicates, one per left-out block.
+    pub n: usize,
+    /// Estimate on the full sample, `θ̂`.
+    pub central: T,
+    /// Mean of the replicates, `θ̄`.
+    pub mean: T,
+    /// `(n − 1)(θ̄ − θ̂)`; subtract it from `central` to bias-correct.
+    pub bias: T,
+    pub std_error: T,
+    /// Replicate `k` leaves out block `k`.
+    pub replicates: Vec<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{mean_estimator, mean_of};
+
+    fn data() -> Vec<f64> {
+        (0..25).map(|i| ((i * 37) % 17) as f64 * 0.5).collect()
+    }
+
+    #[test]
+    fn jackknife_of_mean_gives_textbook_se_and_no_bias() {
+        let x = data();
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        let s2 = x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let est = mean_estimator(&x);
+        let jack = Jackknife::new(est).run().unwrap();
+        assert_eq!(jack.n, 25);
+        assert!((jack.central - mean).abs() < 1e-12);
+        assert!(jack.bias.abs() < 1e-12);
+        assert!((jack.std_error - (s2 / n).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn jackknife_bias_of_plug_in_variance_is_minus_s2_over_n() {
+        let x = data();
+        let n = x.len() as f64;
+        let mean = x.iter().sum::<f64>() / n;
+        let s2 = x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let est = Estimator::new((0..x.len()).collect(), move |ind: &[usize]| {
+            let v: Vec<f64> = ind.iter().map(|&i| x[i]).collect();
+            let m = v.iter().sum::<f64>() / v.len() as f64;
+            let plug_in = v.iter().map(|a| (a - m).powi(2)).sum::<f64>() / v.len() as f64;
+            Ok(vec![m, plug_in])
+        });
+        let jack = Jackknife::new(est).run().unwrap();
+        assert!(jack.bias[0].abs() < 1e-12);
+        assert!((jack.bias[1] + s2 / n).abs() < 1e-10);
+        assert!((jack.central[1] - jack.bias[1] - s2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn tuple_with_vector_part_keeps_its_length() {
+        let x = data();
+        let est = Estimator::new((0..x.len()).collect(), move |ind: &[usize]| {
+            let m = mean_of(&x, ind);
+            Ok((m, vec![m, 2.0 * m, 3.0 * m]))
+        });
+        let jack = Jackknife::new(est).run().unwrap();
+        assert_eq!(jack.mean.1.len(), 3);
+        assert_eq!(jack.std_error.1.len(), 3);
+        for (k, se) in jack.std_error.1.iter().enumerate() {
+            assert!((se - (k + 1) as f64 * jack.std_error.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn block_jackknife_leaves_out_blocks_and_reports_failures() {
+        let est = Estimator::new((10..19).collect(), |ind: &[usize]| {
+            Ok(ind.iter().sum::<usize>() as f64)
+        });
+        let jack = Jackknife::new(est).block_size(3).run().unwrap();
+        assert_eq!(jack.n, 3);
+        // Sum 126 minus each block's 33, 42, 51.
+        assert_eq!(jack.replicates, vec![93.0, 84.0, 75.0]);
+
+        let needs_ten = Estimator::new((10..19).collect(), |ind: &[usize]| {
+            if ind.contains(&10) {
+                Ok(ind.len() as f64)
+            } else {
+                Err(crate::EstimatorError::new("needs index 10"))
+            }
+        });
+        match Jackknife::new(needs_ten).block_size(3).run() {
+            Err(BootstrapError::JackknifeFailed { left_out: 0, .. }) => {}
+            other => panic!("{other:?}"),
+        }
+
+        let one_block = Estimator::new((0..5).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+        assert!(matches!(
+            Jackknife::new(one_block).block_size(3).run(),
+            Err(BootstrapError::InvalidInput(_))
+        ));
+    }
+}
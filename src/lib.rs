@@ -1,12 +1,45 @@
+#[cfg(feature = "ndarray")]
+pub mod array;
+pub mod bca;
+pub mod blb;
 pub mod bootstrap;
+pub mod error;
+pub mod estimators;
+pub mod export;
+pub mod hypothesis;
+pub mod intervals;
+pub mod jackknife;
+#[cfg(feature = "plotters")]
+pub mod plot;
+pub mod prediction;
 pub mod samplers;
+pub mod sliding;
+pub mod streaming;
 pub mod summary;
+pub mod survey;
+#[cfg(feature = "tdigest")]
+pub mod tdigest;
+#[cfg(test)]
+mod test_util;
+pub mod two_sample;
+pub mod wild;
 
+#[cfg(feature = "derive")]
+pub use booted_derive::BootstrapStatistic;
 pub use bootstrap::{
-    Arithmetic, Bootstrap, BootstrapError, BootstrapResult, Estimator, EstimatorError,
-    EstimatorResult, Progress,
+    Arithmetic, Bootstrap, BootstrapResult, Elementwise, Estimator, EstimatorError,
+    EstimatorResult, Progress, ReplicaMoments, ReplicaMonitor, SameBits, SeEstimator, Warning,
+    n_boot_for_mc_se, recommended_n_boot,
 };
-pub use samplers::{Sampler, SamplerError, SamplingStrategy};
+pub use error::BootstrapError;
+pub use export::CsvRow;
+pub use samplers::{MSpec, Sampler, SamplerError, SamplingStrategy, WildDist};
+pub use streaming::{QuantileSketch, StreamingStatistic, StreamingSummary};
 pub use summary::{
-    BootstrapSummary, ConfidenceInterval, Statistics, Summarisable, SummaryStatistic,
+    BootstrapSummary, ConditionalSummary, ConfidenceInterval, MedianPolicy, Report, Statistics,
+    Summarisable, SummaryOptions, SummaryStatistic, SummaryStatsOnly, VectorStatistics,
 };
+
+// Used by code generated by `#[derive(BootstrapStatistic)]`.
+#[doc(hidden)]
+pub use serde as __serde;
@@ -0,0 +1,83 @@
+//! One-call visualisation of a scalar bootstrap distribution with
+//! `plotters`. Enable the `plotters` feature to use.
+//!
+//! The plot is a histogram of the replicas with vertical lines at the
+//! central estimate (black) and the 95% percentile interval (red). No text
+//! is drawn, so no font backend is needed.
+
+use crate::summary::BootstrapSummary;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+/// Draw `summary`'s replica histogram with `bins` bins, binned as
+/// [`BootstrapSummary::histogram`], onto `area`. A summary without finite
+/// replicas leaves the area blank.
+pub fn draw_summary<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    summary: &BootstrapSummary<f64>,
+    bins: usize,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    area.fill(&WHITE)?;
+    let histogram = summary.histogram(bins);
+    let (Some(&(first, _)), Some(&(last, _))) = (histogram.first(), histogram.last()) else {
+        return Ok(());
+    };
+    let width = match histogram.get(1) {
+        Some(&(second, _)) => second - first,
+        None => 1.0,
+    };
+    let top = histogram.iter().map(|&(_, c)| c).max().unwrap() as f64;
+
+    let mut chart = ChartBuilder::on(area)
+        .margin(10)
+        .build_cartesian_2d(first - width / 2.0..last + width / 2.0, 0.0..top * 1.05)?;
+    chart.draw_series(histogram.iter().map(|&(centre, c)| {
+        let x0 = centre - width / 2.0;
+        Rectangle::new([(x0, 0.0), (x0 + width, c as f64)], BLUE.mix(0.5).filled())
+    }))?;
+
+    let vline = |x: f64, colour: RGBColor| {
+        PathElement::new(vec![(x, 0.0), (x, top * 1.05)], colour.stroke_width(2))
+    };
+    if let Ok(central) = summary.central {
+        chart.draw_series(std::iter::once(vline(central, BLACK)))?;
+    }
+    if let Some(stats) = summary.statistics.as_ref() {
+        chart.draw_series([vline(stats.ci_95.low, RED), vline(stats.ci_95.high, RED)])?;
+    }
+    area.present()
+}
+
+/// Render [`draw_summary`] to an in-memory SVG document of `size` pixels.
+pub fn summary_svg(summary: &BootstrapSummary<f64>, size: (u32, u32), bins: usize) -> String {
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, size).into_drawing_area();
+        // The string backend cannot fail to write.
+        draw_summary(&root, summary, bins).expect("drawing to an SVG string");
+    }
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::Bootstrap;
+    use crate::summary::Summarisable;
+    use crate::test_util::mean_estimator;
+
+    #[test]
+    fn renders_non_empty_svg() {
+        let data: Vec<f64> = (0..50).map(|i| (i as f64).ln_1p()).collect();
+        let est = mean_estimator(&data);
+        let summary = Bootstrap::new(est)
+            .n_boot(300)
+            .seed(1)
+            .run()
+            .unwrap()
+            .summarise();
+        let svg = summary_svg(&summary, (400, 300), 20);
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("<rect"));
+    }
+}
@@ -0,0 +1,308 @@
+//! Prediction-error diagnostics for user-supplied predictors, evaluated on
+//! the out-of-bag (OOB) points of each bootstrap training set.
+
+use crate::bootstrap::{Bootstrap, EstimatorError, Progress};
+use crate::error::BootstrapError;
+use crate::samplers::{Sampler, SamplingStrategy};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Bias²/variance/noise decomposition of a predictor's squared error.
+///
+/// Each replica draws an iid training set from the observations, calls
+/// `fit_predict(train, oob)` to fit on `train` and predict the targets at
+/// the `oob` observations (those the draw left out, about 37% of them), and
+/// records those predictions. Every observation left out at least twice
+/// then contributes its mean squared error, the squared gap between its
+/// target and its mean prediction, and the spread of its predictions
+/// across training sets. Costs one fit per replica.
+pub struct BiasVariance<F> {
+    targets: Vec<f64>,
+    fit_predict: F,
+    noise_variance: f64,
+    bootstrap: Bootstrap<Vec<(usize, f64)>>,
+}
+
+impl<F> BiasVariance<F>
+where
+    F: Fn(&[usize], &[usize]) -> Vec<f64> + Sync,
+{
+    /// `fit_predict` must return one prediction per OOB index, in order;
+    /// replicas where it does not are dropped.
+    pub fn new(targets: Vec<f64>, fit_predict: F) -> Self {
+        Self {
+            targets,
+            fit_predict,
+            noise_variance: 0.0,
+            bootstrap: Bootstrap::for_runner().n_boot(200),
+        }
+    }
+
+    pub fn n_boot(mut self, n: usize) -> Self {
+        self.bootstrap = self.bootstrap.n_boot(n);
+        self
+    }
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.bootstrap = self.bootstrap.seed(seed);
+        self
+    }
+    /// Known variance of the noise in the targets (default 0). The data
+    /// alone cannot separate noise from bias, so without it the noise is
+    /// reported as zero and included in `bias_sq`.
+    pub fn noise_variance(mut self, variance: f64) -> Self {
+        self.noise_variance = variance;
+        self
+    }
+    /// As [`Bootstrap::threads`].
+    pub fn threads(mut self, n: usize) -> Self {
+        self.bootstrap = self.bootstrap.threads(n);
+        self
+    }
+    /// As [`Bootstrap::parallel`].
+    pub fn parallel(mut self, on: bool) -> Self {
+        self.bootstrap = self.bootstrap.parallel(on);
+        self
+    }
+    /// As [`Bootstrap::progress`].
+    pub fn progress(mut self, p: Arc<dyn Progress>) -> Self {
+        self.bootstrap = self.bootstrap.progress(p);
+        self
+    }
+    /// As [`Bootstrap::min_valid_fraction`]: with fewer usable replicas
+    /// than this fraction of `n_boot`, `run` fails with
+    /// [`BootstrapError::TooFewValidSamples`].
+    ///
+    /// # Panics
+    ///
+    /// If `fraction` is not in `[0, 1]`.
+    pub fn min_valid_fraction(mut self, fraction: f64) -> Self {
+        self.bootstrap = self.bootstrap.min_valid_fraction(fraction);
+        self
+    }
+
+    pub fn run(self) -> Result<Decomposition, BootstrapError> {
+        let BiasVariance {
+            targets,
+            fit_predict,
+            noise_variance,
+            bootstrap,
+        } = self;
+        let n = targets.len();
+        if n < 2 {
+            return Err(BootstrapError::InvalidInput(format!(
+                "bias-variance decomposition needs at least two targets, got {n}"
+            )));
+        }
+        let indices: Vec<usize> = (0..n).collect();
+
+        let result = bootstrap.run_with(Ok(Vec::new()), |_, rng| {
+            let train = SamplingStrategy::Iid
+                .sample(&indices, rng)
+                .map_err(|e| EstimatorError::new(e.to_string()))?;
+            let mut drawn = vec![false; n];
+            for &j in &train {
+                drawn[j] = true;
+            }
+            let oob: Vec<usize> = (0..n).filter(|&j| !drawn[j]).collect();
+            let predictions = fit_predict(&train, &oob);
+            if predictions.len() == oob.len() {
+                Ok(oob.into_iter().zip(predictions).collect())
+            } else {
+                Err(EstimatorError::new("one prediction per OOB index expected"))
+            }
+        });
+
+        let (n_boot, n_valid) = (result.n_boot, result.samples.len());
+        let mut sums = vec![(0usize, 0.0, 0.0); n];
+        for &(j, p) in result.samples.iter().flatten() {
+            let (count, sum, sum_sq) = &mut sums[j];
+            *count += 1;
+            *sum += p;
+            *sum_sq += p * p;
+        }
+        let (mut error, mut bias_sq, mut variance, mut n_points) = (0.0, 0.0, 0.0, 0);
+        for (&(count, sum, sum_sq), y) in sums.iter().zip(&targets) {
+            if count < 2 {
+                continue;
+            }
+            let c = count as f64;
+            let mean = sum / c;
+            let var = (sum_sq / c - mean * mean).max(0.0);
+            bias_sq += (y - mean).powi(2);
+            variance += var;
+            error += (y - mean).powi(2) + var;
+            n_points += 1;
+        }
+        if n_valid == 0 {
+            return Err(BootstrapError::NoValidSamples {
+                n_boot,
+                failed_samples: n_boot,
+            });
+        }
+        if result.too_few_valid() {
+            return Err(BootstrapError::TooFewValidSamples {
+                n_boot,
+                valid: n_valid,
+                min_valid_fraction: result.min_valid_fraction,
+            });
+        }
+        if n_points == 0 {
+            return Err(BootstrapError::InvalidInput(format!(
+                "no observation was out of bag in two or more of {n_valid} replicas; raise n_boot"
+            )));
+        }
+        let m = n_points as f64;
+        Ok(Decomposition {
+            n_boot,
+            failed: n_boot - n_valid,
+            n_points,
+            error: error / m,
+            bias_sq: bias_sq / m - noise_variance,
+            variance: variance / m,
+            noise: noise_variance,
+        })
+    }
+}
+
+/// Output of [`BiasVariance::run`], averaged over the `n_points`
+/// observations that were out of bag at least twice. The components add up:
+/// `error = bias_sq + variance + noise`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[non_exhaustive]
+pub struct Decomposition {
+    pub n_boot: usize,
+    /// Replicas dropped because `fit_predict` returned the wrong number of
+    /// predictions.
+    pub failed: usize,
+    pub n_points: usize,
+    /// Mean squared OOB prediction error.
+    pub error: f64,
+    /// Squared gap between the target and the mean prediction, less the
+    /// noise.
+    pub bias_sq: f64,
+    /// Spread of the predictions across training sets.
+    pub variance: f64,
+    /// The noise variance supplied to the builder.
+    pub noise: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> (Vec<f64>, Vec<f64>) {
+        let x: Vec<f64> = (0..80).map(|i| i as f64 / 80.0).collect();
+        let y = x
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (std::f64::consts::TAU * v).sin() + 0.5 * (i as f64 * 7.3).sin())
+            .collect();
+        (x, y)
+    }
+
+    #[test]
+    fn nearest_neighbour_has_more_variance_than_mean_predictor() {
+        let (x, y) = data();
+        let nearest = {
+            let (x, y) = (x.clone(), y.clone());
+            BiasVariance::new(y.clone(), move |train: &[usize], oob: &[usize]| {
+                oob.iter()
+                    .map(|&j| {
+                        let &k = train
+                            .iter()
+                            .min_by(|&&a, &&b| (x[a] - x[j]).abs().total_cmp(&(x[b] - x[j]).abs()))
+                            .unwrap();
+                        y[k]
+                    })
+                    .collect()
+            })
+            .n_boot(200)
+            .seed(1)
+            .run()
+            .unwrap()
+        };
+        let global_mean = BiasVariance::new(y.clone(), move |train: &[usize], oob: &[usize]| {
+            let m = train.iter().map(|&k| y[k]).sum::<f64>() / train.len() as f64;
+            vec![m; oob.len()]
+        })
+        .n_boot(200)
+        .seed(1)
+        .run()
+        .unwrap();
+        assert!(
+            nearest.variance > 5.0 * global_mean.variance,
+            "{nearest:?} {global_mean:?}"
+        );
+        assert!(global_mean.bias_sq > nearest.bias_sq);
+        for d in [nearest, global_mean] {
+            assert_eq!(d.failed, 0);
+            assert!((d.error - d.bias_sq - d.variance - d.noise).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn degenerate_runs_report_why() {
+        let mean = |_: &[usize], oob: &[usize]| vec![0.0; oob.len()];
+        assert!(matches!(
+            BiasVariance::new(vec![1.0], mean).run(),
+            Err(BootstrapError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            BiasVariance::new(vec![1.0, 2.0, 3.0], |_: &[usize], oob: &[usize]| {
+                vec![0.0; oob.len() + 1]
+            })
+            .n_boot(20)
+            .seed(1)
+            .run(),
+            Err(BootstrapError::NoValidSamples { n_boot: 20, .. })
+        ));
+        assert!(matches!(
+            BiasVariance::new((0..50).map(f64::from).collect(), mean)
+                .n_boot(1)
+                .seed(1)
+                .run(),
+            Err(BootstrapError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn runs_apply_the_shared_options() {
+        let (_, y) = data();
+        let targets = y.clone();
+        let mean = move |train: &[usize], oob: &[usize]| {
+            let m = train.iter().map(|&k| y[k]).sum::<f64>() / train.len() as f64;
+            vec![m; oob.len()]
+        };
+        let run = |parallel: bool| {
+            BiasVariance::new(targets.clone(), &mean)
+                .seed(6)
+                .parallel(parallel)
+                .run()
+                .unwrap()
+        };
+        let (par, seq) = (run(true), run(false));
+        assert_eq!((par.error, par.variance), (seq.error, seq.variance));
+
+        // Every other replica returns the wrong number of predictions.
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let flaky = |train: &[usize], oob: &[usize]| {
+            let n = calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let mut p = mean(train, oob);
+            if n % 2 == 1 {
+                p.push(0.0);
+            }
+            p
+        };
+        assert!(matches!(
+            BiasVariance::new(targets.clone(), flaky)
+                .parallel(false)
+                .min_valid_fraction(0.9)
+                .run(),
+            Err(BootstrapError::TooFewValidSamples {
+                n_boot: 200,
+                valid: 100,
+                ..
+            })
+        ));
+    }
+}
@@ -1,6 +1,8 @@
 use rand::Rng;
+use rand::distr::weighted::WeightedIndex;
 use rand::distr::{Distribution, Uniform};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
 /// Errors returned when a sampling strategy cannot draw a resample from the
@@ -22,6 +24,23 @@ pub enum SamplerError {
     BadThinning { factor: usize, n: usize },
     /// A `Subsample { m }` was requested with `m == 0`.
     ZeroSample,
+    /// A supplied block start leaves too few items for a full block.
+    BlockStartOutOfRange {
+        start: usize,
+        block_size: usize,
+        n: usize,
+    },
+    /// The strategy draws multipliers, not indices (`Wild`); see
+    /// [`Sampler::weights`].
+    MultipliersOnly,
+    /// `Stratified` was given an index with no entry in `strata`.
+    NoStratum { index: usize, n_strata: usize },
+    /// `Weighted` was drawn without the estimator's weights (on its own
+    /// rather than through a run), or the weights are all zero.
+    BadWeights,
+    /// A `SubsampleRule` exponent or fraction is non-finite or outside
+    /// `(0, 1]`.
+    BadSubsampleRule,
 }
 
 impl fmt::Display for SamplerError {
@@ -45,6 +64,30 @@ impl fmt::Display for SamplerError {
                 "thinning factor {factor} is invalid for population size {n}"
             ),
             SamplerError::ZeroSample => write!(f, "requested sample size 0"),
+            SamplerError::BlockStartOutOfRange {
+                start,
+                block_size,
+                n,
+            } => write!(
+                f,
+                "block of size {block_size} starting at {start} overruns population size {n}"
+            ),
+            SamplerError::MultipliersOnly => write!(
+                f,
+                "wild bootstrap draws multipliers rather than indices; run it with WildBootstrap"
+            ),
+            SamplerError::NoStratum { index, n_strata } => write!(
+                f,
+                "index {index} has no stratum label ({n_strata} labels given)"
+            ),
+            SamplerError::BadWeights => write!(
+                f,
+                "sampling weights missing, negative, non-finite or all zero"
+            ),
+            SamplerError::BadSubsampleRule => write!(
+                f,
+                "subsample exponent or fraction must be finite and in (0, 1]"
+            ),
         }
     }
 }
@@ -53,10 +96,16 @@ impl std::error::Error for SamplerError {}
 
 /// Ways to draw a resample from a population of configuration indices.
 ///
-/// The variants split cleanly into *iid* schemes (`Iid`, `Subsample`,
-/// `Thinning`) and *block* schemes (`Block`, `MovingBlock`). Block schemes
-/// preserve local autocorrelation; iid schemes do not.
-#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+/// *Iid* schemes (`Iid`, `Subsample`, `SubsampleRule`, `Thinning`) draw
+/// indices uniformly with replacement. *Block* schemes (`Block`,
+/// `MovingBlock`, `BlockWithStarts`) draw runs of consecutive indices and
+/// so preserve local autocorrelation, which iid schemes do not. `Poisson`
+/// and `Wild` reweight every index rather than resampling: the first by
+/// Poisson(1) counts, the second by multipliers on regression residuals.
+/// `Stratified` resamples within groups, and `Balanced` and `Weighted`
+/// coordinate or weight the draws across a whole
+/// [`Bootstrap::run`](crate::Bootstrap::run).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[non_exhaustive]
 pub enum SamplingStrategy {
     /// Ordinary bootstrap: draw `n` items with replacement from a population
@@ -64,6 +113,10 @@ pub enum SamplingStrategy {
     Iid,
     /// m-out-of-n subsampling: draw `m` items with replacement.
     Subsample { m: usize },
+    /// m-out-of-n subsampling with `m` given as a rule in the population
+    /// size, e.g. `MSpec::Power(0.7)` for `m = ⌈n^0.7⌉`, resolved at draw
+    /// time.
+    SubsampleRule { m: MSpec },
     /// Thinning: keep an iid subsample of size `n / factor`. Equivalent to
     /// `Subsample { m: n / factor }` but resolved at draw time (does not need
     /// to know `n` at construction time).
@@ -73,6 +126,120 @@ pub enum SamplingStrategy {
     Block { block_size: usize },
     /// Moving (overlapping) block bootstrap of Künsch (1989).
     MovingBlock { block_size: usize },
+    /// Moving-block resample with the block starts given explicitly instead
+    /// of drawn: the resample is the blocks `indices[s..s + block_size]` for
+    /// each `s` in `starts`, in order. Deterministic, for comparing against
+    /// reference implementations; the RNG is not consumed.
+    BlockWithStarts {
+        block_size: usize,
+        starts: Vec<usize>,
+    },
+    /// Poisson bootstrap: every index appears an independent Poisson(1)
+    /// number of times, so the resample size varies around `n`. Each
+    /// index's count is drawn on its own, which is what makes it suit data
+    /// processed in one streaming pass or in parallel shards. Pair with
+    /// [`Estimator::from_weights`](crate::Estimator::from_weights) to work
+    /// with the counts directly.
+    Poisson,
+    /// Wild bootstrap for regression residuals: every observation is kept
+    /// and its residual multiplied by an iid draw from `distribution`
+    /// (mean 0, variance 1), which preserves heteroskedasticity that
+    /// resampling residuals would scramble. Produces multipliers through
+    /// [`Sampler::weights`] rather than indices; run it with
+    /// [`WildBootstrap`](crate::wild::WildBootstrap).
+    Wild { distribution: WildDist },
+    /// Stratified bootstrap: `strata[i]` is the group label of index `i`,
+    /// and each position of the resample is redrawn with replacement from
+    /// the indices sharing its label, so every stratum keeps its size and
+    /// no replica loses a group entirely.
+    Stratified { strata: Vec<usize> },
+    /// Balanced bootstrap: across a run's `n_boot` replicas every index
+    /// appears exactly `n_boot` times, which removes the first-order
+    /// simulation error in the replica mean. Needs the whole run at once,
+    /// so [`Bootstrap::run`](crate::Bootstrap::run) shuffles `n_boot`
+    /// copies of the indices up front, before the parallel loop, and hands
+    /// each replica its own slice; that costs `n·n_boot` indices of memory
+    /// and balance only holds if no replica fails. Drawn on its own, for
+    /// example as a bias-correction inner sampler, it is an iid resample.
+    Balanced,
+    /// Weighted bootstrap: draw `n` items with replacement, each index with
+    /// probability proportional to its weight from
+    /// [`Estimator::weights`](crate::Estimator::weights) (e.g.
+    /// inverse-probability survey weights) rather than uniformly. Like
+    /// `Balanced` it needs the run: [`Bootstrap::run`](crate::Bootstrap::run)
+    /// builds the weighted distribution once and every replica draws from
+    /// it. Drawn on its own there are no weights, and it fails with
+    /// [`SamplerError::BadWeights`].
+    Weighted,
+}
+
+/// Distribution of the wild-bootstrap multipliers. Both have mean 0 and
+/// variance 1.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum WildDist {
+    /// ±1 with equal probability.
+    #[default]
+    Rademacher,
+    /// Mammen's two-point distribution: `−(√5 − 1)/2` with probability
+    /// `(√5 + 1)/(2√5)`, otherwise `(√5 + 1)/2`. Third moment 1, so it
+    /// also matches the residuals' skewness.
+    Mammen,
+}
+
+impl WildDist {
+    fn draw<R: Rng + ?Sized>(self, rng: &mut R) -> f64 {
+        match self {
+            WildDist::Rademacher => {
+                if rng.random::<bool>() {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            WildDist::Mammen => {
+                let sqrt5 = 5f64.sqrt();
+                if rng.random::<f64>() < (sqrt5 + 1.0) / (2.0 * sqrt5) {
+                    -(sqrt5 - 1.0) / 2.0
+                } else {
+                    (sqrt5 + 1.0) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Resample size for m-out-of-n subsampling as a function of the
+/// population size `n`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum MSpec {
+    /// `m` itself, regardless of `n`.
+    Fixed(usize),
+    /// `m = ⌈n^exponent⌉`, with `exponent` in `(0, 1]`.
+    Power(f64),
+    /// `m = ⌈fraction·n⌉`, with `fraction` in `(0, 1]`.
+    Fraction(f64),
+}
+
+impl MSpec {
+    /// Resample size for a population of `n`, or
+    /// [`SamplerError::BadSubsampleRule`] if the exponent or fraction is
+    /// non-finite or outside `(0, 1]`, so a rule never asks for more than
+    /// `n` items.
+    pub fn resolve(&self, n: usize) -> Result<usize, SamplerError> {
+        let in_range = |x: f64| x > 0.0 && x <= 1.0;
+        match *self {
+            MSpec::Fixed(m) => Ok(m),
+            MSpec::Power(exponent) if in_range(exponent) => {
+                Ok((n as f64).powf(exponent).ceil() as usize)
+            }
+            MSpec::Fraction(fraction) if in_range(fraction) => {
+                Ok((fraction * n as f64).ceil() as usize)
+            }
+            MSpec::Power(_) | MSpec::Fraction(_) => Err(SamplerError::BadSubsampleRule),
+        }
+    }
 }
 
 pub trait Sampler {
@@ -84,6 +251,14 @@ pub trait Sampler {
         rng: &mut R,
     ) -> Result<(), SamplerError>;
 
+    /// Per-observation multipliers for `n` observations, for strategies
+    /// that reweight rather than resample (`Wild`). `None` for the others,
+    /// which is the default.
+    fn weights<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Option<Vec<f64>> {
+        let _ = (n, rng);
+        None
+    }
+
     /// Convenience wrapper allocating a fresh `Vec<usize>`.
     fn sample<R: Rng + ?Sized>(
         &self,
@@ -105,6 +280,77 @@ fn iid_draw<R: Rng + ?Sized>(indices: &[usize], m: usize, buffer: &mut Vec<usize
     buffer.extend(dist.sample_iter(rng).take(m).map(|i| indices[i]));
 }
 
+/// Append each index a Poisson(1) number of times (Knuth's method, cheap
+/// for a mean of one).
+fn poisson_draw<R: Rng + ?Sized>(indices: &[usize], buffer: &mut Vec<usize>, rng: &mut R) {
+    let limit = (-1.0f64).exp();
+    buffer.reserve(indices.len());
+    for &i in indices {
+        let mut product: f64 = rng.random();
+        while product > limit {
+            buffer.push(i);
+            product *= rng.random::<f64>();
+        }
+    }
+}
+
+/// `indices.len()` draws of `indices[k]`, with `k` from `positions`, the
+/// weighted distribution over positions in `indices` that a run builds
+/// once for [`SamplingStrategy::Weighted`].
+pub(crate) fn weighted_draw<R: Rng + ?Sized>(
+    indices: &[usize],
+    positions: &WeightedIndex<f64>,
+    buffer: &mut Vec<usize>,
+    rng: &mut R,
+) {
+    buffer.clear();
+    buffer.extend(
+        positions
+            .sample_iter(rng)
+            .take(indices.len())
+            .map(|k| indices[k]),
+    );
+}
+
+/// The strata of an index set for [`SamplingStrategy::Stratified`]: the
+/// members of each stratum in order of appearance, and the stratum of each
+/// position. A run builds it once and shares it across replicas.
+pub(crate) struct Strata {
+    members: Vec<Vec<usize>>,
+    group: Vec<usize>,
+}
+
+impl Strata {
+    pub(crate) fn new(indices: &[usize], strata: &[usize]) -> Result<Self, SamplerError> {
+        let mut ids: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        let mut group = Vec::with_capacity(indices.len());
+        for &i in indices {
+            let label = strata.get(i).copied().ok_or(SamplerError::NoStratum {
+                index: i,
+                n_strata: strata.len(),
+            })?;
+            let id = *ids.entry(label).or_insert_with(|| {
+                members.push(Vec::new());
+                members.len() - 1
+            });
+            members[id].push(i);
+            group.push(id);
+        }
+        Ok(Self { members, group })
+    }
+
+    /// One draw per position, uniform within the position's stratum.
+    pub(crate) fn draw<R: Rng + ?Sized>(&self, buffer: &mut Vec<usize>, rng: &mut R) {
+        buffer.clear();
+        buffer.reserve(self.group.len());
+        for &id in &self.group {
+            let members = &self.members[id];
+            buffer.push(members[rng.random_range(0..members.len())]);
+        }
+    }
+}
+
 fn block_draw<R: Rng + ?Sized>(
     indices: &[usize],
     block_size: usize,
@@ -160,7 +406,7 @@ impl Sampler for SamplingStrategy {
             return Err(SamplerError::Empty);
         }
         match self {
-            SamplingStrategy::Iid => {
+            SamplingStrategy::Iid | SamplingStrategy::Balanced => {
                 iid_draw(indices, indices.len(), buffer, rng);
                 Ok(())
             }
@@ -171,6 +417,14 @@ impl Sampler for SamplingStrategy {
                 iid_draw(indices, *m, buffer, rng);
                 Ok(())
             }
+            SamplingStrategy::SubsampleRule { m } => {
+                let m = m.resolve(indices.len())?;
+                if m == 0 {
+                    return Err(SamplerError::ZeroSample);
+                }
+                iid_draw(indices, m, buffer, rng);
+                Ok(())
+            }
             SamplingStrategy::Thinning { factor } => {
                 if *factor == 0 {
                     return Err(SamplerError::BadThinning {
@@ -200,6 +454,46 @@ impl Sampler for SamplingStrategy {
                 }
                 moving_block_draw(indices, *block_size, buffer, rng)
             }
+            SamplingStrategy::Poisson => {
+                poisson_draw(indices, buffer, rng);
+                if buffer.is_empty() {
+                    return Err(SamplerError::ZeroSample);
+                }
+                Ok(())
+            }
+            SamplingStrategy::BlockWithStarts { block_size, starts } => {
+                if *block_size == 0 {
+                    return Err(SamplerError::ZeroSample);
+                }
+                let n = indices.len();
+                buffer.reserve(starts.len() * block_size);
+                for &start in starts {
+                    let Some(end) = start.checked_add(*block_size).filter(|&e| e <= n) else {
+                        return Err(SamplerError::BlockStartOutOfRange {
+                            start,
+                            block_size: *block_size,
+                            n,
+                        });
+                    };
+                    buffer.extend_from_slice(&indices[start..end]);
+                }
+                Ok(())
+            }
+            SamplingStrategy::Wild { .. } => Err(SamplerError::MultipliersOnly),
+            SamplingStrategy::Stratified { strata } => {
+                Strata::new(indices, strata)?.draw(buffer, rng);
+                Ok(())
+            }
+            SamplingStrategy::Weighted => Err(SamplerError::BadWeights),
+        }
+    }
+
+    fn weights<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Option<Vec<f64>> {
+        match self {
+            SamplingStrategy::Wild { distribution } => {
+                Some((0..n).map(|_| distribution.draw(rng)).collect())
+            }
+            _ => None,
         }
     }
 }
@@ -242,6 +536,22 @@ pub fn generate_block_jackknife_indices(blocksize: usize, data_length: usize) ->
         .collect()
 }
 
+/// Deterministic leave-one-cluster-out folds: fold `k` keeps, in order,
+/// every index `i` whose `cluster_of[i]` is not the `k`-th smallest cluster
+/// label. One fold per distinct label, for grouped cross-validation or a
+/// cluster jackknife.
+pub fn generate_leave_one_cluster_out(cluster_of: &[usize]) -> Vec<Vec<usize>> {
+    let clusters: BTreeSet<usize> = cluster_of.iter().copied().collect();
+    clusters
+        .into_iter()
+        .map(|c| {
+            (0..cluster_of.len())
+                .filter(|&i| cluster_of[i] != c)
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -252,6 +562,67 @@ mod tests {
         SmallRng::seed_from_u64(42)
     }
 
+    #[test]
+    fn wild_multipliers_have_unit_variance() {
+        let mut r = rng();
+        for (distribution, third) in [(WildDist::Rademacher, 0.0), (WildDist::Mammen, 1.0)] {
+            let wild = SamplingStrategy::Wild { distribution };
+            assert_eq!(
+                wild.sample(&[0, 1], &mut r),
+                Err(SamplerError::MultipliersOnly)
+            );
+            let v = wild.weights(200_000, &mut r).unwrap();
+            let moment = |k: i32| v.iter().map(|x| x.powi(k)).sum::<f64>() / v.len() as f64;
+            assert!(moment(1).abs() < 0.01);
+            assert!((moment(2) - 1.0).abs() < 0.01);
+            assert!((moment(3) - third).abs() < 0.03);
+        }
+        assert!(SamplingStrategy::Iid.weights(5, &mut r).is_none());
+    }
+
+    #[test]
+    fn weighted_draws_follow_weights() {
+        let mut weights = vec![1.0; 10];
+        weights[7] = 90.0;
+        let positions = WeightedIndex::new(&weights).unwrap();
+        let ind: Vec<usize> = (10..20).collect();
+        let mut r = rng();
+        let (mut s, mut sevens) = (Vec::new(), 0);
+        for _ in 0..100 {
+            weighted_draw(&ind, &positions, &mut s, &mut r);
+            assert_eq!(s.len(), 10);
+            sevens += s.iter().filter(|&&i| i == 17).count();
+        }
+        assert!((850..950).contains(&sevens), "{sevens}");
+        assert_eq!(
+            SamplingStrategy::Weighted.sample(&ind, &mut r),
+            Err(SamplerError::BadWeights)
+        );
+    }
+
+    #[test]
+    fn stratified_preserves_stratum_counts() {
+        let strata: Vec<usize> = (0..20).map(|i| usize::from(i >= 4)).collect();
+        let sampler = SamplingStrategy::Stratified {
+            strata: strata.clone(),
+        };
+        let ind: Vec<usize> = (0..20).collect();
+        let mut r = rng();
+        for _ in 0..200 {
+            let s = sampler.sample(&ind, &mut r).unwrap();
+            assert_eq!(s.len(), 20);
+            assert_eq!(s.iter().filter(|&&i| strata[i] == 0).count(), 4);
+            assert_eq!(s.iter().filter(|&&i| strata[i] == 1).count(), 16);
+        }
+        assert_eq!(
+            sampler.sample(&[3, 25], &mut r),
+            Err(SamplerError::NoStratum {
+                index: 25,
+                n_strata: 20
+            })
+        );
+    }
+
     #[test]
     fn iid_full_returns_n() {
         let ind: Vec<usize> = (0..10).collect();
@@ -266,6 +637,37 @@ mod tests {
         assert_eq!(s.len(), 3);
     }
 
+    #[test]
+    fn subsample_rule_resolves_against_n() {
+        let ind: Vec<usize> = (0..100).collect();
+        let s = SamplingStrategy::SubsampleRule {
+            m: MSpec::Power(0.7),
+        }
+        .sample(&ind, &mut rng())
+        .unwrap();
+        assert_eq!(s.len(), (100f64).powf(0.7).ceil() as usize);
+        assert_eq!(s.len(), 26);
+        assert_eq!(MSpec::Fraction(0.25).resolve(10), Ok(3));
+    }
+
+    #[test]
+    fn subsample_rule_rejects_out_of_range() {
+        let ind: Vec<usize> = (0..10).collect();
+        for m in [
+            MSpec::Fraction(f64::INFINITY),
+            MSpec::Fraction(0.0),
+            MSpec::Power(f64::NAN),
+            MSpec::Power(1.5),
+        ] {
+            assert_eq!(
+                SamplingStrategy::SubsampleRule { m }
+                    .sample(&ind, &mut rng())
+                    .unwrap_err(),
+                SamplerError::BadSubsampleRule
+            );
+        }
+    }
+
     #[test]
     fn thinning() {
         let ind: Vec<usize> = (0..10).collect();
@@ -317,6 +719,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn block_with_starts_is_exact() {
+        let ind: Vec<usize> = (10..20).collect();
+        let sampler = SamplingStrategy::BlockWithStarts {
+            block_size: 3,
+            starts: vec![7, 0, 4],
+        };
+        let s = sampler.sample(&ind, &mut rng()).unwrap();
+        assert_eq!(s, vec![17, 18, 19, 10, 11, 12, 14, 15, 16]);
+
+        let overrun = SamplingStrategy::BlockWithStarts {
+            block_size: 3,
+            starts: vec![8],
+        };
+        assert!(matches!(
+            overrun.sample(&ind, &mut rng()).unwrap_err(),
+            SamplerError::BlockStartOutOfRange { start: 8, .. }
+        ));
+
+        let wrapping = SamplingStrategy::BlockWithStarts {
+            block_size: 3,
+            starts: vec![usize::MAX],
+        };
+        assert!(matches!(
+            wrapping.sample(&ind, &mut rng()).unwrap_err(),
+            SamplerError::BlockStartOutOfRange { .. }
+        ));
+    }
+
     #[test]
     fn empty_input_is_error() {
         let ind: Vec<usize> = vec![];
@@ -345,6 +776,16 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn poisson_counts_average_one() {
+        let ind: Vec<usize> = (0..2000).collect();
+        let s = SamplingStrategy::Poisson.sample(&ind, &mut rng()).unwrap();
+        assert!((s.len() as f64 - 2000.0).abs() < 150.0);
+        let zeros = ind.iter().filter(|i| !s.contains(i)).count();
+        // P(count = 0) = e⁻¹.
+        assert!((zeros as f64 / 2000.0 - (-1.0f64).exp()).abs() < 0.04);
+    }
+
     #[test]
     fn block_jackknife_shape() {
         let sets = generate_block_jackknife_indices(4, 10);
@@ -354,4 +795,20 @@ mod tests {
             assert_eq!(s.len(), 4);
         }
     }
+
+    #[test]
+    fn leave_one_cluster_out_folds() {
+        let cluster_of = [2, 0, 1, 2, 0, 1, 1];
+        let folds = generate_leave_one_cluster_out(&cluster_of);
+        assert_eq!(folds.len(), 3);
+        for (c, fold) in folds.iter().enumerate() {
+            let missing: Vec<usize> = (0..cluster_of.len())
+                .filter(|i| !fold.contains(i))
+                .collect();
+            let expected: Vec<usize> = (0..cluster_of.len())
+                .filter(|&i| cluster_of[i] == c)
+                .collect();
+            assert_eq!(missing, expected);
+        }
+    }
 }
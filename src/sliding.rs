@@ -0,0 +1,107 @@
+//! Bootstraps repeated over overlapping windows of a series.
+
+use crate::bootstrap::{Bootstrap, Estimator, EstimatorResult};
+use crate::summary::{BootstrapSummary, Summarisable};
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Bootstrap `estimator_fn` separately within each window of `window`
+/// consecutive values, starting every `step` values, and summarise each:
+/// one entry per window, in order, so entry `w` covers the values from
+/// `w·step`. Summaries are labelled with their range. Windows that would
+/// run past the end are not included; a window whose central value fails
+/// to compute is `None`. Windows run in parallel.
+///
+/// Each window is resampled iid, so choose windows short enough that the
+/// series is roughly stationary within them; for autocorrelated series,
+/// bootstrap the windows yourself with a block sampler. With a `seed`,
+/// window `w` is seeded with `seed + w`.
+///
+/// # Panics
+///
+/// If `window` or `step` is zero.
+pub fn sliding_bootstrap<F>(
+    data: &[f64],
+    window: usize,
+    step: usize,
+    estimator_fn: F,
+    n_boot: usize,
+    seed: Option<u64>,
+) -> Vec<Option<BootstrapSummary<f64>>>
+where
+    F: Fn(&[f64]) -> EstimatorResult<f64> + Send + Sync + 'static,
+{
+    assert!(window > 0 && step > 0, "window and step must be positive");
+    let estimator_fn = Arc::new(estimator_fn);
+    let starts: Vec<usize> = (0..data.len().saturating_sub(window - 1))
+        .step_by(step)
+        .collect();
+    starts
+        .into_par_iter()
+        .enumerate()
+        .map(|(w, start)| {
+            let values = data[start..start + window].to_vec();
+            let f = Arc::clone(&estimator_fn);
+            let est = Estimator::new((0..window).collect(), move |ind| {
+                let resample: Vec<f64> = ind.iter().map(|&i| values[i]).collect();
+                f(&resample)
+            });
+            let mut boot = Bootstrap::new(est)
+                .n_boot(n_boot)
+                .label(format!("{start}..{}", start + window));
+            if let Some(s) = seed {
+                boot = boot.seed(s.wrapping_add(w as u64));
+            }
+            let result = boot.run().ok()?;
+            result.central.is_ok().then(|| result.summarise())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_intervals_separate_across_mean_shift() {
+        let data: Vec<f64> = (0..200)
+            .map(|i| if i < 100 { 0.0 } else { 3.0 } + (i as f64 * 2.1).sin())
+            .collect();
+        let mean = |x: &[f64]| Ok(x.iter().sum::<f64>() / x.len() as f64);
+        let summaries: Vec<_> = sliding_bootstrap(&data, 40, 20, mean, 500, Some(1))
+            .into_iter()
+            .map(Option::unwrap)
+            .collect();
+        assert_eq!(summaries.len(), 9);
+        assert_eq!(summaries[0].label.as_deref(), Some("0..40"));
+        let first = summaries[0].statistics.as_ref().unwrap().ci_95;
+        let last = summaries[8].statistics.as_ref().unwrap().ci_95;
+        assert!(first.high < last.low, "{first:?} {last:?}");
+        // The window straddling the shift sits in between.
+        let middle = *summaries[4].central.as_ref().unwrap();
+        assert!(first.high < middle && middle < last.low);
+    }
+
+    #[test]
+    fn failed_window_keeps_its_slot() {
+        let data: Vec<f64> = (0..60)
+            .map(|i| if i == 25 { f64::NAN } else { 1.0 })
+            .collect();
+        let mean = |x: &[f64]| {
+            let m = x.iter().sum::<f64>() / x.len() as f64;
+            if m.is_nan() {
+                Err(crate::EstimatorError::new("NaN in window"))
+            } else {
+                Ok(m)
+            }
+        };
+        let summaries = sliding_bootstrap(&data, 20, 20, mean, 50, Some(1));
+        assert_eq!(summaries.len(), 3);
+        assert!(summaries[0].is_some() && summaries[2].is_some());
+        assert!(summaries[1].is_none());
+        assert_eq!(
+            summaries[2].as_ref().unwrap().label.as_deref(),
+            Some("40..60")
+        );
+    }
+}
@@ -0,0 +1,471 @@
+//! Summaries of replicas that are folded in as they are produced rather
+//! than stored. See [`Bootstrap::run_streaming`](crate::Bootstrap::run_streaming).
+
+use crate::bootstrap::{EstimatorResult, Warning};
+use crate::summary::{Statistics, SummaryOptions, VectorStatistics, stats_of_sorted, with_basic};
+#[cfg(feature = "tdigest")]
+use crate::tdigest::TDigest;
+use serde::Serialize;
+use std::fmt::Debug;
+
+/// How the quantile-based fields of a streamed [`Statistics`] (median,
+/// IQR, MAD and the intervals) are approximated. Moments, `min` and `max`
+/// are always exact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum QuantileSketch {
+    /// Keep a uniform random sample of at most this many replicas and read
+    /// quantiles off it exactly. Replica `i` is kept when its key, a hash
+    /// of the seed and `i`, is among the smallest, so under a seed the
+    /// reservoir does not depend on thread scheduling. Quantiles are exact
+    /// when every successful replica fits; otherwise a tail quantile `q`
+    /// carries a sampling error of about `√(q(1−q)/k)` in probability for
+    /// a reservoir of `k`.
+    Reservoir(usize),
+    /// Feed every replica into a [`TDigest`] with this compression `δ`
+    /// (100–500 is typical). Memory is about `δ` centroids per component
+    /// and worker however many replicas there are, against `k` values for
+    /// a reservoir, and tail quantiles are resolved more finely than the
+    /// centre: the error in probability shrinks like `q(1 − q)/δ`, so the
+    /// 99% interval endpoints are typically good to a few parts in ten
+    /// thousand of probability while a reservoir of comparable memory has
+    /// only a handful of replicas beyond them. Unlike a reservoir the
+    /// result is approximate at any `n_boot` and, since digests merge in
+    /// scheduling order, varies slightly with the thread count. The
+    /// statistics read the digest through a fine grid of its quantiles.
+    #[cfg(feature = "tdigest")]
+    TDigest { compression: f64 },
+}
+
+/// Points of the quantile grid a digest is read through.
+#[cfg(feature = "tdigest")]
+const DIGEST_GRID: usize = 10_001;
+
+/// Replica types [`Bootstrap::run_streaming`](crate::Bootstrap::run_streaming)
+/// can summarise: a fixed number of `f64` components, each accumulated on
+/// its own.
+pub trait StreamingStatistic: Clone + Send + Sync + Serialize + Debug + 'static {
+    type Stats: Clone + Send + Debug + Serialize;
+
+    /// The replica's components, in a fixed order.
+    fn components(&self) -> &[f64];
+
+    /// Statistics of the whole replica from those of its components and
+    /// their sample covariance matrix.
+    fn assemble(components: Vec<Statistics>, covariance: Vec<Vec<f64>>) -> Self::Stats;
+}
+
+impl StreamingStatistic for f64 {
+    type Stats = Statistics;
+
+    fn components(&self) -> &[f64] {
+        std::slice::from_ref(self)
+    }
+
+    fn assemble(components: Vec<Statistics>, _covariance: Vec<Vec<f64>>) -> Statistics {
+        components.into_iter().next().unwrap()
+    }
+}
+
+/// The same [`VectorStatistics`] as a stored run. The covariance matrix is
+/// accumulated exactly, at a cost of `d²` per replica for `d` components.
+impl StreamingStatistic for Vec<f64> {
+    type Stats = VectorStatistics;
+
+    fn components(&self) -> &[f64] {
+        self
+    }
+
+    fn assemble(components: Vec<Statistics>, covariance: Vec<Vec<f64>>) -> VectorStatistics {
+        VectorStatistics::new(components, covariance)
+    }
+}
+
+/// Outcome of [`Bootstrap::run_streaming`](crate::Bootstrap::run_streaming).
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct StreamingSummary<T: StreamingStatistic> {
+    pub label: Option<String>,
+    pub n_boot: usize,
+    pub seed: Option<u64>,
+    pub central: EstimatorResult<T>,
+    /// Replicas whose estimator failed, or whose length differed from the
+    /// first successful replica.
+    pub failed: usize,
+    /// `None` when no replica succeeded. BCa intervals are never present.
+    pub statistics: Option<T::Stats>,
+    pub warnings: Vec<Warning>,
+}
+
+/// Running central moments up to the fourth (Pébay's one-pass and
+/// pairwise-merge updates), with the extremes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Moments {
+    pub(crate) n: usize,
+    pub(crate) mean: f64,
+    pub(crate) m2: f64,
+    m3: f64,
+    m4: f64,
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+}
+
+impl Moments {
+    pub(crate) const EMPTY: Moments = Moments {
+        n: 0,
+        mean: 0.0,
+        m2: 0.0,
+        m3: 0.0,
+        m4: 0.0,
+        min: f64::INFINITY,
+        max: f64::NEG_INFINITY,
+    };
+
+    fn push(&mut self, x: f64) {
+        let n1 = self.n as f64;
+        self.n += 1;
+        let n = self.n as f64;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n1;
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    fn merge(self, other: Moments) -> Moments {
+        if self.n == 0 {
+            return other;
+        }
+        if other.n == 0 {
+            return self;
+        }
+        let (na, nb) = (self.n as f64, other.n as f64);
+        let n = na + nb;
+        let delta = other.mean - self.mean;
+        let (d2, d3) = (delta * delta, delta * delta * delta);
+        Moments {
+            n: self.n + other.n,
+            mean: self.mean + delta * nb / n,
+            m2: self.m2 + other.m2 + d2 * na * nb / n,
+            m3: self.m3
+                + other.m3
+                + d3 * na * nb * (na - nb) / (n * n)
+                + 3.0 * delta * (na * other.m2 - nb * self.m2) / n,
+            m4: self.m4
+                + other.m4
+                + d2 * d2 * na * nb * (na * na - na * nb + nb * nb) / (n * n * n)
+                + 6.0 * d2 * (na * na * other.m2 + nb * nb * self.m2) / (n * n)
+                + 4.0 * delta * (na * other.m3 - nb * self.m3) / n,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+/// Bottom-`k` sample: the values whose keys are the `k` smallest seen.
+/// Trimmed back to `k` whenever it doubles, so pushes are amortised O(1).
+#[derive(Debug, Clone)]
+struct Reservoir {
+    capacity: usize,
+    items: Vec<(u64, f64)>,
+}
+
+impl Reservoir {
+    fn push(&mut self, key: u64, x: f64) {
+        self.items.push((key, x));
+        if self.items.len() >= 2 * self.capacity {
+            self.trim();
+        }
+    }
+
+    fn trim(&mut self) {
+        if self.items.len() > self.capacity {
+            self.items
+                .select_nth_unstable_by_key(self.capacity, |&(key, _)| key);
+            self.items.truncate(self.capacity);
+        }
+    }
+
+    fn merge(mut self, other: Reservoir) -> Reservoir {
+        self.items.extend(other.items);
+        self.trim();
+        self
+    }
+
+    fn sorted_values(mut self) -> Vec<f64> {
+        self.trim();
+        let mut values: Vec<f64> = self.items.into_iter().map(|(_, x)| x).collect();
+        values.sort_unstable_by(f64::total_cmp);
+        values
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Sketch {
+    Reservoir(Reservoir),
+    #[cfg(feature = "tdigest")]
+    TDigest(TDigest),
+}
+
+impl Sketch {
+    fn new(sketch: QuantileSketch) -> Self {
+        match sketch {
+            QuantileSketch::Reservoir(capacity) => Sketch::Reservoir(Reservoir {
+                capacity,
+                items: Vec::new(),
+            }),
+            #[cfg(feature = "tdigest")]
+            QuantileSketch::TDigest { compression } => Sketch::TDigest(TDigest::new(compression)),
+        }
+    }
+
+    fn push(&mut self, key: u64, x: f64) {
+        match self {
+            Sketch::Reservoir(r) => r.push(key, x),
+            #[cfg(feature = "tdigest")]
+            Sketch::TDigest(d) => d.insert(x),
+        }
+    }
+
+    fn merge(self, other: Sketch) -> Sketch {
+        match (self, other) {
+            (Sketch::Reservoir(a), Sketch::Reservoir(b)) => Sketch::Reservoir(a.merge(b)),
+            #[cfg(feature = "tdigest")]
+            (Sketch::TDigest(mut a), Sketch::TDigest(b)) => {
+                a.merge(b);
+                Sketch::TDigest(a)
+            }
+            #[cfg(feature = "tdigest")]
+            _ => unreachable!("accumulators of one run share a sketch"),
+        }
+    }
+
+    /// Ascending values whose quantiles stand in for the replicas'.
+    fn sorted_values(self) -> Vec<f64> {
+        match self {
+            Sketch::Reservoir(r) => r.sorted_values(),
+            #[cfg(feature = "tdigest")]
+            Sketch::TDigest(mut d) => d.quantile_grid(DIGEST_GRID),
+        }
+    }
+}
+
+/// One component's moments and quantile sketch.
+#[derive(Debug, Clone)]
+struct Component {
+    moments: Moments,
+    sketch: Sketch,
+}
+
+impl Component {
+    fn merge(self, other: Component) -> Component {
+        Component {
+            moments: self.moments.merge(other.moments),
+            sketch: self.sketch.merge(other.sketch),
+        }
+    }
+
+    fn statistics(self) -> Statistics {
+        let Component { moments, sketch } = self;
+        let sorted = sketch.sorted_values();
+        let mut stats = stats_of_sorted(&sorted, &SummaryOptions::default());
+        let n = moments.n as f64;
+        let (m2, m3, m4) = (moments.m2 / n, moments.m3 / n, moments.m4 / n);
+        stats.n = moments.n;
+        stats.mean = moments.mean;
+        stats.stddev = (moments.m2 / (n - 1.0).max(1.0)).sqrt();
+        stats.mc_std_error = stats.stddev / n.sqrt();
+        (stats.skewness, stats.excess_kurtosis) = if m2 > 0.0 {
+            (m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+        stats.min = moments.min;
+        stats.max = moments.max;
+        stats
+    }
+}
+
+/// Per-worker accumulator for a streaming run. Components are sized by the
+/// first replica pushed; later replicas of another length are counted as
+/// failures.
+#[derive(Debug, Clone)]
+pub(crate) struct Accumulator {
+    sketch: QuantileSketch,
+    components: Vec<Component>,
+    /// Sums of cross products of deviations from the running means, row
+    /// major over the components.
+    comoments: Vec<f64>,
+    pub(crate) failed: usize,
+}
+
+impl Accumulator {
+    pub(crate) fn new(sketch: QuantileSketch) -> Self {
+        Self {
+            sketch,
+            components: Vec::new(),
+            comoments: Vec::new(),
+            failed: 0,
+        }
+    }
+
+    fn component(&self) -> Component {
+        Component {
+            moments: Moments::EMPTY,
+            sketch: Sketch::new(self.sketch),
+        }
+    }
+
+    pub(crate) fn push(&mut self, key: u64, values: &[f64]) {
+        if self.components.is_empty() {
+            self.components = vec![self.component(); values.len()];
+            self.comoments = vec![0.0; values.len() * values.len()];
+        }
+        if values.len() != self.components.len() {
+            self.failed += 1;
+            return;
+        }
+        let before: Vec<f64> = self
+            .components
+            .iter()
+            .zip(values)
+            .map(|(c, &x)| x - c.moments.mean)
+            .collect();
+        for (component, &x) in self.components.iter_mut().zip(values) {
+            component.moments.push(x);
+            component.sketch.push(key, x);
+        }
+        let d = values.len();
+        for (j, dj) in before.iter().enumerate() {
+            for (k, (c, &x)) in self.components.iter().zip(values).enumerate() {
+                self.comoments[j * d + k] += dj * (x - c.moments.mean);
+            }
+        }
+    }
+
+    pub(crate) fn merge(self, other: Accumulator) -> Accumulator {
+        let failed = self.failed + other.failed;
+        let (mut kept, dropped) = match (self.n(), other.n()) {
+            (0, _) => (other, self),
+            (_, 0) => (self, other),
+            (na, nb) if self.components.len() == other.components.len() => {
+                let weight = na as f64 * nb as f64 / (na + nb) as f64;
+                let delta: Vec<f64> = self
+                    .components
+                    .iter()
+                    .zip(&other.components)
+                    .map(|(a, b)| b.moments.mean - a.moments.mean)
+                    .collect();
+                let d = delta.len();
+                let comoments = self
+                    .comoments
+                    .iter()
+                    .zip(&other.comoments)
+                    .enumerate()
+                    .map(|(i, (a, b))| a + b + delta[i / d] * delta[i % d] * weight)
+                    .collect();
+                let components = self
+                    .components
+                    .into_iter()
+                    .zip(other.components)
+                    .map(|(a, b)| a.merge(b))
+                    .collect();
+                let sketch = self.sketch;
+                return Accumulator {
+                    sketch,
+                    components,
+                    comoments,
+                    failed,
+                };
+            }
+            (a, b) if a >= b => (self, other),
+            _ => (other, self),
+        };
+        // Mismatched lengths across workers: keep the larger group and
+        // count the other's replicas as failed.
+        kept.failed = failed + dropped.n();
+        kept
+    }
+
+    fn n(&self) -> usize {
+        self.components.first().map_or(0, |c| c.moments.n)
+    }
+
+    /// Moments of the first component, empty when nothing was pushed.
+    pub(crate) fn moments(&self) -> Moments {
+        self.components
+            .first()
+            .map_or(Moments::EMPTY, |c| c.moments)
+    }
+
+    pub(crate) fn finish<T: StreamingStatistic>(self, central: Option<&T>) -> Option<T::Stats> {
+        if self.n() == 0 {
+            return None;
+        }
+        let d = self.components.len();
+        let denom = (self.n() as f64 - 1.0).max(1.0);
+        let covariance = self
+            .comoments
+            .chunks(d)
+            .map(|row| row.iter().map(|c| c / denom).collect())
+            .collect();
+        let components = self.components.into_iter().enumerate().map(|(k, c)| {
+            let stats = c.statistics();
+            match central.and_then(|v| v.components().get(k)) {
+                Some(&c) => with_basic(stats, c),
+                None => stats,
+            }
+        });
+        Some(T::assemble(components.collect(), covariance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_moments_match_single_pass() {
+        let data: Vec<f64> = (0..500).map(|i| ((i * 37) % 101) as f64 / 7.0).collect();
+        let mut whole = Moments::EMPTY;
+        data.iter().for_each(|&x| whole.push(x));
+        let (mut a, mut b) = (Moments::EMPTY, Moments::EMPTY);
+        data[..123].iter().for_each(|&x| a.push(x));
+        data[123..].iter().for_each(|&x| b.push(x));
+        let merged = a.merge(b);
+
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let central = |k: i32| data.iter().map(|x| (x - mean).powi(k)).sum::<f64>();
+        for m in [whole, merged] {
+            assert_eq!(m.n, 500);
+            assert!((m.mean - mean).abs() < 1e-12);
+            assert!((m.m2 - central(2)).abs() < 1e-9 * central(2));
+            assert!((m.m3 - central(3)).abs() < 1e-9 * central(2).powf(1.5));
+            assert!((m.m4 - central(4)).abs() < 1e-9 * central(4));
+        }
+    }
+
+    #[test]
+    fn reservoir_keeps_smallest_keys_in_any_order() {
+        let items: Vec<(u64, f64)> = (0..100u64).map(|i| ((i * 7919) % 100, i as f64)).collect();
+        let fill = |items: &[(u64, f64)]| {
+            let mut r = Reservoir {
+                capacity: 10,
+                items: Vec::new(),
+            };
+            items.iter().for_each(|&(k, x)| r.push(k, x));
+            r
+        };
+        let one = fill(&items).sorted_values();
+        let split = fill(&items[60..]).merge(fill(&items[..60])).sorted_values();
+        assert_eq!(one.len(), 10);
+        assert_eq!(one, split);
+    }
+}
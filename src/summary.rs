@@ -1,69 +1,219 @@
-use crate::bootstrap::{BootstrapResult, EstimatorError, EstimatorResult};
+use crate::bca::acceleration_from_jackknife;
+use crate::bootstrap::{BootstrapResult, EstimatorError, EstimatorResult, Warning};
+use crate::error::BootstrapError;
 use crate::samplers::SamplingStrategy;
+use serde::de::DeserializeOwned;
 use serde::ser::SerializeStruct;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::ops::Deref;
 
-const ONE_SIGMA: f64 = 0.682_689_492_137_086;
-const TWO_SIGMA: f64 = 0.954_499_736_103_642;
-const THREE_SIGMA: f64 = 0.997_300_203_936_740;
+pub(crate) const ONE_SIGMA: f64 = 0.682_689_492_137_086;
+pub(crate) const TWO_SIGMA: f64 = 0.954_499_736_103_642;
+pub(crate) const THREE_SIGMA: f64 = 0.997_300_203_936_740;
+/// `1/Φ⁻¹(3/4)`, making the MAD consistent for the normal standard deviation.
+const MAD_SCALE: f64 = 1.4826;
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct ConfidenceInterval {
     pub low: f64,
     pub high: f64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Statistics {
     pub n: usize,
     pub mean: f64,
     pub median: f64,
+    /// Standard deviation of the replicas: the bootstrap estimate of the
+    /// statistic's standard error, which `n_boot` does not shrink.
     pub stddev: f64,
+    /// Monte Carlo standard error of `mean`, `stddev / √n`: the resampling
+    /// noise in the replica mean, which does shrink with `n_boot`. Small
+    /// next to `stddev`, the spread reflects the data; comparable to it,
+    /// the run is too short to say. Weighted statistics use the effective
+    /// sample size in place of `n`. NaN when read from JSON written before
+    /// the field existed.
+    #[serde(default = "missing_f64", deserialize_with = "nan_if_null")]
+    pub mc_std_error: f64,
+    /// Sample skewness `m₃ / m₂^{3/2}` of the replicas, from the central
+    /// moments `mₖ = Σ(x − mean)ᵏ / n`. Far from zero, the replica
+    /// distribution is lopsided and percentile intervals deserve doubt.
+    /// NaN when every replica is equal.
+    #[serde(deserialize_with = "nan_if_null")]
+    pub skewness: f64,
+    /// Sample excess kurtosis `m₄ / m₂² − 3`; zero for normal replicas.
+    /// NaN when every replica is equal.
+    #[serde(deserialize_with = "nan_if_null")]
+    pub excess_kurtosis: f64,
     pub iqr: f64,
+    /// Median absolute deviation from the median, scaled by 1.4826 so it
+    /// estimates the standard deviation for normal replicas. Unlike
+    /// `stddev`, not dragged around by a few extreme replicas.
+    pub mad: f64,
     pub max: f64,
     pub min: f64,
     pub ci_68: ConfidenceInterval,
     pub ci_95: ConfidenceInterval,
     pub ci_99: ConfidenceInterval,
+    /// Bias-corrected and accelerated intervals at the same levels. Only
+    /// present when the run kept jackknife values ([`Bootstrap::bca`]) and
+    /// the central value succeeded.
+    ///
+    /// [`Bootstrap::bca`]: crate::Bootstrap::bca
+    pub ci_68_bca: Option<ConfidenceInterval>,
+    pub ci_95_bca: Option<ConfidenceInterval>,
+    pub ci_99_bca: Option<ConfidenceInterval>,
+    /// Basic (reverse percentile) intervals `[2θ̂ − q_high, 2θ̂ − q_low]`,
+    /// reflecting the percentile interval about the central value to
+    /// correct for the direction of bias. Only present when the central
+    /// value succeeded.
+    pub ci_68_basic: Option<ConfidenceInterval>,
+    pub ci_95_basic: Option<ConfidenceInterval>,
+    pub ci_99_basic: Option<ConfidenceInterval>,
+}
+
+/// JSON writes NaN as `null`; read it back as NaN.
+fn nan_if_null<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NAN))
+}
+
+/// A field absent from older output reads as NaN.
+fn missing_f64() -> f64 {
+    f64::NAN
+}
+
+impl Statistics {
+    /// Monte Carlo standard error of `mean`: how much the reported replica
+    /// mean would move between runs with different seeds. If it is not
+    /// small next to `stddev`, raise `n_boot`.
+    #[deprecated(note = "read the `mc_std_error` field")]
+    pub fn mc_se_of_mean(&self) -> f64 {
+        self.mc_std_error
+    }
+
+    /// Half-widths of `ci_68`, `ci_95` and `ci_99` relative to the mean,
+    /// `(high − low) / (2·|mean|)`, for quoting an interval as `±x%`.
+    /// `None` when the mean is zero or negligible next to the replicas'
+    /// magnitude, where a relative width would be meaningless.
+    pub fn relative_half_widths(&self) -> Option<[f64; 3]> {
+        let scale = self.min.abs().max(self.max.abs());
+        if self.mean.is_nan() || self.mean.abs() <= 1e-12 * scale {
+            return None;
+        }
+        let relative = |ci: &ConfidenceInterval| (ci.high - ci.low) / (2.0 * self.mean.abs());
+        Some([
+            relative(&self.ci_68),
+            relative(&self.ci_95),
+            relative(&self.ci_99),
+        ])
+    }
+}
+
+/// Which value `Statistics::median` reports for an even number of
+/// replicas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MedianPolicy {
+    /// Mean of the two central order statistics.
+    #[default]
+    Average,
+    /// The lower of the two; always an attained replica value, which suits
+    /// discrete statistics.
+    Lower,
+    /// The higher of the two.
+    Higher,
+}
+
+/// Conventions for computing summary statistics. The default matches
+/// [`calculate_stats`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct SummaryOptions {
+    pub median: MedianPolicy,
+}
+
+impl SummaryOptions {
+    pub fn median(mut self, policy: MedianPolicy) -> Self {
+        self.median = policy;
+        self
+    }
 }
 
 /// Compute summary stats on a slice of samples. Uses `f64::total_cmp` for
 /// sorting so NaN inputs land in a well-defined place rather than silently
 /// corrupting quantiles.
 pub fn calculate_stats(data: &mut [f64]) -> Option<Statistics> {
+    calculate_stats_by(data, f64::total_cmp)
+}
+
+/// As [`calculate_stats`], following the conventions in `options`.
+pub fn calculate_stats_with(data: &mut [f64], options: &SummaryOptions) -> Option<Statistics> {
     if data.is_empty() {
         return None;
     }
-
     data.sort_unstable_by(f64::total_cmp);
+    Some(stats_of_sorted(data, options))
+}
+
+/// As [`calculate_stats`], but sorting with `compare`. The comparator must
+/// be a total order for the quantiles to mean anything; use it to choose
+/// where NaNs land, or to order values the default does not handle.
+pub fn calculate_stats_by<F>(data: &mut [f64], compare: F) -> Option<Statistics>
+where
+    F: FnMut(&f64, &f64) -> Ordering,
+{
+    if data.is_empty() {
+        return None;
+    }
 
+    data.sort_unstable_by(compare);
+    Some(stats_of_sorted(data, &SummaryOptions::default()))
+}
+
+/// Statistics of sorted `data`, which may be any float type that widens
+/// to `f64`: only the outputs are widened, never a copy of the data.
+pub(crate) fn stats_of_sorted<V: Copy + Into<f64>>(
+    data: &[V],
+    options: &SummaryOptions,
+) -> Statistics {
+    let at = |i: usize| -> f64 { data[i].into() };
+    let values = || data.iter().map(|&x| -> f64 { x.into() });
     let n = data.len() as f64;
-    let mean = data.iter().sum::<f64>() / n;
-    let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+    let mean = values().sum::<f64>() / n;
+    let variance = values().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
     let stddev = variance.sqrt();
+    let (skewness, excess_kurtosis) = shape(values().map(|x| (x, 1.0)), mean, n);
     let mid = data.len() / 2;
     let median = if data.len().is_multiple_of(2) {
-        (data[mid - 1] + data[mid]) / 2.0
+        match options.median {
+            MedianPolicy::Average => (at(mid - 1) + at(mid)) / 2.0,
+            MedianPolicy::Lower => at(mid - 1),
+            MedianPolicy::Higher => at(mid),
+        }
     } else {
-        data[mid]
+        at(mid)
     };
 
-    let quantile = |q: f64| -> f64 {
-        let idx = (q * (data.len() - 1) as f64).round() as usize;
-        data[idx]
-    };
+    let quantile = |q: f64| quantile_sorted(data, q);
+    let mut deviations: Vec<f64> = values().map(|x| (x - median).abs()).collect();
+    deviations.sort_unstable_by(f64::total_cmp);
 
-    Some(Statistics {
+    Statistics {
         n: data.len(),
         mean,
         median,
         stddev,
-        min: *data.first().unwrap(),
-        max: *data.last().unwrap(),
+        mc_std_error: stddev / n.sqrt(),
+        skewness,
+        excess_kurtosis,
+        min: at(0),
+        max: at(data.len() - 1),
         iqr: quantile(0.75) - quantile(0.25),
+        mad: MAD_SCALE * quantile_sorted(&deviations, 0.5),
         ci_68: ConfidenceInterval {
             low: quantile((1.0 - ONE_SIGMA) / 2.0),
             high: quantile((1.0 + ONE_SIGMA) / 2.0),
@@ -76,9 +226,389 @@ pub fn calculate_stats(data: &mut [f64]) -> Option<Statistics> {
             low: quantile((1.0 - THREE_SIGMA) / 2.0),
             high: quantile((1.0 + THREE_SIGMA) / 2.0),
         },
+        ci_68_bca: None,
+        ci_95_bca: None,
+        ci_99_bca: None,
+        ci_68_basic: None,
+        ci_95_basic: None,
+        ci_99_basic: None,
+    }
+}
+
+/// Summary stats with replica `i` carrying weight `weights[i]`, for
+/// example [`decay_weights`] over a rolling window of replicas. Quantiles
+/// (and so the median, IQR and intervals) interpolate the same way as the
+/// unweighted ones (see [`weighted_quantile_sorted`]); `stddev`
+/// uses the reliability-weights correction `Σw·(x − m)² / (W − Σw²/W)`.
+/// `n` stays the number of replicas. Returns `None` for empty input or a
+/// non-positive total weight.
+///
+/// # Panics
+///
+/// If `weights` and `data` differ in length, or any weight is negative or
+/// non-finite.
+pub fn calculate_weighted_stats(data: &[f64], weights: &[f64]) -> Option<Statistics> {
+    assert_eq!(data.len(), weights.len(), "one weight per replica");
+    assert!(
+        weights.iter().all(|w| w.is_finite() && *w >= 0.0),
+        "replica weights must be finite and non-negative"
+    );
+    let total: f64 = weights.iter().sum();
+    if data.is_empty() || total <= 0.0 {
+        return None;
+    }
+    let mut pairs: Vec<(f64, f64)> = data.iter().copied().zip(weights.iter().copied()).collect();
+    pairs.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mean = pairs.iter().map(|(x, w)| w * x).sum::<f64>() / total;
+    let sum_sq_w: f64 = weights.iter().map(|w| w * w).sum();
+    let denom = total - sum_sq_w / total;
+    let variance = if denom > 0.0 {
+        pairs
+            .iter()
+            .map(|(x, w)| w * (x - mean).powi(2))
+            .sum::<f64>()
+            / denom
+    } else {
+        0.0
+    };
+    // Zero-weight replicas would otherwise anchor the interpolation.
+    let positive: Vec<(f64, f64)> = pairs.iter().copied().filter(|&(_, w)| w > 0.0).collect();
+    let quantile = |q: f64| weighted_quantile_sorted(&positive, total, q);
+    let median = quantile(0.5);
+    let mut deviations: Vec<(f64, f64)> = positive
+        .iter()
+        .map(|&(x, w)| ((x - median).abs(), w))
+        .collect();
+    deviations.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+    let interval = |level: f64| ConfidenceInterval {
+        low: quantile((1.0 - level) / 2.0),
+        high: quantile((1.0 + level) / 2.0),
+    };
+
+    let (skewness, excess_kurtosis) = shape(pairs.iter().copied(), mean, total);
+    let stddev = variance.sqrt();
+    let effective_n = total * total / sum_sq_w;
+
+    Some(Statistics {
+        n: data.len(),
+        mean,
+        median,
+        stddev,
+        mc_std_error: stddev / effective_n.sqrt(),
+        skewness,
+        excess_kurtosis,
+        min: pairs[0].0,
+        max: pairs[pairs.len() - 1].0,
+        iqr: quantile(0.75) - quantile(0.25),
+        mad: MAD_SCALE * weighted_quantile_sorted(&deviations, total, 0.5),
+        ci_68: interval(ONE_SIGMA),
+        ci_95: interval(TWO_SIGMA),
+        ci_99: interval(THREE_SIGMA),
+        ci_68_bca: None,
+        ci_95_bca: None,
+        ci_99_bca: None,
+        ci_68_basic: None,
+        ci_95_basic: None,
+        ci_99_basic: None,
     })
 }
 
+/// Quantile `q` of the sorted `(value, weight)` pairs, generalising
+/// [`quantile_sorted`]'s type 7 rule: each value sits at the weight
+/// accumulated before it, on a scale running from 0 at the first value to
+/// `total − w_last` at the last, and `q` is interpolated linearly along
+/// it. With equal weights this is exactly the unweighted rule, so the two
+/// agree rather than one stepping where the other interpolates. Every
+/// weight must be positive.
+fn weighted_quantile_sorted(pairs: &[(f64, f64)], total: f64, q: f64) -> f64 {
+    let last = pairs[pairs.len() - 1];
+    let h = q.clamp(0.0, 1.0) * (total - last.1);
+    let mut before = 0.0;
+    for step in pairs.windows(2) {
+        let ((x, w), (next, _)) = (step[0], step[1]);
+        if before + w >= h {
+            let frac = if w > 0.0 { (h - before) / w } else { 0.0 };
+            return x + frac * (next - x);
+        }
+        before += w;
+    }
+    last.0
+}
+
+/// Skewness and excess kurtosis of weighted `(value, weight)` pairs with
+/// weighted mean `mean` and total weight `total`.
+fn shape(pairs: impl Iterator<Item = (f64, f64)>, mean: f64, total: f64) -> (f64, f64) {
+    let (m2, m3, m4) = pairs.fold((0.0, 0.0, 0.0), |(m2, m3, m4), (x, w)| {
+        let d = x - mean;
+        let d2 = d * d;
+        (m2 + w * d2, m3 + w * d2 * d, m4 + w * d2 * d2)
+    });
+    let (m2, m3, m4) = (m2 / total, m3 / total, m4 / total);
+    if m2 > 0.0 {
+        (m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+    } else {
+        (f64::NAN, f64::NAN)
+    }
+}
+
+/// Freedman–Diaconis bin count for a histogram of `data`: bins of width
+/// `2·IQR·n^(−1/3)` spanning the range. Falls back to Sturges' rule,
+/// `⌈log₂ n⌉ + 1`, when the IQR is zero, and is 1 for empty or constant
+/// data. Never more than the number of values, so a far outlier cannot
+/// blow the count up. Non-finite values are ignored.
+pub fn freedman_diaconis_bins(data: &[f64]) -> usize {
+    let mut sorted: Vec<f64> = data.iter().copied().filter(|x| x.is_finite()).collect();
+    if sorted.len() < 2 {
+        return 1;
+    }
+    sorted.sort_unstable_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let range = sorted[sorted.len() - 1] - sorted[0];
+    if range == 0.0 {
+        return 1;
+    }
+    let iqr = quantile_sorted(&sorted, 0.75) - quantile_sorted(&sorted, 0.25);
+    let bins = if iqr > 0.0 {
+        (range / (2.0 * iqr * n.powf(-1.0 / 3.0))).ceil() as usize
+    } else {
+        n.log2().ceil() as usize + 1
+    };
+    bins.min(sorted.len())
+}
+
+/// Exponential-decay weights `λ^(k−i)` for replicas `i = 1..=k` in arrival
+/// order, so the newest replica has weight 1 and each older one `λ` times
+/// the next. `λ = 1` weights all replicas equally.
+///
+/// # Panics
+///
+/// If `lambda` is not in `(0, 1]`.
+pub fn decay_weights(k: usize, lambda: f64) -> Vec<f64> {
+    assert!(
+        lambda > 0.0 && lambda <= 1.0,
+        "decay factor must lie in (0, 1], got {lambda}"
+    );
+    (1..=k).map(|i| lambda.powi((k - i) as i32)).collect()
+}
+
+/// Quantile `q` of already-sorted `data`. Shared by every interval
+/// computation so they all agree on the quantile rule: Hyndman and Fan's
+/// type 7 (R's default), interpolating linearly between the order
+/// statistics either side of position `q·(n − 1)`. Rounding to the nearer
+/// one instead biases tail endpoints and makes them jump as `n_boot`
+/// changes.
+pub(crate) fn quantile_sorted<V: Copy + Into<f64>>(data: &[V], q: f64) -> f64 {
+    let h = q.clamp(0.0, 1.0) * (data.len() - 1) as f64;
+    let lo = h.floor() as usize;
+    let low: f64 = data[lo].into();
+    match data.get(lo + 1) {
+        Some(&next) => low + (h - lo as f64) * (next.into() - low),
+        None => low,
+    }
+}
+
+/// Sort replicas of any float type that widens to `f64` in the order
+/// `f64::total_cmp` gives their widened values, without widening a copy.
+fn sort_widened<V: Copy + Into<f64>>(data: &mut [V]) {
+    data.sort_unstable_by(|&a, &b| a.into().total_cmp(&b.into()));
+}
+
+/// Standard normal quantile function (Acklam's rational approximation,
+/// relative error below 1.2e-9). `p` must lie in `(0, 1)`.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+/// Fraction of `samples` strictly below `central`.
+pub(crate) fn fraction_below<V: Copy + Into<f64>>(samples: &[V], central: f64) -> f64 {
+    samples.iter().filter(|&&x| x.into() < central).count() as f64 / samples.len() as f64
+}
+
+/// BCa interval at `level` from sorted replicas, the central value and the
+/// jackknife values over the original indices. The bias correction is
+/// `z₀ = Φ⁻¹(fraction of replicas below θ̂)` and the acceleration
+/// `a = Σ(θ̄₍.₎ − θ₍ᵢ₎)³ / (6·(Σ(θ̄₍.₎ − θ₍ᵢ₎)²)^{3/2})`; the interval takes the
+/// replica quantiles at `Φ(z₀ + (z₀ + z)/(1 − a(z₀ + z)))` for `z = ∓z₁₋α/₂`.
+/// `None` without replicas or jackknife values, or when every replica lies
+/// on one side of `θ̂`.
+pub(crate) fn bca_interval<V: Copy + Into<f64>>(
+    sorted: &[V],
+    central: f64,
+    jackknife: &[f64],
+    level: f64,
+) -> Option<ConfidenceInterval> {
+    if sorted.is_empty() || jackknife.is_empty() {
+        return None;
+    }
+    let z0 = normal_quantile(fraction_below(sorted, central));
+    if !z0.is_finite() {
+        return None;
+    }
+    let accel = acceleration_from_jackknife(jackknife);
+    let z = normal_quantile(0.5 + level / 2.0);
+    let adjusted = |zq: f64| normal_cdf(z0 + (z0 + zq) / (1.0 - accel * (z0 + zq)));
+    Some(ConfidenceInterval {
+        low: quantile_sorted(sorted, adjusted(-z)),
+        high: quantile_sorted(sorted, adjusted(z)),
+    })
+}
+
+/// Fill in the BCa intervals of `stats`, computed from `samples`.
+fn with_bca<V: Copy + Into<f64>>(
+    stats: Statistics,
+    samples: &[V],
+    central: f64,
+    jackknife: &[f64],
+) -> Statistics {
+    let mut sorted = samples.to_vec();
+    sort_widened(&mut sorted);
+    with_bca_sorted(stats, &sorted, central, jackknife)
+}
+
+/// As [`with_bca`], for replicas already in ascending order.
+fn with_bca_sorted<V: Copy + Into<f64>>(
+    mut stats: Statistics,
+    sorted: &[V],
+    central: f64,
+    jackknife: &[f64],
+) -> Statistics {
+    stats.ci_68_bca = bca_interval(sorted, central, jackknife, ONE_SIGMA);
+    stats.ci_95_bca = bca_interval(sorted, central, jackknife, TWO_SIGMA);
+    stats.ci_99_bca = bca_interval(sorted, central, jackknife, THREE_SIGMA);
+    stats
+}
+
+/// Fill in the basic intervals of `stats` by reflecting its percentile
+/// intervals about `central`.
+pub(crate) fn with_basic(mut stats: Statistics, central: f64) -> Statistics {
+    let reflect = |ci: &ConfidenceInterval| ConfidenceInterval {
+        low: 2.0 * central - ci.high,
+        high: 2.0 * central - ci.low,
+    };
+    stats.ci_68_basic = Some(reflect(&stats.ci_68));
+    stats.ci_95_basic = Some(reflect(&stats.ci_95));
+    stats.ci_99_basic = Some(reflect(&stats.ci_99));
+    stats
+}
+
+/// Standard normal CDF, via the complementary error function (Numerical
+/// Recipes' Chebyshev fit, relative error below 1.2e-7).
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let erfc = t * poly.exp();
+    if x >= 0.0 {
+        1.0 - erfc / 2.0
+    } else {
+        erfc / 2.0
+    }
+}
+
+/// Simultaneous intervals for a vector statistic via the max-t method.
+///
+/// For each replica the largest standardised deviation across components,
+/// `max_j |θ*_j − mean_j| / sd_j`, is recorded; the `level` quantile `c` of
+/// that maximum then gives intervals `mean_j ± c·sd_j` which cover *all*
+/// components jointly with probability `level`, unlike the per-component
+/// percentile intervals in `Statistics`. Components with zero spread get a
+/// degenerate interval. Returns `None` for no samples, ragged samples or a
+/// level outside `(0, 1)`.
+pub fn simultaneous_intervals(samples: &[Vec<f64>], level: f64) -> Option<Vec<ConfidenceInterval>> {
+    if samples.is_empty() || !(level > 0.0 && level < 1.0) {
+        return None;
+    }
+    let dim = samples[0].len();
+    if samples.iter().any(|s| s.len() != dim) {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let means: Vec<f64> = (0..dim)
+        .map(|j| samples.iter().map(|s| s[j]).sum::<f64>() / n)
+        .collect();
+    let sds: Vec<f64> = (0..dim)
+        .map(|j| {
+            let ss = samples
+                .iter()
+                .map(|s| (s[j] - means[j]).powi(2))
+                .sum::<f64>();
+            (ss / (n - 1.0).max(1.0)).sqrt()
+        })
+        .collect();
+    let mut max_t: Vec<f64> = samples
+        .iter()
+        .map(|s| {
+            (0..dim)
+                .filter(|&j| sds[j] > 0.0)
+                .map(|j| (s[j] - means[j]).abs() / sds[j])
+                .fold(0.0, f64::max)
+        })
+        .collect();
+    max_t.sort_unstable_by(f64::total_cmp);
+    let c = quantile_sorted(&max_t, level);
+    Some(
+        means
+            .iter()
+            .zip(&sds)
+            .map(|(m, sd)| ConfidenceInterval {
+                low: m - c * sd,
+                high: m + c * sd,
+            })
+            .collect(),
+    )
+}
+
 /// Types that can be summarised by aggregating replicas. Deliberately does
 /// **not** require arithmetic ops on `Self` — bias correction is the only
 /// operation that needs those and lives on `Estimator`.
@@ -89,60 +619,473 @@ pub trait SummaryStatistic: Sized + Clone + Send + Sync + Serialize + Debug + 's
     /// Reduce replicas to summary stats.
     fn compute_stats(samples: &[Self]) -> Option<Self::Stats>;
 
+    /// As `compute_stats`, following the conventions in `options`. The
+    /// default ignores them.
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
+        let _ = options;
+        Self::compute_stats(samples)
+    }
+
+    /// Stats from the whole result, for intervals that need more than the
+    /// replicas (the central value, jackknife values). The default uses the
+    /// replicas alone.
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        Self::compute_stats_with(&result.samples, options)
+    }
+
     /// Standard-error projection back into `Self` (used for double-bootstrap
     /// composition: `Bootstrap<Bootstrap<T>>::standard_error → T`).
     fn standard_error(stats: &Self::Stats) -> Self;
+
+    /// Bias estimate `mean(replicas) − central` projected back into
+    /// `Self`, as reported in [`BootstrapSummary::bias`]. The default is
+    /// `None`, for types whose statistics carry no mean.
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        let _ = (stats, central);
+        None
+    }
 }
 
 impl SummaryStatistic for f64 {
     type Stats = Statistics;
 
     fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+        Self::compute_stats_with(samples, &SummaryOptions::default())
+    }
+
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
         let mut data = samples.to_vec();
-        calculate_stats(&mut data)
+        calculate_stats_with(&mut data, options)
+    }
+
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        let stats = Self::compute_stats_with(&result.samples, options)?;
+        Some(match (&result.central, &result.jackknife) {
+            (Ok(central), Some(jack)) => {
+                with_bca(with_basic(stats, *central), &result.samples, *central, jack)
+            }
+            (Ok(central), None) => with_basic(stats, *central),
+            _ => stats,
+        })
     }
 
     fn standard_error(stats: &Self::Stats) -> Self {
         stats.stddev
     }
+
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        Some(stats.mean - central)
+    }
+}
+
+/// Statistics of a vector statistic: the per-component [`Statistics`] and
+/// the covariance matrix of the replicas, which joint inference on several
+/// components needs. Derefs to the per-component slice, so `stats[j]` and
+/// `stats.len()` read as they did when this was a `Vec<Statistics>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct VectorStatistics {
+    marginals: Vec<Statistics>,
+    covariance: Vec<Vec<f64>>,
+}
+
+impl VectorStatistics {
+    pub(crate) fn new(marginals: Vec<Statistics>, covariance: Vec<Vec<f64>>) -> Self {
+        Self {
+            marginals,
+            covariance,
+        }
+    }
+
+    /// Per-component statistics, computed as for a scalar statistic.
+    pub fn marginals(&self) -> &[Statistics] {
+        &self.marginals
+    }
+
+    /// Sample covariance matrix of the replicas, `Σ(θ* − θ̄*)(θ* − θ̄*)ᵀ /
+    /// (n − 1)`; its diagonal is the square of each component's `stddev`.
+    pub fn covariance(&self) -> &[Vec<f64>] {
+        &self.covariance
+    }
+}
+
+impl Deref for VectorStatistics {
+    type Target = [Statistics];
+
+    fn deref(&self) -> &[Statistics] {
+        &self.marginals
+    }
+}
+
+impl<'a> IntoIterator for &'a VectorStatistics {
+    type Item = &'a Statistics;
+    type IntoIter = std::slice::Iter<'a, Statistics>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.marginals.iter()
+    }
+}
+
+/// Sample covariance matrix of the components, given as equal-length
+/// columns of replica values.
+fn covariance_matrix<V: Copy + Into<f64>>(columns: &[Vec<V>]) -> Vec<Vec<f64>> {
+    let n = columns.first().map_or(0, Vec::len) as f64;
+    let means: Vec<f64> = columns
+        .iter()
+        .map(|c| c.iter().map(|&x| -> f64 { x.into() }).sum::<f64>() / n)
+        .collect();
+    let denom = (n - 1.0).max(1.0);
+    columns
+        .iter()
+        .zip(&means)
+        .map(|(a, ma)| {
+            columns
+                .iter()
+                .zip(&means)
+                .map(|(b, mb)| {
+                    a.iter()
+                        .zip(b)
+                        .map(|(&x, &y)| (x.into() - ma) * (y.into() - mb))
+                        .sum::<f64>()
+                        / denom
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `rows` laid out by component: `columns[j]` holds component `j` of
+/// every row. `None` without rows or when their lengths differ.
+fn columns_of<V: Copy, R: AsRef<[V]>>(rows: &[R]) -> Option<Vec<Vec<V>>> {
+    let width = rows.first()?.as_ref().len();
+    if rows.iter().any(|r| r.as_ref().len() != width) {
+        return None;
+    }
+    Some(
+        (0..width)
+            .map(|j| rows.iter().map(|r| r.as_ref()[j]).collect())
+            .collect(),
+    )
+}
+
+/// Vector statistics from the replicas laid out by component: `columns[j]`
+/// holds component `j` of every replica. Each column is sorted in place
+/// and widened value by value, so no `f64` copy is made. Given the central
+/// value, basic intervals are added per component, and BCa ones too given
+/// the jackknife replicates laid out the same way.
+pub(crate) fn vector_stats_from_columns<V: Copy + Into<f64>>(
+    columns: Vec<Vec<V>>,
+    options: &SummaryOptions,
+    central: Option<&[f64]>,
+    jackknife: Option<&[Vec<f64>]>,
+) -> Option<VectorStatistics> {
+    let covariance = covariance_matrix(&columns);
+    let mut marginals = Vec::with_capacity(columns.len());
+    for (j, mut column) in columns.into_iter().enumerate() {
+        if column.is_empty() {
+            return None;
+        }
+        sort_widened(&mut column);
+        let mut stats = stats_of_sorted(&column, options);
+        if let Some(&c) = central.and_then(|c| c.get(j)) {
+            stats = with_basic(stats, c);
+            if let Some(jack) = jackknife.and_then(|jack| jack.get(j)) {
+                stats = with_bca_sorted(stats, &column, c, jack);
+            }
+        }
+        marginals.push(stats);
+    }
+    Some(VectorStatistics {
+        marginals,
+        covariance,
+    })
 }
 
 impl SummaryStatistic for Vec<f64> {
-    type Stats = Vec<Statistics>;
+    type Stats = VectorStatistics;
+
+    fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+        Self::compute_stats_with(samples, &SummaryOptions::default())
+    }
+
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
+        vector_stats_from_columns(columns_of(samples)?, options, None, None)
+    }
+
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        let jackknife = result.jackknife.as_deref().and_then(columns_of);
+        vector_stats_from_columns(
+            columns_of(&result.samples)?,
+            options,
+            result.central.as_deref().ok(),
+            jackknife.as_deref(),
+        )
+    }
+
+    fn standard_error(stats: &Self::Stats) -> Self {
+        stats.iter().map(|s| s.stddev).collect()
+    }
+
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        (stats.len() == central.len())
+            .then(|| stats.iter().zip(central).map(|(s, c)| s.mean - c).collect())
+    }
+}
+
+/// Keyed statistics are summarised key by key over every replica. A key
+/// missing from a replica, the central value or a jackknife replicate
+/// reads as zero, as in the map's [`Arithmetic`](crate::Arithmetic) impl,
+/// so each key's `n` is the number of replicas and its `mean` is the
+/// arithmetic mean of the maps. Keys seen in no replica are absent from
+/// the result.
+impl<K> SummaryStatistic for BTreeMap<K, f64>
+where
+    K: Ord + Clone + Send + Sync + Serialize + Debug + 'static,
+{
+    type Stats = BTreeMap<K, Statistics>;
 
     fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+        Self::compute_stats_with(samples, &SummaryOptions::default())
+    }
+
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
         if samples.is_empty() {
             return None;
         }
-        let vec_len = samples[0].len();
-        let n_samples = samples.len();
-        let mut transposed: Vec<Vec<f64>> = (0..vec_len)
-            .map(|_| Vec::with_capacity(n_samples))
-            .collect();
-        for sample in samples {
-            for (i, val) in sample.iter().enumerate() {
-                transposed[i].push(*val);
+        keyed_columns(samples)
+            .into_iter()
+            .map(|(k, mut column)| Some((k, calculate_stats_with(&mut column, options)?)))
+            .collect()
+    }
+
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        let columns = keyed_columns(&result.samples);
+        let Ok(central) = &result.central else {
+            return Self::compute_stats_with(&result.samples, options);
+        };
+        let jack_columns = result.jackknife.as_deref().map(|jack| {
+            let mut keyed = keyed_columns(jack);
+            columns
+                .keys()
+                .map(|k| keyed.remove(k).unwrap_or_else(|| vec![0.0; jack.len()]))
+                .collect::<Vec<_>>()
+        });
+        columns
+            .into_iter()
+            .enumerate()
+            .map(|(j, (k, column))| {
+                let mut sorted = column.clone();
+                let s = calculate_stats_with(&mut sorted, options)?;
+                let c = central.get(&k).copied().unwrap_or(0.0);
+                let s = with_basic(s, c);
+                let s = match &jack_columns {
+                    Some(jack) => with_bca(s, &column, c, &jack[j]),
+                    None => s,
+                };
+                Some((k, s))
+            })
+            .collect()
+    }
+
+    fn standard_error(stats: &Self::Stats) -> Self {
+        stats.iter().map(|(k, s)| (k.clone(), s.stddev)).collect()
+    }
+
+    /// Per key of the statistics, a key missing from `central` reading as
+    /// zero.
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        Some(
+            stats
+                .iter()
+                .map(|(k, s)| (k.clone(), s.mean - central.get(k).copied().unwrap_or(0.0)))
+                .collect(),
+        )
+    }
+}
+
+/// One column per key seen in `maps`, holding that key's value in each map
+/// in order, zero where a map lacks it.
+fn keyed_columns<K: Ord + Clone>(maps: &[BTreeMap<K, f64>]) -> BTreeMap<K, Vec<f64>> {
+    let mut columns: BTreeMap<K, Vec<f64>> = BTreeMap::new();
+    for (i, map) in maps.iter().enumerate() {
+        for (k, &v) in map {
+            columns
+                .entry(k.clone())
+                .or_insert_with(|| vec![0.0; maps.len()])[i] = v;
+        }
+    }
+    columns
+}
+
+/// Tuples are summarised component by component, so `(f64, f64)` gives
+/// `(Statistics, Statistics)`. Unlike `Vec<f64>`, no covariance is kept.
+macro_rules! tuple_summary {
+    ($($T:ident . $i:tt),+) => {
+        impl<$($T: SummaryStatistic),+> SummaryStatistic for ($($T,)+) {
+            type Stats = ($($T::Stats,)+);
+
+            fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+                Self::compute_stats_with(samples, &SummaryOptions::default())
+            }
+
+            fn compute_stats_with(
+                samples: &[Self],
+                options: &SummaryOptions,
+            ) -> Option<Self::Stats> {
+                Some(($(
+                    $T::compute_stats_with(
+                        &samples.iter().map(|s| s.$i.clone()).collect::<Vec<_>>(),
+                        options,
+                    )?,
+                )+))
+            }
+
+            fn compute_stats_from(
+                result: &BootstrapResult<Self>,
+                options: &SummaryOptions,
+            ) -> Option<Self::Stats> {
+                Some(($($T::compute_stats_from(&result.map(|s| s.$i), options)?,)+))
+            }
+
+            fn standard_error(stats: &Self::Stats) -> Self {
+                ($($T::standard_error(&stats.$i),)+)
+            }
+
+            fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+                Some(($($T::bias(&stats.$i, &central.$i)?,)+))
             }
         }
-        let mut statistics_vec = Vec::with_capacity(vec_len);
-        for mut col_data in transposed.into_iter() {
-            statistics_vec.push(calculate_stats(&mut col_data)?);
+    };
+}
+
+tuple_summary!(A.0, B.1);
+tuple_summary!(A.0, B.1, C.2);
+tuple_summary!(A.0, B.1, C.2, D.3);
+tuple_summary!(A.0, B.1, C.2, D.3, E.4);
+tuple_summary!(A.0, B.1, C.2, D.3, E.4, F.5);
+
+/// Single-precision replicas, for runs where replica storage dominates
+/// memory. Summaries sort an `f32` copy and widen each value as it is
+/// read, so the statistics match an `f64` run on the same replicas without
+/// an `f64` copy of them.
+impl SummaryStatistic for f32 {
+    type Stats = Statistics;
+
+    fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+        Self::compute_stats_with(samples, &SummaryOptions::default())
+    }
+
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
+        if samples.is_empty() {
+            return None;
         }
-        Some(statistics_vec)
+        let mut data = samples.to_vec();
+        sort_widened(&mut data);
+        Some(stats_of_sorted(&data, options))
+    }
+
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        let stats = Self::compute_stats_with(&result.samples, options)?;
+        Some(match (&result.central, &result.jackknife) {
+            (Ok(central), Some(jack)) => {
+                let central = f64::from(*central);
+                with_bca(
+                    with_basic(stats, central),
+                    &result.samples,
+                    central,
+                    &widen(jack),
+                )
+            }
+            (Ok(central), None) => with_basic(stats, f64::from(*central)),
+            _ => stats,
+        })
     }
 
     fn standard_error(stats: &Self::Stats) -> Self {
-        stats.iter().map(|s| s.stddev).collect()
+        stats.stddev as f32
+    }
+
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        Some((stats.mean - f64::from(*central)) as f32)
     }
 }
 
+/// As for `f32`, one component column at a time.
+impl SummaryStatistic for Vec<f32> {
+    type Stats = VectorStatistics;
+
+    fn compute_stats(samples: &[Self]) -> Option<Self::Stats> {
+        Self::compute_stats_with(samples, &SummaryOptions::default())
+    }
+
+    fn compute_stats_with(samples: &[Self], options: &SummaryOptions) -> Option<Self::Stats> {
+        vector_stats_from_columns(columns_of(samples)?, options, None, None)
+    }
+
+    fn compute_stats_from(
+        result: &BootstrapResult<Self>,
+        options: &SummaryOptions,
+    ) -> Option<Self::Stats> {
+        let central = result.central.as_deref().ok().map(widen);
+        let jackknife: Option<Vec<Vec<f64>>> = result
+            .jackknife
+            .as_deref()
+            .and_then(columns_of)
+            .map(|columns| columns.iter().map(|c| widen(c)).collect());
+        vector_stats_from_columns(
+            columns_of(&result.samples)?,
+            options,
+            central.as_deref(),
+            jackknife.as_deref(),
+        )
+    }
+
+    fn standard_error(stats: &Self::Stats) -> Self {
+        stats.iter().map(|s| s.stddev as f32).collect()
+    }
+
+    fn bias(stats: &Self::Stats, central: &Self) -> Option<Self> {
+        Vec::<f64>::bias(stats, &widen(central)).map(|b| b.iter().map(|&x| x as f32).collect())
+    }
+}
+
+fn widen(v: &[f32]) -> Vec<f64> {
+    v.iter().map(|&x| f64::from(x)).collect()
+}
+
 pub trait Summarisable<S> {
     fn summarise(self) -> S;
+
+    /// As `summarise`, but an error rather than a summary without
+    /// statistics when every replica failed, or too many did. The default
+    /// never fails, for implementors with no such condition.
+    fn try_summarise(self) -> Result<S, BootstrapError>
+    where
+        Self: Sized,
+    {
+        Ok(self.summarise())
+    }
 }
 
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct BootstrapSummary<T: SummaryStatistic> {
+    pub label: Option<String>,
     pub n_boot: usize,
     pub sampler: SamplingStrategy,
     pub seed: Option<u64>,
@@ -154,16 +1097,23 @@ pub struct BootstrapSummary<T: SummaryStatistic> {
     pub central: EstimatorResult<T>,
     pub replicas: Vec<T>,
     pub failures: Vec<EstimatorError>,
+    pub warnings: Vec<Warning>,
     pub statistics: Option<T::Stats>,
+    /// Estimated bias of the statistic, `mean(replicas) − central`; `None`
+    /// without a central value or statistics, or for types whose
+    /// [`SummaryStatistic::bias`] gives none. Reported whether or not the
+    /// estimator was bias-corrected.
+    pub bias: Option<T>,
 }
 
 // Hand-written to preserve the legacy JSON shape while also emitting the
 // new diagnostic fields. Downstream consumers that read `central_val` and
 // `failed_samples` continue to work; new consumers can also see
-// `failure_reasons`, `seed`, and `truncated`.
+// `failure_reasons`, `warnings`, `label`, `seed`, and `truncated`.
 impl<T: SummaryStatistic> Serialize for BootstrapSummary<T> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut s = serializer.serialize_struct("BootstrapSummary", 9)?;
+        let mut s = serializer.serialize_struct("BootstrapSummary", 12)?;
+        s.serialize_field("label", &self.label)?;
         s.serialize_field("n_boot", &self.n_boot)?;
         s.serialize_field("sampler", &self.sampler)?;
         s.serialize_field("seed", &self.seed)?;
@@ -175,15 +1125,419 @@ impl<T: SummaryStatistic> Serialize for BootstrapSummary<T> {
         s.serialize_field("replicas", &self.replicas)?;
         s.serialize_field("failed_samples", &self.failures.len())?;
         s.serialize_field("failure_reasons", &self.failures)?;
+        s.serialize_field("warnings", &self.warnings)?;
         s.serialize_field("statistics", &self.statistics)?;
+        s.serialize_field("bias", &self.bias)?;
+        s.end()
+    }
+}
+
+/// The legacy JSON shape written by `Serialize`, read back field by field.
+#[derive(Deserialize)]
+#[serde(bound = "T: DeserializeOwned, S: DeserializeOwned")]
+struct SummaryRepr<T, S> {
+    label: Option<String>,
+    n_boot: usize,
+    sampler: SamplingStrategy,
+    seed: Option<u64>,
+    truncated: usize,
+    central_val: Option<T>,
+    replicas: Vec<T>,
+    failure_reasons: Vec<EstimatorError>,
+    warnings: Vec<Warning>,
+    statistics: Option<S>,
+    bias: Option<T>,
+}
+
+/// Reads back what `Serialize` writes. A failed central value is written
+/// as `null` without its reason, so it comes back as an error saying only
+/// that it was unavailable; `failed_samples` is implied by
+/// `failure_reasons` and ignored.
+impl<'de, T> Deserialize<'de> for BootstrapSummary<T>
+where
+    T: SummaryStatistic + DeserializeOwned,
+    T::Stats: DeserializeOwned,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = SummaryRepr::<T, T::Stats>::deserialize(deserializer)?;
+        Ok(BootstrapSummary {
+            label: repr.label,
+            n_boot: repr.n_boot,
+            sampler: repr.sampler,
+            seed: repr.seed,
+            truncated: repr.truncated,
+            central: repr
+                .central_val
+                .ok_or_else(|| EstimatorError::new("central value unavailable")),
+            replicas: repr.replicas,
+            failures: repr.failure_reasons,
+            warnings: repr.warnings,
+            statistics: repr.statistics,
+            bias: repr.bias,
+        })
+    }
+}
+
+/// Serializable view of a summary without its replicas (and the other
+/// diagnostics): just `n_boot`, `central_val`, `failed_samples` and
+/// `statistics`, under the same keys as the full form. Obtained from
+/// [`BootstrapSummary::stats_only`]; use it when the replica vector would
+/// dominate the output.
+#[derive(Debug, Clone, Copy)]
+pub struct SummaryStatsOnly<'a, T: SummaryStatistic> {
+    summary: &'a BootstrapSummary<T>,
+}
+
+impl<T: SummaryStatistic> BootstrapSummary<T> {
+    pub fn stats_only(&self) -> SummaryStatsOnly<'_, T> {
+        SummaryStatsOnly { summary: self }
+    }
+
+    /// Statistics of the replicas satisfying `predicate`, i.e. of the
+    /// statistic conditional on a resample-level event, together with the
+    /// estimated probability of that event: the fraction of successful
+    /// replicas retained. Failed replicas count towards neither.
+    pub fn conditional_summary<P>(&self, predicate: P) -> ConditionalSummary<T>
+    where
+        P: Fn(&T) -> bool,
+    {
+        let kept: Vec<T> = self
+            .replicas
+            .iter()
+            .filter(|r| predicate(r))
+            .cloned()
+            .collect();
+        let fraction = if self.replicas.is_empty() {
+            f64::NAN
+        } else {
+            kept.len() as f64 / self.replicas.len() as f64
+        };
+        ConditionalSummary {
+            retained: kept.len(),
+            fraction,
+            statistics: T::compute_stats(&kept),
+        }
+    }
+}
+
+/// Output of [`BootstrapSummary::conditional_summary`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct ConditionalSummary<T: SummaryStatistic> {
+    /// Number of replicas satisfying the predicate.
+    pub retained: usize,
+    /// `retained` over the number of successful replicas; NaN if there
+    /// were none.
+    pub fraction: f64,
+    /// Statistics of the retained replicas; `None` if none were.
+    pub statistics: Option<T::Stats>,
+}
+
+impl<T: SummaryStatistic> Serialize for SummaryStatsOnly<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let summary = self.summary;
+        let mut s = serializer.serialize_struct("SummaryStatsOnly", 4)?;
+        s.serialize_field("n_boot", &summary.n_boot)?;
+        s.serialize_field("central_val", &summary.central.as_ref().ok())?;
+        s.serialize_field("failed_samples", &summary.failures.len())?;
+        s.serialize_field("statistics", &summary.statistics)?;
         s.end()
     }
 }
 
+/// Flat view of a scalar summary holding only the most-used numbers, for
+/// embedding in larger reports without depending on the generic summary
+/// type. Fields are `None` when the corresponding quantity is unavailable.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Report {
+    pub estimate: Option<f64>,
+    pub std_error: Option<f64>,
+    pub ci_95_low: Option<f64>,
+    pub ci_95_high: Option<f64>,
+}
+
+impl From<BootstrapSummary<f64>> for Report {
+    fn from(summary: BootstrapSummary<f64>) -> Self {
+        let stats = summary.statistics.as_ref();
+        Report {
+            estimate: summary.central.ok(),
+            std_error: stats.map(|s| s.stddev),
+            ci_95_low: stats.map(|s| s.ci_95.low),
+            ci_95_high: stats.map(|s| s.ci_95.high),
+        }
+    }
+}
+
+impl BootstrapSummary<f64> {
+    /// Histogram of the finite replicas: `bins` equal-width bins from their
+    /// minimum to their maximum (the last bin closed), as `(centre, count)`
+    /// pairs in ascending order. Empty without replicas or for `bins == 0`;
+    /// a single bin when every replica is equal. See
+    /// [`histogram_auto`](Self::histogram_auto) to choose `bins`.
+    pub fn histogram(&self, bins: usize) -> Vec<(f64, usize)> {
+        let finite = || self.replicas.iter().copied().filter(|x| x.is_finite());
+        let min = finite().fold(f64::INFINITY, f64::min);
+        let max = finite().fold(f64::NEG_INFINITY, f64::max);
+        if bins == 0 || min > max {
+            return Vec::new();
+        }
+        if min == max {
+            return vec![(min, finite().count())];
+        }
+        let width = (max - min) / bins as f64;
+        let mut counts = vec![0usize; bins];
+        for x in finite() {
+            counts[(((x - min) / width) as usize).min(bins - 1)] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(b, c)| (min + (b as f64 + 0.5) * width, c))
+            .collect()
+    }
+
+    /// [`histogram`](Self::histogram) with [`freedman_diaconis_bins`] bins.
+    pub fn histogram_auto(&self) -> Vec<(f64, usize)> {
+        self.histogram(freedman_diaconis_bins(&self.replicas))
+    }
+
+    /// The `q`-quantile of the replicas, interpolated as for the
+    /// intervals in [`Statistics`]. `None` without replicas or for `q`
+    /// outside `[0, 1]`.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.replicas.is_empty() || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        let mut sorted = self.replicas.clone();
+        sorted.sort_unstable_by(f64::total_cmp);
+        Some(quantile_sorted(&sorted, q))
+    }
+
+    /// Each replica's standardised deviation `(θ* − mean) / stddev`, in
+    /// replica order, for flagging outlying resamples. `None` without
+    /// statistics or when every replica is identical.
+    pub fn standardized_residuals(&self) -> Option<Vec<f64>> {
+        let stats = self.statistics.as_ref()?;
+        if stats.stddev == 0.0 {
+            return None;
+        }
+        Some(
+            self.replicas
+                .iter()
+                .map(|x| (x - stats.mean) / stats.stddev)
+                .collect(),
+        )
+    }
+
+    /// Interval at `level` for a proportion estimated from `n`
+    /// observations, formed on the log-odds scale and mapped back, so it
+    /// always lies inside `(0, 1)` and is asymmetric near the boundaries.
+    ///
+    /// Each proportion is first shrunk to `(p·n + ½)/(n + 1)` so that
+    /// replicas with no successes (or no failures) keep a finite logit. The
+    /// interval is `logit(p̃) ± z·sd(logit p̃*)`, transformed back with the
+    /// logistic function. Returns `None` without a central value, for
+    /// `n == 0`, for a level outside `(0, 1)`, or with fewer than two
+    /// replicas.
+    pub fn logit_interval(&self, n: usize, level: f64) -> Option<ConfidenceInterval> {
+        if n == 0 || !(level > 0.0 && level < 1.0) || self.replicas.len() < 2 {
+            return None;
+        }
+        let n = n as f64;
+        let logit = |p: f64| {
+            let p = (p * n + 0.5) / (n + 1.0);
+            (p / (1.0 - p)).ln()
+        };
+        let centre = logit(*self.central.as_ref().ok()?);
+        let transformed: Vec<f64> = self.replicas.iter().map(|&p| logit(p)).collect();
+        let m = transformed.len() as f64;
+        let mean = transformed.iter().sum::<f64>() / m;
+        let var = transformed.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (m - 1.0);
+        let half = normal_quantile(0.5 + level / 2.0) * var.sqrt();
+        let logistic = |x: f64| 1.0 / (1.0 + (-x).exp());
+        Some(ConfidenceInterval {
+            low: logistic(centre - half),
+            high: logistic(centre + half),
+        })
+    }
+
+    /// Statistics of the replicas weighted by recency with
+    /// [`decay_weights`], treating replica order as arrival order. Useful
+    /// when the replicas are a rolling window and the target drifts.
+    pub fn decayed_statistics(&self, lambda: f64) -> Option<Statistics> {
+        calculate_weighted_stats(&self.replicas, &decay_weights(self.replicas.len(), lambda))
+    }
+
+    /// Prediction interval at `level` for the mean of `k` future
+    /// observations (`k = 1` for a single new observation).
+    ///
+    /// `obs_variance` is the per-observation variance, typically the sample
+    /// variance of the data; the bootstrap supplies only the uncertainty in
+    /// the parameter. The interval is `central ± z·√(se² + obs_variance/k)`,
+    /// which assumes the statistic is a mean-like location estimate, future
+    /// observations are independent of the data, and the combined error is
+    /// roughly normal. Returns `None` without a central value or statistics,
+    /// for `k == 0`, or for a level outside `(0, 1)`.
+    pub fn prediction_interval(
+        &self,
+        obs_variance: f64,
+        k: usize,
+        level: f64,
+    ) -> Option<ConfidenceInterval> {
+        if k == 0 || !(level > 0.0 && level < 1.0) {
+            return None;
+        }
+        let centre = *self.central.as_ref().ok()?;
+        let se = self.statistics.as_ref()?.stddev;
+        let z = normal_quantile(0.5 + level / 2.0);
+        let half = z * (se * se + obs_variance / k as f64).sqrt();
+        Some(ConfidenceInterval {
+            low: centre - half,
+            high: centre + half,
+        })
+    }
+}
+
+impl BootstrapSummary<Vec<f64>> {
+    /// Assemble scalar summaries of the components of a vector statistic,
+    /// bootstrapped separately, into one vector summary.
+    ///
+    /// Replicas are paired by position, so the combined replicas (and
+    /// anything computed from them jointly, like simultaneous intervals)
+    /// only mean something if every part ran with the same seed and sampler
+    /// and none had failures; the per-component statistics are carried over
+    /// unchanged either way. Returns `None` for no parts or parts with
+    /// differing `n_boot` or replica counts.
+    pub fn combine_components(parts: Vec<BootstrapSummary<f64>>) -> Option<Self> {
+        let first = parts.first()?;
+        let (n_boot, n_replicas) = (first.n_boot, first.replicas.len());
+        if parts
+            .iter()
+            .any(|p| p.n_boot != n_boot || p.replicas.len() != n_replicas)
+        {
+            return None;
+        }
+        let sampler = first.sampler.clone();
+        let seed = first.seed;
+        let truncated = parts.iter().map(|p| p.truncated).max().unwrap_or(0);
+        let mut replicas = vec![Vec::with_capacity(parts.len()); n_replicas];
+        let mut central = Ok(Vec::with_capacity(parts.len()));
+        let mut marginals = Some(Vec::with_capacity(parts.len()));
+        let mut bias = Some(Vec::with_capacity(parts.len()));
+        let (mut failures, mut warnings) = (Vec::new(), Vec::new());
+        for part in parts {
+            for (r, v) in replicas.iter_mut().zip(part.replicas) {
+                r.push(v);
+            }
+            central = match (central, part.central) {
+                (Ok(mut c), Ok(v)) => {
+                    c.push(v);
+                    Ok(c)
+                }
+                (Err(e), _) | (Ok(_), Err(e)) => Err(e),
+            };
+            marginals = marginals.zip(part.statistics).map(|(mut s, p)| {
+                s.push(p);
+                s
+            });
+            bias = bias.zip(part.bias).map(|(mut b, p)| {
+                b.push(p);
+                b
+            });
+            failures.extend(part.failures);
+            warnings.extend(part.warnings);
+        }
+        let statistics = marginals.map(|marginals| {
+            let columns: Vec<Vec<f64>> = (0..marginals.len())
+                .map(|j| replicas.iter().map(|r| r[j]).collect())
+                .collect();
+            VectorStatistics {
+                marginals,
+                covariance: covariance_matrix(&columns),
+            }
+        });
+        Some(BootstrapSummary {
+            label: None,
+            n_boot,
+            sampler,
+            seed,
+            truncated,
+            central,
+            replicas,
+            failures,
+            warnings,
+            statistics,
+            bias,
+        })
+    }
+
+    /// Max-t simultaneous intervals at `level` over all components. See
+    /// [`simultaneous_intervals`].
+    pub fn simultaneous_ci(&self, level: f64) -> Option<Vec<ConfidenceInterval>> {
+        simultaneous_intervals(&self.replicas, level)
+    }
+
+    /// Per-component `q`-quantile of the replicas, interpolated as for the
+    /// intervals in [`Statistics`]. `None` without replicas, for `q`
+    /// outside `[0, 1]`, or for replicas of differing lengths.
+    pub fn quantile(&self, q: f64) -> Option<Vec<f64>> {
+        let width = self.replicas.first()?.len();
+        if !(0.0..=1.0).contains(&q) || self.replicas.iter().any(|r| r.len() != width) {
+            return None;
+        }
+        let mut column = Vec::with_capacity(self.replicas.len());
+        let quantiles = (0..width)
+            .map(|j| {
+                column.clear();
+                column.extend(self.replicas.iter().map(|r| r[j]));
+                column.sort_unstable_by(f64::total_cmp);
+                quantile_sorted(&column, q)
+            })
+            .collect();
+        Some(quantiles)
+    }
+}
+
 impl<T: SummaryStatistic> Summarisable<BootstrapSummary<T>> for BootstrapResult<T> {
     fn summarise(self) -> BootstrapSummary<T> {
-        let statistics = T::compute_stats(&self.samples);
+        self.summarise_with(&SummaryOptions::default())
+    }
+
+    fn try_summarise(self) -> Result<BootstrapSummary<T>, BootstrapError> {
+        if self.samples.is_empty() {
+            return Err(BootstrapError::NoValidSamples {
+                n_boot: self.n_boot,
+                failed_samples: self.failures.len(),
+            });
+        }
+        if self.too_few_valid() {
+            return Err(BootstrapError::TooFewValidSamples {
+                n_boot: self.n_boot,
+                valid: self.samples.len(),
+                min_valid_fraction: self.min_valid_fraction,
+            });
+        }
+        Ok(self.summarise())
+    }
+}
+
+impl<T: SummaryStatistic> BootstrapResult<T> {
+    /// As `summarise`, computing the statistics under `options`. There
+    /// are none when fewer replicas succeeded than `min_valid_fraction`
+    /// allows.
+    pub fn summarise_with(self, options: &SummaryOptions) -> BootstrapSummary<T> {
+        let statistics = if self.too_few_valid() {
+            None
+        } else {
+            T::compute_stats_from(&self, options)
+        };
+        let bias = match (&statistics, &self.central) {
+            (Some(stats), Ok(central)) => T::bias(stats, central),
+            _ => None,
+        };
         BootstrapSummary {
+            label: self.label,
             n_boot: self.n_boot,
             sampler: self.sampler,
             seed: self.seed,
@@ -191,7 +1545,9 @@ impl<T: SummaryStatistic> Summarisable<BootstrapSummary<T>> for BootstrapResult<
             central: self.central,
             replicas: self.samples,
             failures: self.failures,
+            warnings: self.warnings,
             statistics,
+            bias,
         }
     }
 }
@@ -199,6 +1555,7 @@ impl<T: SummaryStatistic> Summarisable<BootstrapSummary<T>> for BootstrapResult<
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{mean_estimator, mean_of};
 
     #[test]
     fn stats_on_integers() {
@@ -219,4 +1576,768 @@ mod tests {
         assert_eq!(s.min, 1.0);
         assert!(s.max.is_nan());
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn mc_se_of_mean_is_stddev_over_root_n() {
+        let mut data: Vec<f64> = (0..400).map(|x| (x as f64).sin()).collect();
+        let s = calculate_stats(&mut data).unwrap();
+        assert!((s.mc_se_of_mean() - s.stddev / 20.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn mc_std_error_uses_effective_sample_size() {
+        let data: Vec<f64> = (0..400).map(|x| (x as f64).sin()).collect();
+        let plain = calculate_stats(&mut data.clone()).unwrap();
+        assert_eq!(plain.mc_std_error, plain.stddev / 20.0);
+
+        // Half the replicas carry all the weight: 200 effective replicas.
+        let weights: Vec<f64> = (0..400).map(|i| f64::from(i % 2)).collect();
+        let weighted = calculate_weighted_stats(&data, &weights).unwrap();
+        let expected = weighted.stddev / 200f64.sqrt();
+        assert!((weighted.mc_std_error - expected).abs() < 1e-15);
+
+        // Output from before the field existed still reads.
+        let mut json = serde_json::to_value(&plain).unwrap();
+        json.as_object_mut().unwrap().remove("mc_std_error");
+        let old: Statistics = serde_json::from_value(json).unwrap();
+        assert!(old.mc_std_error.is_nan());
+        assert_eq!(old.stddev, plain.stddev);
+    }
+
+    #[test]
+    fn relative_half_widths_match_manual() {
+        let mut data: Vec<f64> = (0..200).map(|x| 50.0 + (x as f64 * 0.3).sin()).collect();
+        let s = calculate_stats(&mut data).unwrap();
+        let widths = s.relative_half_widths().unwrap();
+        let manual = (s.ci_95.high - s.ci_95.low) / (2.0 * s.mean);
+        assert_eq!(widths[1], manual);
+        assert!(widths[0] < widths[1] && widths[1] < widths[2]);
+
+        let mut centred: Vec<f64> = (-100..=100).map(|x| x as f64).collect();
+        let s = calculate_stats(&mut centred).unwrap();
+        assert_eq!(s.mean, 0.0);
+        assert!(s.relative_half_widths().is_none());
+    }
+
+    #[test]
+    fn nan_position_does_not_change_stats() {
+        let base = [3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0];
+        let reference = {
+            let mut d = base.to_vec();
+            d.push(f64::NAN);
+            calculate_stats(&mut d).unwrap()
+        };
+        for pos in 0..=base.len() {
+            let mut d = base.to_vec();
+            d.insert(pos, f64::NAN);
+            let s = calculate_stats(&mut d).unwrap();
+            assert_eq!(s.min, reference.min);
+            assert_eq!(s.median, reference.median);
+            assert_eq!(s.ci_68.low, reference.ci_68.low);
+            assert!(d.last().unwrap().is_nan());
+        }
+    }
+
+    #[test]
+    fn custom_comparator_controls_nan_placement() {
+        // NaN first, then finite values ascending.
+        let nan_first = |a: &f64, b: &f64| match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => a.total_cmp(b),
+        };
+        let mut data = vec![2.0, f64::NAN, 1.0, 3.0];
+        let s = calculate_stats_by(&mut data, nan_first).unwrap();
+        assert!(s.min.is_nan());
+        assert_eq!(s.max, 3.0);
+    }
+
+    #[test]
+    fn decay_weighted_mean_tracks_shift() {
+        // The true value jumps from 0 to 5 two thirds of the way through.
+        let data: Vec<f64> = (0..300)
+            .map(|i| if i < 200 { 0.0 } else { 5.0 } + (i as f64 * 0.7).sin())
+            .collect();
+        let plain = calculate_stats(&mut data.clone()).unwrap();
+        let decayed = calculate_weighted_stats(&data, &decay_weights(data.len(), 0.95)).unwrap();
+        assert!((decayed.mean - 5.0).abs() < 0.3, "{}", decayed.mean);
+        assert!((plain.mean - 5.0).abs() > 3.0);
+        assert!(decayed.median > 4.0);
+    }
+
+    #[test]
+    fn uniform_weights_match_unweighted_stats() {
+        let data: Vec<f64> = (0..101).map(|i| (i as f64 * 1.3).cos()).collect();
+        let plain = calculate_stats(&mut data.clone()).unwrap();
+        let weighted = calculate_weighted_stats(&data, &decay_weights(data.len(), 1.0)).unwrap();
+        assert!((plain.mean - weighted.mean).abs() < 1e-12);
+        assert!((plain.stddev - weighted.stddev).abs() < 1e-12);
+        assert_eq!(plain.median, weighted.median);
+        assert!((plain.iqr - weighted.iqr).abs() < 1e-12);
+        assert!((plain.ci_95.low - weighted.ci_95.low).abs() < 1e-12);
+        assert!((plain.ci_95.high - weighted.ci_95.high).abs() < 1e-12);
+    }
+
+    #[test]
+    fn median_policy_on_even_length() {
+        let median = |policy| {
+            let mut data = vec![4.0, 1.0, 3.0, 2.0];
+            calculate_stats_with(&mut data, &SummaryOptions::default().median(policy))
+                .unwrap()
+                .median
+        };
+        assert_eq!(median(MedianPolicy::Average), 2.5);
+        assert_eq!(median(MedianPolicy::Lower), 2.0);
+        assert_eq!(median(MedianPolicy::Higher), 3.0);
+        let mut odd = vec![3.0, 1.0, 2.0];
+        let options = SummaryOptions::default().median(MedianPolicy::Higher);
+        assert_eq!(
+            calculate_stats_with(&mut odd, &options).unwrap().median,
+            2.0
+        );
+    }
+
+    #[test]
+    fn summarise_with_applies_median_policy() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        let est = Estimator::new((0..10).collect(), |ind: &[usize]| {
+            Ok(ind.iter().filter(|&&i| i < 3).count() as f64)
+        });
+        let run = || {
+            Bootstrap::new(est.clone())
+                .n_boot(200)
+                .seed(3)
+                .run()
+                .unwrap()
+        };
+        let options = SummaryOptions::default().median(MedianPolicy::Lower);
+        let lower = run().summarise_with(&options).statistics.unwrap().median;
+        assert_eq!(lower.fract(), 0.0);
+        let average: BootstrapSummary<f64> = run().summarise();
+        assert!(lower <= average.statistics.unwrap().median);
+    }
+
+    #[test]
+    fn bca_improves_coverage_for_skewed_mean() {
+        use crate::bootstrap::Bootstrap;
+        use rand::SeedableRng;
+        use rand::rngs::SmallRng;
+        use rand_distr::{Distribution, LogNormal};
+        let lognormal = LogNormal::new(0.0, 1.0).unwrap();
+        let truth = 0.5f64.exp();
+        let trials = 400;
+        let mut rng = SmallRng::seed_from_u64(31);
+        let (mut percentile, mut bca) = (0, 0);
+        for t in 0..trials {
+            let data: Vec<f64> = (0..20).map(|_| lognormal.sample(&mut rng)).collect();
+            let est = mean_estimator(&data);
+            let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+                .n_boot(500)
+                .seed(t)
+                .bca(true)
+                .run()
+                .unwrap()
+                .summarise();
+            let stats = summary.statistics.unwrap();
+            let covers = |ci: ConfidenceInterval| usize::from(ci.low <= truth && truth <= ci.high);
+            percentile += covers(stats.ci_95);
+            bca += covers(stats.ci_95_bca.unwrap());
+        }
+        assert!(bca > percentile, "percentile {percentile}, bca {bca}");
+    }
+
+    #[test]
+    fn basic_interval_reflects_percentile_about_central() {
+        use crate::bootstrap::Bootstrap;
+        let data = [1.0, 2.0, 3.0, 4.0, 100.0];
+        let est = mean_estimator(&data);
+        let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(1000)
+            .seed(2)
+            .run()
+            .unwrap()
+            .summarise();
+        let central = *summary.central.as_ref().unwrap();
+        let stats = summary.statistics.unwrap();
+        let basic = stats.ci_95_basic.unwrap();
+        assert!((basic.low - (2.0 * central - stats.ci_95.high)).abs() < 1e-12);
+        assert!((basic.high - (2.0 * central - stats.ci_95.low)).abs() < 1e-12);
+        // The replicas are right-skewed, so reflecting moves the interval down.
+        assert!(basic.low < stats.ci_95.low && basic.high < stats.ci_95.high);
+        assert!(
+            calculate_stats(&mut data.to_vec())
+                .unwrap()
+                .ci_95_basic
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn conditional_summary_reports_retained_fraction() {
+        use crate::bootstrap::Bootstrap;
+        let data: Vec<f64> = (0..30).map(|i| f64::from(i) / 10.0 - 1.4).collect();
+        let est = mean_estimator(&data);
+        let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(2000)
+            .seed(9)
+            .run()
+            .unwrap()
+            .summarise();
+        let positive = summary.replicas.iter().filter(|&&r| r > 0.0).count();
+        assert!(positive > 200 && positive < 1800, "{positive}");
+
+        let conditional = summary.conditional_summary(|&r| r > 0.0);
+        assert_eq!(conditional.retained, positive);
+        assert_eq!(conditional.fraction, positive as f64 / 2000.0);
+        let stats = conditional.statistics.unwrap();
+        assert!(stats.min > 0.0);
+        assert!(stats.mean > summary.statistics.as_ref().unwrap().mean);
+
+        let none = summary.conditional_summary(|&r| r > 100.0);
+        assert_eq!(none.fraction, 0.0);
+        assert!(none.statistics.is_none());
+    }
+
+    #[test]
+    fn f32_replicas_summarise_like_f64() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        let data: Vec<f32> = (0..50).map(|i| (i as f32 * 0.7).sin() + 3.0).collect();
+        let wide: Vec<f64> = data.iter().map(|&x| f64::from(x)).collect();
+        let narrow = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            Ok(ind.iter().map(|&i| data[i]).sum::<f32>() / ind.len() as f32)
+        });
+        let double = mean_estimator(&wide);
+        let a: BootstrapSummary<f32> = Bootstrap::new(narrow.clone())
+            .n_boot(500)
+            .seed(1)
+            .bca(true)
+            .run()
+            .unwrap()
+            .summarise();
+        let b: BootstrapSummary<f64> = Bootstrap::new(double)
+            .n_boot(500)
+            .seed(1)
+            .bca(true)
+            .run()
+            .unwrap()
+            .summarise();
+        let (a, b) = (a.statistics.unwrap(), b.statistics.unwrap());
+        assert!((a.mean - b.mean).abs() < 1e-5);
+        assert!((a.stddev / b.stddev - 1.0).abs() < 1e-3);
+        assert!((a.ci_95_bca.unwrap().low - b.ci_95_bca.unwrap().low).abs() < 1e-4);
+
+        // Summarising the f32 column directly matches widening it first.
+        let replicas: Vec<f32> = (0..101).map(|i| (i as f32 * 1.7).cos()).collect();
+        let direct = f32::compute_stats(&replicas).unwrap();
+        let widened = f64::compute_stats(&widen(&replicas)).unwrap();
+        assert_eq!(direct.median, widened.median);
+        assert_eq!(direct.mad, widened.mad);
+        assert_eq!(direct.ci_95.low, widened.ci_95.low);
+        assert_eq!((direct.min, direct.max), (widened.min, widened.max));
+
+        let pair = Estimator::new(narrow.indices().to_vec(), move |ind: &[usize]| {
+            let m = narrow.apply(ind)?;
+            Ok(vec![m, m * m])
+        });
+        let result = Bootstrap::new(pair)
+            .n_boot(200)
+            .seed(1)
+            .bca(true)
+            .run()
+            .unwrap();
+        let wide: BootstrapSummary<Vec<f64>> = result.map(|v| widen(&v)).summarise();
+        let summary: BootstrapSummary<Vec<f32>> = result.summarise();
+        let (stats, wide) = (summary.statistics.unwrap(), wide.statistics.unwrap());
+        assert_eq!(stats.len(), 2);
+        assert!(stats.covariance()[0][1] > 0.0);
+        assert_eq!(stats.covariance(), wide.covariance());
+        for (a, b) in stats.iter().zip(wide.iter()) {
+            assert_eq!(a.mean, b.mean);
+            assert_eq!(a.ci_95_basic.unwrap().low, b.ci_95_basic.unwrap().low);
+            assert_eq!(a.ci_95_bca.unwrap().high, b.ci_95_bca.unwrap().high);
+        }
+    }
+
+    #[test]
+    fn tuple_replicas_summarise_componentwise() {
+        use crate::bootstrap::{Arithmetic, Bootstrap, Estimator};
+        let data: Vec<f64> = (0..40).map(|i| (f64::from(i) * 1.3).cos() + 2.0).collect();
+        let mean = move |ind: &[usize]| mean_of(&data, ind);
+        let (m1, m2) = (mean.clone(), mean.clone());
+        let tuple = Estimator::new((0..40).collect(), move |ind: &[usize]| {
+            let m = m1(ind);
+            Ok((m, (m * m) as f32, -m))
+        });
+        let vector = Estimator::new((0..40).collect(), move |ind: &[usize]| {
+            let m = m2(ind);
+            Ok(vec![m, m * m, -m])
+        });
+        let summary: BootstrapSummary<(f64, f32, f64)> = Bootstrap::new(tuple)
+            .n_boot(300)
+            .seed(4)
+            .bca(true)
+            .run()
+            .unwrap()
+            .summarise();
+        let reference: BootstrapSummary<Vec<f64>> = Bootstrap::new(vector)
+            .n_boot(300)
+            .seed(4)
+            .bca(true)
+            .run()
+            .unwrap()
+            .summarise();
+        let (a, b, c) = summary.statistics.clone().unwrap();
+        let r = reference.statistics.unwrap();
+        assert_eq!(a.mean, r[0].mean);
+        assert_eq!(a.ci_95_bca.unwrap().low, r[0].ci_95_bca.unwrap().low);
+        assert!((b.mean / r[1].mean - 1.0).abs() < 1e-6);
+        assert_eq!(c.ci_95_basic.unwrap().high, r[2].ci_95_basic.unwrap().high);
+
+        let se = <(f64, f32, f64)>::standard_error(&summary.statistics.unwrap());
+        assert_eq!((se.0, se.2), (a.stddev, c.stddev));
+        assert_eq!(se.len(), 3);
+        assert_eq!((1.0, 2.0).add(&(0.5, -1.0)), (1.5, 1.0));
+    }
+
+    #[test]
+    fn keyed_replicas_summarise_per_key() {
+        use crate::bootstrap::{Arithmetic, Bootstrap, Estimator};
+        // Category "rare" appears once, so many resamples lack it.
+        let data: Vec<(&str, f64)> = (0..30)
+            .map(|i| (if i % 2 == 0 { "even" } else { "odd" }, f64::from(i)))
+            .chain([("rare", 100.0)])
+            .collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            let mut sums: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+            for &i in ind {
+                let (k, x) = data[i];
+                let e = sums.entry(k).or_default();
+                *e = (e.0 + x, e.1 + 1.0);
+            }
+            Ok(sums.into_iter().map(|(k, (s, n))| (k, s / n)).collect())
+        });
+        let result = Bootstrap::new(est)
+            .n_boot(400)
+            .seed(6)
+            .bca(true)
+            .run()
+            .unwrap();
+        let summary: BootstrapSummary<BTreeMap<&str, f64>> = result.summarise();
+        let stats = summary.statistics.unwrap();
+        assert_eq!(
+            stats.keys().copied().collect::<Vec<_>>(),
+            ["even", "odd", "rare"]
+        );
+        assert_eq!(stats["even"].n, 400);
+        // Resamples without "rare" count it as zero.
+        assert_eq!(stats["rare"].n, 400);
+        assert_eq!((stats["rare"].min, stats["rare"].max), (0.0, 100.0));
+        assert!((stats["odd"].mean - 15.0).abs() < 0.5);
+        assert!(stats["even"].ci_95_bca.is_some());
+        assert!(stats["rare"].ci_95_bca.is_some());
+
+        let a = BTreeMap::from([("x", 1.0), ("y", 2.0)]);
+        let b = BTreeMap::from([("y", 0.5), ("z", 4.0)]);
+        assert_eq!(
+            a.sub(&b),
+            BTreeMap::from([("x", 1.0), ("y", 1.5), ("z", -4.0)])
+        );
+        let mut sum = BTreeMap::zero(2);
+        sum.add_assign(&a);
+        sum.add_assign(&b);
+        assert_eq!(sum, a.add(&b));
+        assert_eq!(sum.len(), 3);
+    }
+
+    #[test]
+    fn keyed_summary_mean_matches_arithmetic_mean() {
+        use crate::bootstrap::Arithmetic;
+        let replicas = vec![
+            BTreeMap::from([("a", 1.0), ("b", 4.0)]),
+            BTreeMap::from([("a", 3.0)]),
+            BTreeMap::from([("b", 2.0), ("c", 6.0)]),
+        ];
+        let mut sum = BTreeMap::zero(0);
+        replicas.iter().for_each(|r| sum.add_assign(r));
+        let mean = sum.scale(1.0 / replicas.len() as f64);
+        let stats = BTreeMap::compute_stats(&replicas).unwrap();
+        assert_eq!(
+            stats.keys().collect::<Vec<_>>(),
+            mean.keys().collect::<Vec<_>>()
+        );
+        for (k, s) in &stats {
+            assert_eq!(s.n, 3);
+            assert!((s.mean - mean[k]).abs() < 1e-12, "{k}");
+        }
+    }
+
+    #[test]
+    fn summary_reports_bias_of_plug_in_variance() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        // The 1/n variance underestimates, so its bootstrap bias is negative.
+        let data: Vec<f64> = (0..25).map(|i| f64::from(i * i % 11)).collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            let n = ind.len() as f64;
+            let mean = ind.iter().map(|&i| data[i]).sum::<f64>() / n;
+            let var = ind.iter().map(|&i| (data[i] - mean).powi(2)).sum::<f64>() / n;
+            Ok((var, vec![mean, var]))
+        });
+        let summary: BootstrapSummary<(f64, Vec<f64>)> = Bootstrap::new(est)
+            .n_boot(2000)
+            .seed(9)
+            .run()
+            .unwrap()
+            .summarise();
+        let (var_stats, vec_stats) = summary.statistics.as_ref().unwrap();
+        let (central, _) = summary.central.as_ref().unwrap();
+        let (bias, vec_bias) = summary.bias.clone().unwrap();
+        assert_eq!(bias, var_stats.mean - central);
+        assert!(bias < 0.0);
+        assert!(
+            (bias + central / 25.0).abs() < 0.3 * central / 25.0,
+            "{bias} {central}"
+        );
+        assert_eq!(vec_bias[1], bias);
+        assert!(vec_bias[0].abs() < 0.05);
+        assert_eq!(
+            vec_bias[0],
+            vec_stats[0].mean - summary.central.as_ref().unwrap().1[0]
+        );
+
+        let failed: BootstrapSummary<f64> =
+            Bootstrap::new(Estimator::new((0..5).collect(), |ind: &[usize]| {
+                if ind.windows(2).all(|w| w[0] < w[1]) {
+                    Err(EstimatorError::new("identity resample"))
+                } else {
+                    Ok(1.0)
+                }
+            }))
+            .n_boot(50)
+            .seed(1)
+            .run()
+            .unwrap()
+            .summarise();
+        assert!(failed.central.is_err());
+        assert!(failed.bias.is_none());
+    }
+
+    #[test]
+    fn vector_statistics_carry_covariance() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        let data: Vec<f64> = (0..30)
+            .map(|i| f64::from(i % 7) + f64::from(i) / 10.0)
+            .collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            let m = mean_of(&data, ind);
+            Ok(vec![m, 2.0 * m, 5.0])
+        });
+        let summary: BootstrapSummary<Vec<f64>> = Bootstrap::new(est)
+            .n_boot(500)
+            .seed(2)
+            .run()
+            .unwrap()
+            .summarise();
+        let stats = summary.statistics.unwrap();
+        assert_eq!(stats.len(), 3);
+        let cov = stats.covariance();
+        for j in 0..3 {
+            assert!((cov[j][j] - stats[j].stddev.powi(2)).abs() < 1e-9);
+        }
+        assert!((cov[0][1] - 2.0 * cov[0][0]).abs() < 1e-9);
+        assert_eq!(cov[1][0], cov[0][1]);
+        assert_eq!(cov[0][2], 0.0);
+        assert!(
+            Vec::<f64>::compute_stats(&[vec![1.0, 2.0], vec![1.0]]).is_none(),
+            "ragged replicas"
+        );
+    }
+
+    #[test]
+    fn mad_resists_heavy_tail_that_inflates_stddev() {
+        // Median 3, absolute deviations 0, 1, 1, 2, 97: median deviation 1.
+        let stats = calculate_stats(&mut [1.0, 2.0, 3.0, 4.0, 100.0]).unwrap();
+        assert!((stats.mad - 1.4826).abs() < 1e-12);
+        assert!(stats.stddev > 40.0);
+        let weighted = calculate_weighted_stats(&[1.0, 2.0, 3.0, 4.0, 100.0], &[1.0; 5]).unwrap();
+        assert!((weighted.mad - 1.4826).abs() < 1e-12);
+
+        let mut normal = {
+            use rand::SeedableRng;
+            use rand_distr::{Distribution, Normal};
+            let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+            let dist = Normal::new(0.0, 2.0).unwrap();
+            (0..20_000)
+                .map(|_| dist.sample(&mut rng))
+                .collect::<Vec<f64>>()
+        };
+        let stats = calculate_stats(&mut normal).unwrap();
+        assert!((stats.mad / stats.stddev - 1.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn skewness_and_kurtosis_of_known_samples() {
+        // Symmetric two-point sample: no skew, excess kurtosis 1 − 3.
+        let stats = calculate_stats(&mut [-1.0, 1.0, -1.0, 1.0]).unwrap();
+        assert!(stats.skewness.abs() < 1e-12);
+        assert!((stats.excess_kurtosis + 2.0).abs() < 1e-12);
+        // 0, 0, 0, 4: m₂ = 3, m₃ = 6, m₄ = 21.
+        let stats = calculate_stats(&mut [0.0, 0.0, 0.0, 4.0]).unwrap();
+        assert!((stats.skewness - 6.0 / 3f64.powf(1.5)).abs() < 1e-12);
+        assert!((stats.excess_kurtosis - (21.0 / 9.0 - 3.0)).abs() < 1e-12);
+        let weighted = calculate_weighted_stats(&[0.0, 4.0], &[3.0, 1.0]).unwrap();
+        assert!((weighted.skewness - stats.skewness).abs() < 1e-12);
+        assert!(calculate_stats(&mut [2.0; 5]).unwrap().skewness.is_nan());
+    }
+
+    #[test]
+    fn quantiles_interpolate_like_r_type_7() {
+        // R: quantile(1:10, c(0.1, 0.25, 0.975)) gives 1.9, 3.25, 9.775.
+        let data: Vec<f64> = (1..=10).map(f64::from).collect();
+        assert!((quantile_sorted(&data, 0.1) - 1.9).abs() < 1e-12);
+        assert!((quantile_sorted(&data, 0.25) - 3.25).abs() < 1e-12);
+        assert!((quantile_sorted(&data, 0.975) - 9.775).abs() < 1e-12);
+        assert_eq!(quantile_sorted(&data, 0.0), 1.0);
+        assert_eq!(quantile_sorted(&data, 1.0), 10.0);
+
+        // An evenly spaced sample of U(0, 1): every quantile is exact.
+        let mut uniform: Vec<f64> = (0..=400).map(|i| f64::from(i) / 400.0).collect();
+        let stats = calculate_stats(&mut uniform).unwrap();
+        let ci_99 = stats.ci_99;
+        assert!((ci_99.low - (1.0 - THREE_SIGMA) / 2.0).abs() < 1e-12);
+        assert!((ci_99.high - (1.0 + THREE_SIGMA) / 2.0).abs() < 1e-12);
+        assert!((stats.iqr - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn normal_quantile_matches_known_values() {
+        assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-6);
+        assert!((normal_quantile(0.5)).abs() < 1e-12);
+        assert!((normal_quantile(0.001) + 3.090_232).abs() < 1e-6);
+    }
+
+    #[test]
+    fn normal_cdf_inverts_quantile() {
+        for p in [0.001, 0.025, 0.3, 0.5, 0.8, 0.975, 0.999] {
+            assert!((normal_cdf(normal_quantile(p)) - p).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn prediction_interval_wider_than_confidence_interval() {
+        use crate::bootstrap::Bootstrap;
+        let data: Vec<f64> = (0..50)
+            .map(|i| (i as f64 * 0.37).sin() * 3.0 + 10.0)
+            .collect();
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let var = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let est = mean_estimator(&data);
+        let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(1000)
+            .seed(4)
+            .run()
+            .unwrap()
+            .summarise();
+        let pi = summary.prediction_interval(var, 1, 0.95).unwrap();
+        let ci = summary.statistics.as_ref().unwrap().ci_95;
+        assert!(pi.low < ci.low && pi.high > ci.high);
+    }
+
+    #[test]
+    fn histogram_bins_replicas_between_extremes() {
+        let summary = |replicas: Vec<f64>| BootstrapSummary {
+            label: None,
+            n_boot: replicas.len(),
+            sampler: SamplingStrategy::Iid,
+            seed: None,
+            truncated: 0,
+            central: Ok(0.0),
+            statistics: None,
+            replicas,
+            failures: Vec::new(),
+            warnings: Vec::new(),
+            bias: None,
+        };
+        let h = summary(vec![0.0, 0.1, 0.4, 0.6, 1.0, f64::NAN]).histogram(4);
+        assert_eq!(h, vec![(0.125, 2), (0.375, 1), (0.625, 1), (0.875, 1)]);
+        assert_eq!(summary(vec![2.0; 5]).histogram(10), vec![(2.0, 5)]);
+        assert!(summary(Vec::new()).histogram(3).is_empty());
+
+        let spread: Vec<f64> = (0..1000)
+            .map(|i| (i as f64 / 1000.0 - 0.5).powi(3))
+            .collect();
+        let bins = freedman_diaconis_bins(&spread);
+        let auto = summary(spread).histogram_auto();
+        assert_eq!(auto.len(), bins);
+        assert!(bins > 10 && bins < 200);
+        assert_eq!(auto.iter().map(|b| b.1).sum::<usize>(), 1000);
+        assert_eq!(freedman_diaconis_bins(&[1.0, 1.0, 1.0, 1.0, 9.0]), 4);
+
+        let mut outlier: Vec<f64> = (0..999).map(|i| i as f64 / 999.0).collect();
+        outlier.push(1e15);
+        assert_eq!(freedman_diaconis_bins(&outlier), 1000);
+        assert_eq!(summary(outlier).histogram_auto().len(), 1000);
+    }
+
+    #[test]
+    fn quantile_matches_interval_endpoints() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        let data: Vec<f64> = (0..30).map(|i| (i as f64 * 0.7).sin()).collect();
+        let est = Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+            let mean = mean_of(&data, ind);
+            Ok(vec![mean, 3.0 - mean])
+        });
+        let summary: BootstrapSummary<Vec<f64>> = Bootstrap::new(est)
+            .n_boot(999)
+            .seed(2)
+            .run()
+            .unwrap()
+            .summarise();
+        let stats = summary.statistics.as_ref().unwrap();
+        let q = (1.0 - TWO_SIGMA) / 2.0;
+        let low = summary.quantile(q).unwrap();
+        assert_eq!(low[0], stats[0].ci_95.low);
+        assert_eq!(low[1], stats[1].ci_95.low);
+        assert_eq!(summary.quantile(0.5).unwrap()[0], stats[0].median);
+        assert!(summary.quantile(1.5).is_none());
+
+        let first: Vec<f64> = summary.replicas.iter().map(|r| r[0]).collect();
+        let scalar = BootstrapSummary {
+            label: None,
+            n_boot: 999,
+            sampler: SamplingStrategy::Iid,
+            seed: None,
+            truncated: 0,
+            central: Ok(0.0),
+            statistics: None,
+            replicas: first,
+            failures: Vec::new(),
+            warnings: Vec::new(),
+            bias: None,
+        };
+        assert_eq!(scalar.quantile(q), Some(stats[0].ci_95.low));
+        assert_eq!(scalar.quantile(1.0), Some(stats[0].max));
+        assert_eq!(scalar.quantile(f64::NAN), None);
+        assert_eq!(scalar.quantile(-0.1), None);
+    }
+
+    #[test]
+    fn standardized_residuals_have_unit_scale() {
+        use crate::bootstrap::Bootstrap;
+        let data: Vec<f64> = (0..30).map(|i| (i as f64).powf(1.5)).collect();
+        let est = mean_estimator(&data);
+        let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(500)
+            .seed(6)
+            .run()
+            .unwrap()
+            .summarise();
+        let z = summary.standardized_residuals().unwrap();
+        assert_eq!(z.len(), 500);
+        let n = z.len() as f64;
+        let mean = z.iter().sum::<f64>() / n;
+        let var = z.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        assert!(mean.abs() < 1e-9);
+        assert!((var - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn report_flattens_scalar_summary() {
+        use crate::bootstrap::Bootstrap;
+        let data: Vec<f64> = (0..40).map(|i| i as f64).collect();
+        let est = mean_estimator(&data);
+        let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+            .n_boot(200)
+            .seed(2)
+            .run()
+            .unwrap()
+            .summarise();
+        let central = *summary.central.as_ref().unwrap();
+        let stats = summary.statistics.clone().unwrap();
+        let report = Report::from(summary);
+        assert_eq!(report.estimate, Some(central));
+        assert_eq!(report.std_error, Some(stats.stddev));
+        assert_eq!(report.ci_95_low, Some(stats.ci_95.low));
+        assert_eq!(report.ci_95_high, Some(stats.ci_95.high));
+    }
+
+    #[test]
+    fn combine_components_lines_up_statistics() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.3).cos()).collect();
+        let component = |power: i32| {
+            let data = data.clone();
+            let est = Estimator::new((0..data.len()).collect(), move |ind| {
+                Ok(ind.iter().map(|&i| data[i].powi(power)).sum::<f64>() / ind.len() as f64)
+            });
+            let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+                .n_boot(300)
+                .seed(9)
+                .run()
+                .unwrap()
+                .summarise();
+            summary
+        };
+        let parts = vec![component(1), component(2)];
+        let expected: Vec<(f64, f64)> = parts
+            .iter()
+            .map(|p| {
+                (
+                    *p.central.as_ref().unwrap(),
+                    p.statistics.as_ref().unwrap().mean,
+                )
+            })
+            .collect();
+        let combined = BootstrapSummary::combine_components(parts).unwrap();
+        let central = combined.central.as_ref().unwrap();
+        let stats = combined.statistics.as_ref().unwrap();
+        assert_eq!(combined.replicas.len(), 300);
+        assert!(combined.replicas.iter().all(|r| r.len() == 2));
+        for (j, (c, m)) in expected.into_iter().enumerate() {
+            assert_eq!(central[j], c);
+            assert_eq!(stats[j].mean, m);
+        }
+    }
+
+    #[test]
+    fn simultaneous_intervals_wider_than_marginal() {
+        use crate::bootstrap::{Bootstrap, Estimator};
+        let rows: Vec<[f64; 5]> = (0..60)
+            .map(|i| {
+                let x = i as f64;
+                [
+                    x.sin(),
+                    x.cos(),
+                    (0.3 * x).sin(),
+                    (0.7 * x).cos(),
+                    (1.3 * x).sin(),
+                ]
+            })
+            .collect();
+        let est = Estimator::new((0..rows.len()).collect(), move |ind| {
+            let mut m = vec![0.0; 5];
+            for &i in ind {
+                for (acc, v) in m.iter_mut().zip(rows[i]) {
+                    *acc += v;
+                }
+            }
+            Ok(m.into_iter()
+                .map(|v| v / ind.len() as f64)
+                .collect::<Vec<f64>>())
+        });
+        let summary: BootstrapSummary<Vec<f64>> = Bootstrap::new(est)
+            .n_boot(2000)
+            .seed(3)
+            .run()
+            .unwrap()
+            .summarise();
+        let simultaneous = summary.simultaneous_ci(0.95).unwrap();
+        let marginal = summary.statistics.as_ref().unwrap();
+        assert_eq!(simultaneous.len(), 5);
+        for (sim, stats) in simultaneous.iter().zip(marginal) {
+            assert!(sim.high - sim.low > stats.ci_95.high - stats.ci_95.low);
+        }
+    }
 }
@@ -0,0 +1,170 @@
+//! Standard errors from externally supplied survey replicate weights.
+
+use crate::bootstrap::EstimatorResult;
+use crate::error::BootstrapError;
+use rayon::prelude::*;
+use serde::Serialize;
+
+/// Replicate-weight variance estimation, as produced by survey software
+/// (Stata's `svy`, R's `survey`): the statistic is computed once with the
+/// full-sample weights and once with each set of replicate weights, and
+/// the variance is `scale · Σ (θᵣ − θ̂)²`.
+///
+/// `scale` depends on how the replicates were built: `1/R` for bootstrap
+/// replicate weights (the default), `(n − 1)/n` for delete-one jackknife
+/// (JK1) weights, `1/(R(1 − ρ)²)` for Fay's BRR with factor `ρ`. Use the
+/// value documented by whoever produced the weights.
+pub struct ReplicateWeights {
+    full: Vec<f64>,
+    replicates: Vec<Vec<f64>>,
+    scale: Option<f64>,
+}
+
+impl ReplicateWeights {
+    /// `full` holds the full-sample weight of each observation and every
+    /// entry of `replicates` one replicate's weights, in the same order.
+    pub fn new(full: Vec<f64>, replicates: Vec<Vec<f64>>) -> Self {
+        Self {
+            full,
+            replicates,
+            scale: None,
+        }
+    }
+
+    /// Variance multiplier; see the type documentation.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Apply `estimator(indices, weights)` with the full-sample weights and
+    /// with each replicate's. `indices` is always every observation; a zero
+    /// weight drops one. The closure has the shape used by
+    /// [`Estimator::from_weights`](crate::Estimator::from_weights), so the
+    /// same statistic can be bootstrapped or replicate-weighted. Fails if
+    /// the full-sample estimate fails, a replicate has the wrong length
+    /// ([`BootstrapError::InvalidInput`]) or every replicate fails
+    /// ([`BootstrapError::NoValidSamples`]).
+    pub fn run<F>(self, estimator: F) -> Result<ReplicateResult, BootstrapError>
+    where
+        F: Fn(&[usize], &[f64]) -> EstimatorResult<f64> + Sync,
+    {
+        let ReplicateWeights {
+            full,
+            replicates,
+            scale,
+        } = self;
+        let n = full.len();
+        if n == 0 {
+            return Err(BootstrapError::EmptyIndices);
+        }
+        if let Some((r, w)) = replicates.iter().enumerate().find(|(_, w)| w.len() != n) {
+            return Err(BootstrapError::InvalidInput(format!(
+                "replicate {r} has {} weights for {n} observations",
+                w.len()
+            )));
+        }
+        let indices: Vec<usize> = (0..n).collect();
+        let estimate = estimator(&indices, &full).map_err(BootstrapError::CentralFailed)?;
+        let values: Vec<Option<f64>> = replicates
+            .par_iter()
+            .map(|w| estimator(&indices, w).ok())
+            .collect();
+        let n_replicates = values.len();
+        let values: Vec<f64> = values.into_iter().flatten().collect();
+        if values.is_empty() {
+            return Err(BootstrapError::NoValidSamples {
+                n_boot: n_replicates,
+                failed_samples: n_replicates,
+            });
+        }
+        let scale = scale.unwrap_or(1.0 / values.len() as f64);
+        let sum_sq: f64 = values.iter().map(|v| (v - estimate).powi(2)).sum();
+        Ok(ReplicateResult {
+            estimate,
+            std_error: (scale * sum_sq).sqrt(),
+            failed: n_replicates - values.len(),
+            replicates: values,
+        })
+    }
+}
+
+/// Output of [`ReplicateWeights::run`].
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub struct ReplicateResult {
+    /// Estimate with the full-sample weights.
+    pub estimate: f64,
+    /// `√(scale · Σ (θᵣ − θ̂)²)` over the successful replicates.
+    pub std_error: f64,
+    /// Replicates whose estimate failed; they are left out of the sum.
+    pub failed: usize,
+    pub replicates: Vec<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jackknife_replicate_weights_reproduce_reported_se() {
+        // JK1 replicate weights for 6 equally weighted observations, as a
+        // survey package would export them: replicate r drops observation r
+        // and reweights the rest by n/(n − 1).
+        let y = [3.1, 4.7, 2.2, 5.9, 4.4, 3.8];
+        let n = y.len();
+        let full = vec![1.0; n];
+        let replicates: Vec<Vec<f64>> = (0..n)
+            .map(|r| {
+                (0..n)
+                    .map(|i| {
+                        if i == r {
+                            0.0
+                        } else {
+                            n as f64 / (n as f64 - 1.0)
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let weighted_mean = |ind: &[usize], w: &[f64]| {
+            let total: f64 = ind.iter().map(|&i| w[i]).sum();
+            Ok(ind.iter().map(|&i| w[i] * y[i]).sum::<f64>() / total)
+        };
+        let result = ReplicateWeights::new(full, replicates)
+            .scale((n as f64 - 1.0) / n as f64)
+            .run(weighted_mean)
+            .unwrap();
+        // For the mean, JK1 gives exactly the textbook s/√n.
+        let mean = y.iter().sum::<f64>() / n as f64;
+        let s = (y.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n as f64 - 1.0)).sqrt();
+        assert!((result.estimate - mean).abs() < 1e-12);
+        assert!((result.std_error - s / (n as f64).sqrt()).abs() < 1e-12);
+        assert_eq!(result.failed, 0);
+    }
+
+    #[test]
+    fn bad_replicates_are_errors() {
+        let total = |_: &[usize], w: &[f64]| Ok(w.iter().sum::<f64>());
+        let short = ReplicateWeights::new(vec![1.0; 3], vec![vec![1.0; 3], vec![1.0; 2]]);
+        assert!(matches!(
+            short.run(total),
+            Err(BootstrapError::InvalidInput(_))
+        ));
+        let failing = |_: &[usize], w: &[f64]| {
+            if w[0] == 1.0 {
+                Ok(1.0)
+            } else {
+                Err(crate::EstimatorError::new("replicate"))
+            }
+        };
+        let all_failed = ReplicateWeights::new(vec![1.0; 3], vec![vec![0.0; 3]; 4]);
+        assert!(matches!(
+            all_failed.run(failing),
+            Err(BootstrapError::NoValidSamples {
+                n_boot: 4,
+                failed_samples: 4
+            })
+        ));
+    }
+}
@@ -0,0 +1,216 @@
+//! Merging t-digest (Dunning & Ertl) for approximate quantiles of a stream
+//! in bounded memory. Enable the `tdigest` feature to use.
+
+/// A cluster of nearby values, summarised by their mean and count.
+#[derive(Debug, Clone, Copy)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Approximate quantile sketch. Values are buffered and periodically merged
+/// into at most about `compression` centroids, each allowed to grow only
+/// as far as the `k₁(q) = δ/2π · asin(2q − 1)` scale function permits:
+/// centroids near the median absorb many values, those near the tails
+/// very few, so extreme quantiles stay accurate. Quantile error in
+/// probability is roughly `q(1 − q)/δ`-proportional; memory is `O(δ)`
+/// whatever the number of values inserted. Digests merge, so per-thread
+/// digests can be combined.
+#[derive(Debug, Clone)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    count: f64,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    /// Empty digest with compression `δ`; 100–500 is typical.
+    ///
+    /// # Panics
+    ///
+    /// If `compression` is not finite or below 10.
+    pub fn new(compression: f64) -> Self {
+        assert!(
+            compression.is_finite() && compression >= 10.0,
+            "compression must be finite and at least 10"
+        );
+        Self {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Number of values inserted (including through merges).
+    pub fn len(&self) -> usize {
+        self.count as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0.0
+    }
+
+    /// Add one value. NaN is ignored.
+    pub fn insert(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        self.buffer.push(x);
+        self.count += 1.0;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        if self.buffer.len() as f64 >= 5.0 * self.compression {
+            self.compress();
+        }
+    }
+
+    /// Fold `other` into `self`.
+    pub fn merge(&mut self, other: TDigest) {
+        self.centroids.extend(other.centroids);
+        self.centroids.extend(
+            other
+                .buffer
+                .into_iter()
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+
+    fn scale(&self, q: f64) -> f64 {
+        self.compression / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).asin()
+    }
+
+    fn inverse_scale(&self, k: f64) -> f64 {
+        ((k * 2.0 * std::f64::consts::PI / self.compression).sin() + 1.0) / 2.0
+    }
+
+    fn compress(&mut self) {
+        let mut all = std::mem::take(&mut self.centroids);
+        all.extend(
+            self.buffer
+                .drain(..)
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        if all.is_empty() {
+            return;
+        }
+        all.sort_unstable_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+
+        let mut merged = Vec::with_capacity(self.compression as usize);
+        let mut before = 0.0;
+        let mut current = all[0];
+        let mut limit = self.inverse_scale(self.scale(0.0) + 1.0);
+        for &next in &all[1..] {
+            if (before + current.weight + next.weight) / total <= limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                current = next;
+                limit = self.inverse_scale(self.scale(before / total) + 1.0);
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Approximate `q`-quantile, interpolating linearly between centroid
+    /// centres and out to the exact minimum and maximum. `None` when
+    /// empty.
+    pub fn quantile(&mut self, q: f64) -> Option<f64> {
+        if !self.buffer.is_empty() {
+            self.compress();
+        }
+        let centroids = &self.centroids;
+        if centroids.is_empty() {
+            return None;
+        }
+        let target = q.clamp(0.0, 1.0) * self.count;
+        let mut left = (0.0, self.min);
+        let mut before = 0.0;
+        for c in centroids {
+            let centre = (before + c.weight / 2.0, c.mean);
+            if target <= centre.0 {
+                return Some(interpolate(left, centre, target));
+            }
+            left = centre;
+            before += c.weight;
+        }
+        Some(interpolate(left, (self.count, self.max), target))
+    }
+
+    /// `points` quantiles at `q = j/(points − 1)`, ascending: a stand-in
+    /// sample whose type-7 quantiles follow the digest's.
+    pub(crate) fn quantile_grid(&mut self, points: usize) -> Vec<f64> {
+        let last = (points - 1) as f64;
+        let mut grid: Vec<f64> = (0..points)
+            .filter_map(|j| self.quantile(j as f64 / last))
+            .collect();
+        grid.sort_unstable_by(f64::total_cmp);
+        grid
+    }
+}
+
+fn interpolate((x0, y0): (f64, f64), (x1, y1): (f64, f64), x: f64) -> f64 {
+    if x1 <= x0 {
+        y1
+    } else {
+        y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantiles_of_uniform_stream() {
+        let n = 200_000;
+        let mut digest = TDigest::new(200.0);
+        // A permutation of 0..n, so the stream is not sorted.
+        (0..n).for_each(|i| digest.insert(((i * 7919) % n) as f64));
+        assert_eq!(digest.len(), n);
+        assert!(digest.centroids.len() < 400);
+        for q in [0.001, 0.025, 0.25, 0.5, 0.75, 0.975, 0.999] {
+            let estimate = digest.quantile(q).unwrap() / n as f64;
+            assert!((estimate - q).abs() < 2e-3, "q = {q}: {estimate}");
+        }
+        assert_eq!(digest.quantile(0.0), Some(0.0));
+        assert_eq!(digest.quantile(1.0), Some((n - 1) as f64));
+    }
+
+    #[test]
+    fn merged_digests_agree_with_one() {
+        let values: Vec<f64> = (0..50_000).map(|i| ((i as f64) * 0.37).sin()).collect();
+        let mut whole = TDigest::new(100.0);
+        values.iter().for_each(|&x| whole.insert(x));
+        let mut parts: Vec<TDigest> = values
+            .chunks(7000)
+            .map(|chunk| {
+                let mut d = TDigest::new(100.0);
+                chunk.iter().for_each(|&x| d.insert(x));
+                d
+            })
+            .collect();
+        let mut merged = parts.pop().unwrap();
+        parts.into_iter().for_each(|d| merged.merge(d));
+        assert_eq!(merged.len(), whole.len());
+        for q in [0.01, 0.5, 0.99] {
+            let (a, b) = (whole.quantile(q).unwrap(), merged.quantile(q).unwrap());
+            assert!((a - b).abs() < 0.01, "q = {q}: {a} vs {b}");
+        }
+        assert!(TDigest::new(50.0).quantile(0.5).is_none());
+    }
+}
@@ -0,0 +1,14 @@
+use crate::Estimator;
+
+/// Mean of `data` over the resampled indices `ind`.
+pub(crate) fn mean_of(data: &[f64], ind: &[usize]) -> f64 {
+    ind.iter().map(|&i| data[i]).sum::<f64>() / ind.len() as f64
+}
+
+/// The sample mean of `data`, the estimator most tests bootstrap.
+pub(crate) fn mean_estimator(data: &[f64]) -> Estimator<f64> {
+    let data = data.to_vec();
+    Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+        Ok(mean_of(&data, ind))
+    })
+}
@@ -0,0 +1,240 @@
+//! Wild bootstrap for regression with heteroskedastic errors.
+
+use crate::bootstrap::{Bootstrap, BootstrapResult, EstimatorError, EstimatorResult, Progress};
+use crate::error::BootstrapError;
+use crate::samplers::{Sampler, SamplingStrategy, WildDist};
+use std::sync::Arc;
+
+/// Wild bootstrap of a regression fit.
+///
+/// Given the fitted values `ŷ` and residuals `e` of the original fit, each
+/// replica builds `y* = ŷ + e·v` with iid multipliers `v` from
+/// [`WildDist`] and hands `y*` to the estimator, which refits on the
+/// original design. Each observation keeps its own residual scale, so the
+/// spread of the replicas reflects heteroskedasticity that a residual or
+/// pairs bootstrap would average away. The central value is the estimator
+/// on `ŷ + e`, the observed response.
+///
+/// The result is an ordinary [`BootstrapResult`] whose `sampler` is
+/// [`SamplingStrategy::Wild`], so it summarises like any other run. The
+/// replicas go through the same loop as [`Bootstrap::run`], so the options
+/// forwarded here and the `n_boot` warning behave as they do there.
+pub struct WildBootstrap<T, F> {
+    fitted: Vec<f64>,
+    residuals: Vec<f64>,
+    estimator: F,
+    distribution: WildDist,
+    bootstrap: Bootstrap<T>,
+}
+
+impl<T, F> WildBootstrap<T, F>
+where
+    T: Clone + Send + Sync + 'static,
+    F: Fn(&[f64]) -> EstimatorResult<T> + Sync,
+{
+    /// # Panics
+    ///
+    /// If `fitted` and `residuals` differ in length.
+    pub fn new(fitted: Vec<f64>, residuals: Vec<f64>, estimator: F) -> Self {
+        assert_eq!(
+            fitted.len(),
+            residuals.len(),
+            "one residual per fitted value"
+        );
+        Self {
+            fitted,
+            residuals,
+            estimator,
+            distribution: WildDist::default(),
+            bootstrap: Bootstrap::for_runner(),
+        }
+    }
+
+    pub fn distribution(mut self, distribution: WildDist) -> Self {
+        self.distribution = distribution;
+        self
+    }
+    pub fn n_boot(mut self, n: usize) -> Self {
+        self.bootstrap = self.bootstrap.n_boot(n);
+        self
+    }
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.bootstrap = self.bootstrap.seed(seed);
+        self
+    }
+    /// As [`Bootstrap::threads`].
+    pub fn threads(mut self, n: usize) -> Self {
+        self.bootstrap = self.bootstrap.threads(n);
+        self
+    }
+    /// As [`Bootstrap::parallel`].
+    pub fn parallel(mut self, on: bool) -> Self {
+        self.bootstrap = self.bootstrap.parallel(on);
+        self
+    }
+    /// As [`Bootstrap::progress`].
+    pub fn progress(mut self, p: Arc<dyn Progress>) -> Self {
+        self.bootstrap = self.bootstrap.progress(p);
+        self
+    }
+    /// As [`Bootstrap::label`].
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.bootstrap = self.bootstrap.label(label);
+        self
+    }
+    /// As [`Bootstrap::validate`].
+    pub fn validate<V>(mut self, f: V) -> Self
+    where
+        V: Fn(&T) -> EstimatorResult<()> + Send + Sync + 'static,
+    {
+        self.bootstrap = self.bootstrap.validate(f);
+        self
+    }
+    /// As [`Bootstrap::min_valid_fraction`].
+    ///
+    /// # Panics
+    ///
+    /// If `fraction` is not in `[0, 1]`.
+    pub fn min_valid_fraction(mut self, fraction: f64) -> Self {
+        self.bootstrap = self.bootstrap.min_valid_fraction(fraction);
+        self
+    }
+
+    pub fn run(self) -> Result<BootstrapResult<T>, BootstrapError> {
+        let WildBootstrap {
+            fitted,
+            residuals,
+            estimator,
+            distribution,
+            bootstrap,
+        } = self;
+        if fitted.is_empty() {
+            return Err(BootstrapError::EmptyIndices);
+        }
+        let sampler = SamplingStrategy::Wild { distribution };
+        let observed: Vec<f64> = fitted.iter().zip(&residuals).map(|(f, e)| f + e).collect();
+        let central = estimator(&observed);
+
+        let bootstrap = bootstrap.sampler(sampler.clone());
+        Ok(bootstrap.run_with(central, |_, rng| {
+            let multipliers = sampler
+                .weights(fitted.len(), rng)
+                .ok_or_else(|| EstimatorError::new("wild sampler drew no multipliers"))?;
+            let y_star: Vec<f64> = fitted
+                .iter()
+                .zip(&residuals)
+                .zip(&multipliers)
+                .map(|((f, e), v)| f + e * v)
+                .collect();
+            estimator(&y_star)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::{BootstrapSummary, Summarisable};
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+    use rand_distr::{Distribution, Normal};
+
+    fn ols(x: &[f64], y: &[f64]) -> (f64, f64) {
+        let n = x.len() as f64;
+        let (mx, my) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+        let sxx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+        let sxy: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+        let slope = sxy / sxx;
+        (my - slope * mx, slope)
+    }
+
+    #[test]
+    fn wild_slope_se_matches_heteroskedasticity_robust_se() {
+        // Noise standard deviation grows with x.
+        let mut rng = SmallRng::seed_from_u64(13);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let x: Vec<f64> = (0..200).map(|i| f64::from(i) / 20.0).collect();
+        let y: Vec<f64> = x
+            .iter()
+            .map(|&a| 1.0 + 2.0 * a + 0.2 * a * normal.sample(&mut rng))
+            .collect();
+        let (a, b) = ols(&x, &y);
+        let fitted: Vec<f64> = x.iter().map(|xi| a + b * xi).collect();
+        let residuals: Vec<f64> = y.iter().zip(&fitted).map(|(y, f)| y - f).collect();
+
+        // White's HC0 standard error of the slope.
+        let mx = x.iter().sum::<f64>() / x.len() as f64;
+        let sxx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+        let hc0 = x
+            .iter()
+            .zip(&residuals)
+            .map(|(a, e)| ((a - mx) * e).powi(2))
+            .sum::<f64>()
+            .sqrt()
+            / sxx;
+
+        for distribution in [WildDist::Rademacher, WildDist::Mammen] {
+            let design = x.clone();
+            let summary: BootstrapSummary<f64> = WildBootstrap::new(
+                fitted.clone(),
+                residuals.clone(),
+                move |y_star: &[f64]| Ok(ols(&design, y_star).1),
+            )
+            .distribution(distribution)
+            .n_boot(2000)
+            .seed(3)
+            .run()
+            .unwrap()
+            .summarise();
+            assert!((summary.central.as_ref().unwrap() - b).abs() < 1e-9);
+            let se = summary.statistics.unwrap().stddev;
+            assert!(
+                (se / hc0 - 1.0).abs() < 0.1,
+                "{distribution:?}: {se} vs {hc0}"
+            );
+        }
+    }
+
+    #[test]
+    fn resampled_residuals_keep_their_second_moment() {
+        let residuals: Vec<f64> = (0..50).map(|i| (f64::from(i) - 24.5) / 5.0).collect();
+        let sum_sq: f64 = residuals.iter().map(|e| e * e).sum();
+        let result = WildBootstrap::new(vec![0.0; 50], residuals, |y_star: &[f64]| {
+            Ok(y_star.iter().map(|e| e * e).sum::<f64>())
+        })
+        .distribution(WildDist::Mammen)
+        .n_boot(4000)
+        .seed(8)
+        .run()
+        .unwrap();
+        let mean = result.samples.iter().sum::<f64>() / result.samples.len() as f64;
+        assert!((mean / sum_sq - 1.0).abs() < 0.02, "{mean} vs {sum_sq}");
+    }
+
+    #[test]
+    fn wild_runs_apply_warnings_and_the_valid_fraction() {
+        let residuals: Vec<f64> = (0..20).map(|i| f64::from(i) - 9.5).collect();
+        let sum = |y_star: &[f64]| Ok(y_star.iter().sum::<f64>());
+        let result = WildBootstrap::new(vec![1.0; 20], residuals.clone(), sum)
+            .n_boot(50)
+            .seed(2)
+            .validate(|s: &f64| {
+                (*s > 20.0)
+                    .then_some(())
+                    .ok_or_else(|| EstimatorError::new("small sum"))
+            })
+            .min_valid_fraction(0.9)
+            .run()
+            .unwrap();
+        assert!(matches!(
+            result.warnings.as_slice(),
+            [crate::Warning::LowNBoot { n_boot: 50, .. }]
+        ));
+        assert!(result.samples.iter().all(|&s| s > 20.0));
+        assert_eq!(result.samples.len() + result.failed(), 50);
+        assert!(matches!(
+            result.try_summarise(),
+            Err(BootstrapError::TooFewValidSamples { n_boot: 50, .. })
+        ));
+    }
+}
@@ -1,9 +1,23 @@
+// The original tests predate these lints; keep them as written.
+#![allow(clippy::useless_vec, clippy::manual_is_multiple_of)]
+
 use booted::{
-    Bootstrap, BootstrapSummary, Estimator, EstimatorError, SamplingStrategy, Summarisable,
+    Bootstrap, BootstrapError, BootstrapSummary, Estimator, EstimatorError, SamplingStrategy,
+    Summarisable,
 };
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
 use rand_distr::{Distribution, Normal};
 use serde_json::Value;
 
+/// The sample mean of `data`, the estimator most tests bootstrap.
+fn mean_estimator(data: &[f64]) -> Estimator<f64> {
+    let data = data.to_vec();
+    Estimator::new((0..data.len()).collect(), move |ind: &[usize]| {
+        Ok(ind.iter().map(|&i| data[i]).sum::<f64>() / ind.len() as f64)
+    })
+}
+
 /// Downstream tooling (analysis pipelines, reports) reads `central_val` as
 /// a bare scalar and `failed_samples` as a count. Assert that both keys
 /// still exist in the emitted JSON, even after the 0.6 refactor that
@@ -12,9 +26,7 @@ use serde_json::Value;
 #[test]
 fn legacy_json_shape_preserved() {
     let data: Vec<f64> = (1..=10).map(|x| x as f64).collect();
-    let est = Estimator::new((0..data.len()).collect(), move |ind| {
-        Ok(ind.iter().map(|&i| data[i]).sum::<f64>() / ind.len() as f64)
-    });
+    let est = mean_estimator(&data);
     let summary: BootstrapSummary<f64> = Bootstrap::new(est)
         .n_boot(50)
         .seed(1)
@@ -31,11 +43,88 @@ fn legacy_json_shape_preserved() {
     // New diagnostic fields are also present
     assert!(v.get("failure_reasons").unwrap().is_array());
     assert!(v.get("truncated").is_some());
+    assert!(v.get("warnings").unwrap().is_array());
+}
+
+#[test]
+fn label_round_trips_through_json() {
+    let est = Estimator::new((0..10).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+    let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+        .n_boot(20)
+        .seed(1)
+        .label("run-42")
+        .run()
+        .unwrap()
+        .summarise();
+    let v: Value = serde_json::from_str(&serde_json::to_string(&summary).unwrap()).unwrap();
+    assert_eq!(v["label"], "run-42");
+}
+
+#[test]
+fn summary_round_trips_through_json() {
+    let data: Vec<f64> = (0..30).map(|x| (x as f64).sqrt()).collect();
+    let est = Estimator::new((0..data.len()).collect(), move |ind| {
+        if ind[0] == 0 {
+            return Err(EstimatorError::new("starts at zero"));
+        }
+        Ok(ind.iter().map(|&i| data[i]).sum::<f64>() / ind.len() as f64)
+    });
+    let result = Bootstrap::new(est)
+        .n_boot(200)
+        .seed(5)
+        .label("reload")
+        .run()
+        .unwrap();
+    let json = serde_json::to_string(&result).unwrap();
+    let reloaded: booted::BootstrapResult<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(reloaded.samples, result.samples);
+    assert_eq!(reloaded.failures, result.failures);
+
+    let summary: BootstrapSummary<f64> = result.summarise();
+    let json = serde_json::to_string(&summary).unwrap();
+    let back: BootstrapSummary<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.label.as_deref(), Some("reload"));
+    assert_eq!(back.seed, Some(5));
+    assert_eq!(back.central.as_ref().ok(), summary.central.as_ref().ok());
+    assert_eq!(back.replicas, summary.replicas);
+    assert_eq!(back.failures, summary.failures);
+    let (a, b) = (
+        back.statistics.as_ref().unwrap(),
+        summary.statistics.as_ref().unwrap(),
+    );
+    assert_eq!(
+        (a.mean, a.stddev, a.skewness),
+        (b.mean, b.stddev, b.skewness)
+    );
+    assert_eq!((a.ci_95.low, a.ci_95.high), (b.ci_95.low, b.ci_95.high));
+    assert_eq!(serde_json::to_string(&back).unwrap(), json);
+}
+
+#[test]
+fn stats_only_view_omits_replicas() {
+    let data: Vec<f64> = (0..100).map(|x| x as f64).collect();
+    let est = mean_estimator(&data);
+    let summary: BootstrapSummary<f64> = Bootstrap::new(est)
+        .n_boot(500)
+        .seed(1)
+        .run()
+        .unwrap()
+        .summarise();
+    let full = serde_json::to_string(&summary).unwrap();
+    let compact = serde_json::to_string(&summary.stats_only()).unwrap();
+    let v: Value = serde_json::from_str(&compact).unwrap();
+    assert!(v.get("replicas").is_none());
+    assert_eq!(v["n_boot"], 500);
+    assert!(v["central_val"].is_number());
+    assert_eq!(v["failed_samples"], 0);
+    assert!(v["statistics"].is_object());
+    let replicas = serde_json::to_string(&summary.replicas).unwrap();
+    assert!(compact.len() + replicas.len() <= full.len());
 }
 
-fn generate_data(n: usize, mean: f64, std_dev: f64) -> Vec<f64> {
+fn generate_data(n: usize, mean: f64, std_dev: f64, seed: u64) -> Vec<f64> {
     let normal = Normal::new(mean, std_dev).unwrap();
-    let mut rng = rand::rng();
+    let mut rng = SmallRng::seed_from_u64(seed);
     (0..n).map(|_| normal.sample(&mut rng)).collect()
 }
 
@@ -44,7 +133,7 @@ fn scalar_bootstrap_mean() {
     let true_mean = 10.0;
     let true_std_dev = 2.0;
     let n_samples = 1000;
-    let data = generate_data(n_samples, true_mean, true_std_dev);
+    let data = generate_data(n_samples, true_mean, true_std_dev, 7);
 
     let estimator = Estimator::new((0..n_samples).collect(), move |indices: &[usize]| {
         let sum: f64 = indices.iter().map(|&i| data[i]).sum();
@@ -54,6 +143,7 @@ fn scalar_bootstrap_mean() {
     let summary: BootstrapSummary<f64> = Bootstrap::new(estimator)
         .n_boot(2000)
         .sampler(SamplingStrategy::Iid)
+        .seed(3)
         .run()
         .unwrap()
         .summarise();
@@ -117,6 +207,29 @@ fn bias_corrected_bootstrap() {
     assert!(summary.statistics.unwrap().stddev > 0.0);
 }
 
+#[test]
+fn bca_interval_on_skewed_mean() {
+    let data = [1.0, 2.0, 3.0, 4.0, 100.0];
+    let estimator = Estimator::new((0..data.len()).collect(), move |indices: &[usize]| {
+        Ok(indices.iter().map(|&i| data[i]).sum::<f64>() / indices.len() as f64)
+    });
+    let summary: BootstrapSummary<f64> = Bootstrap::new(estimator)
+        .n_boot(2000)
+        .seed(3)
+        .bca(true)
+        .run()
+        .unwrap()
+        .summarise();
+    let central = *summary.central.as_ref().unwrap();
+    let statistics = summary.statistics.unwrap();
+    let (percentile, bca) = (statistics.ci_95, statistics.ci_95_bca.unwrap());
+    assert!(bca.low < central && central < bca.high);
+    // Most replicas miss the outlier and fall below the mean, so BCa moves
+    // the interval up relative to the percentile interval.
+    assert!(bca.low >= percentile.low && bca.high >= percentile.high);
+    assert!(bca.low + bca.high > percentile.low + percentile.high);
+}
+
 #[test]
 fn handling_failures() {
     let n = 10;
@@ -141,17 +254,75 @@ fn handling_failures() {
     assert_eq!(statistics.mean, 1.0);
 }
 
+#[test]
+fn failed_resamples_recorded_on_request() {
+    let estimator = Estimator::new((0..10).collect(), move |indices: &[usize]| {
+        if indices[0].is_multiple_of(2) {
+            Err(EstimatorError::new("first index even"))
+        } else {
+            Ok(1.0)
+        }
+    });
+    let result = Bootstrap::new(estimator.clone())
+        .n_boot(100)
+        .seed(4)
+        .record_failures(true)
+        .run()
+        .unwrap();
+    let resamples = result.failed_resamples.as_ref().unwrap();
+    assert!(!resamples.is_empty());
+    assert_eq!(resamples.len(), result.failures.len());
+    for resample in resamples {
+        assert_eq!(resample.len(), 10);
+        assert!(resample[0].is_multiple_of(2));
+        assert!(estimator.apply(resample).is_err());
+    }
+
+    let plain = Bootstrap::new(estimator).n_boot(100).seed(4).run().unwrap();
+    assert!(plain.failed_resamples.is_none());
+}
+
+#[test]
+fn try_summarise_reports_total_failure() {
+    let estimator = Estimator::new((0..10).collect(), |_: &[usize]| -> Result<f64, _> {
+        Err(EstimatorError::new("always fails"))
+    });
+    let result = Bootstrap::new(estimator).n_boot(50).seed(1).run().unwrap();
+    match result.try_summarise() {
+        Err(BootstrapError::NoValidSamples {
+            n_boot,
+            failed_samples,
+        }) => {
+            assert_eq!(n_boot, 50);
+            assert_eq!(failed_samples, 50);
+        }
+        other => panic!("expected NoValidSamples, got {other:?}"),
+    }
+
+    let ok = Estimator::new((0..10).collect(), |ind: &[usize]| Ok(ind.len() as f64));
+    let summary: BootstrapSummary<f64> = Bootstrap::new(ok)
+        .n_boot(50)
+        .seed(1)
+        .run()
+        .unwrap()
+        .try_summarise()
+        .unwrap();
+    assert!(summary.statistics.is_some());
+}
+
 #[test]
 fn double_bootstrap() {
     let true_mean = 10.0;
     let true_std_dev = 2.0;
     let n_samples = 2000;
     let n_boot = 100;
-    let data = generate_data(n_samples, true_mean, true_std_dev);
+    let data = generate_data(n_samples, true_mean, true_std_dev, 11);
 
     // The outer estimator's closure builds and runs an inner bootstrap.
     // Because `Estimator<T>` is now a nameable, `Clone`able type, this
-    // pattern no longer requires `impl Fn` in an unnameable position.
+    // pattern no longer requires `impl Fn` in an unnameable position. The
+    // inner run stays on the outer worker's thread rather than nesting
+    // rayon tasks.
     let outer = Estimator::new((0..n_samples).collect(), move |indices: &[usize]| {
         let data = data.clone();
         let inner = Estimator::new(indices.to_owned(), move |idx: &[usize]| {
@@ -160,6 +331,7 @@ fn double_bootstrap() {
         let inner_summary: BootstrapSummary<f64> = Bootstrap::new(inner)
             .n_boot(n_boot)
             .sampler(SamplingStrategy::Iid)
+            .parallel(false)
             .run()
             .map_err(|e| EstimatorError::new(e.to_string()))?
             .summarise();
@@ -178,3 +350,90 @@ fn double_bootstrap() {
 
     assert_eq!(summary.n_boot, n_boot);
 }
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_statistic_gives_named_summaries() {
+    use booted::BootstrapStatistic;
+    use serde::Serialize;
+
+    #[derive(Debug, Clone, Serialize, BootstrapStatistic)]
+    struct Line {
+        slope: f64,
+        intercept: f64,
+    }
+
+    let x: Vec<f64> = (0..40).map(|i| i as f64 / 4.0).collect();
+    let y: Vec<f64> = generate_data(40, 0.0, 0.3, 5)
+        .iter()
+        .zip(&x)
+        .map(|(e, x)| 1.0 + 2.0 * x + e)
+        .collect();
+    let estimator = Estimator::new((0..x.len()).collect(), move |ind: &[usize]| {
+        let n = ind.len() as f64;
+        let mx = ind.iter().map(|&i| x[i]).sum::<f64>() / n;
+        let my = ind.iter().map(|&i| y[i]).sum::<f64>() / n;
+        let sxy: f64 = ind.iter().map(|&i| (x[i] - mx) * (y[i] - my)).sum();
+        let sxx: f64 = ind.iter().map(|&i| (x[i] - mx).powi(2)).sum();
+        let slope = sxy / sxx;
+        Ok(Line {
+            slope,
+            intercept: my - slope * mx,
+        })
+    });
+    let summary: BootstrapSummary<Line> = Bootstrap::new(estimator)
+        .n_boot(1000)
+        .seed(8)
+        .run()
+        .unwrap()
+        .summarise();
+    let stats = summary.statistics.as_ref().unwrap();
+    assert!(stats.slope.ci_95.low < 2.0 && 2.0 < stats.slope.ci_95.high);
+    assert!(stats.intercept.ci_95.low < 1.0 && 1.0 < stats.intercept.ci_95.high);
+    assert!(stats.slope.ci_95_basic.is_some());
+
+    let v: Value = serde_json::to_value(&summary).unwrap();
+    assert!(v["statistics"]["slope"]["stddev"].is_number());
+
+    use booted::Arithmetic;
+    let a = Line {
+        slope: 1.0,
+        intercept: 4.0,
+    };
+    let sum = a.add(&a.scale(2.0)).sub(&Line::zero(a.len()));
+    assert_eq!((sum.slope, sum.intercept, a.len()), (3.0, 12.0, 2));
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derived_statistic_keeps_vector_field_lengths() {
+    use booted::BootstrapStatistic;
+    use booted::jackknife::Jackknife;
+    use serde::Serialize;
+
+    #[derive(Debug, Clone, Serialize, BootstrapStatistic)]
+    struct Moments {
+        mean: f64,
+        powers: Vec<f64>,
+    }
+
+    let data: Vec<f64> = (0..30).map(|i| (i as f64 * 0.7).sin()).collect();
+    let est = Estimator::new((0..data.len()).collect(), move |ind| {
+        let n = ind.len() as f64;
+        let power = |k: i32| ind.iter().map(|&i| data[i].powi(k)).sum::<f64>() / n;
+        Ok(Moments {
+            mean: power(1),
+            powers: vec![power(1), power(2), power(3)],
+        })
+    });
+    let checked = Bootstrap::new(est.clone())
+        .n_boot(200)
+        .check_determinism(true)
+        .run()
+        .unwrap();
+    assert!(checked.warnings.is_empty());
+    let jack = Jackknife::new(est).run().unwrap();
+    assert_eq!(jack.mean.powers.len(), 3);
+    assert_eq!(jack.std_error.powers.len(), 3);
+    assert!((jack.std_error.powers[0] - jack.std_error.mean).abs() < 1e-12);
+}